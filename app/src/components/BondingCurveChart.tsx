@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import React, { useState, useEffect, useMemo } from 'react'
@@ -311,4 +310,3 @@ export default function BondingCurveChart({
     </Card>
   )
 }
-```
\ No newline at end of file
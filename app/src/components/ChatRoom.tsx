@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import { useState, useEffect, useRef } from 'react'
@@ -362,4 +361,3 @@ export default function ChatRoom({ roomId, creatorPublicKey }: ChatRoomProps) {
     </div>
   )
 }
-```
\ No newline at end of file
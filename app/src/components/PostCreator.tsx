@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import { useState, useRef } from 'react'
@@ -382,4 +381,3 @@ export default function PostCreator({ onPostCreated, className }: PostCreatorPro
     </Card>
   )
 }
-```
\ No newline at end of file
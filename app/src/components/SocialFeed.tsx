@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import { useState, useEffect } from 'react'
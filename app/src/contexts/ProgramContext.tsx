@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import React, { createContext, useContext, useEffect, useState, ReactNode } from 'react'
@@ -154,7 +153,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
     setIsLoading(true)
     try {
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), wallet.publicKey.toBuffer()],
+        [Buffer.from('user_profile'), wallet.publicKey.toBuffer()],
         PROGRAM_ID
       )
 
@@ -189,7 +188,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
 
     try {
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), authority.toBuffer()],
+        [Buffer.from('user_profile'), authority.toBuffer()],
         PROGRAM_ID
       )
 
@@ -214,7 +213,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
     setIsLoading(true)
     try {
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), wallet.publicKey.toBuffer()],
+        [Buffer.from('user_profile'), wallet.publicKey.toBuffer()],
         PROGRAM_ID
       )
 
@@ -249,7 +248,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
       const postKeypair = web3.Keypair.generate()
       
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), wallet.publicKey.toBuffer()],
+        [Buffer.from('user_profile'), wallet.publicKey.toBuffer()],
         PROGRAM_ID
       )
 
@@ -374,7 +373,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
     setIsLoading(true)
     try {
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), profileAuthority.toBuffer()],
+        [Buffer.from('user_profile'), profileAuthority.toBuffer()],
         PROGRAM_ID
       )
 
@@ -413,7 +412,7 @@ export function ProgramProvider({ children }: ProgramProviderProps) {
     setIsLoading(true)
     try {
       const [profilePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('profile'), profileAuthority.toBuffer()],
+        [Buffer.from('user_profile'), profileAuthority.toBuffer()],
         PROGRAM_ID
       )
 
@@ -1,4 +1,3 @@
-```tsx
 'use client'
 
 import React, { createContext, useContext, useEffect, useState, ReactNode } from 'react'
@@ -287,4 +286,3 @@ const WalletContextInner: React.FC<WalletContextInnerProps> = ({ children, conne
 }
 
 export default WalletContextProvider
-```
\ No newline at end of file
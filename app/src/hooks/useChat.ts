@@ -1,4 +1,3 @@
-```typescript
 import { useState, useEffect, useCallback, useRef } from 'react'
 import { useWallet, useConnection } from '@solana/wallet-adapter-react'
 import { PublicKey, Transaction, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js'
@@ -457,4 +456,3 @@ export const useChat = (options: UseChatOptions = {}) => {
 }
 
 export default useChat
-```
\ No newline at end of file
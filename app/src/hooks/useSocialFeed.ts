@@ -1,4 +1,3 @@
-```typescript
 import { useState, useEffect, useCallback, useMemo } from 'react'
 import { useConnection, useWallet } from '@solana/wallet-adapter-react'
 import { PublicKey, Connection } from '@solana/web3.js'
@@ -369,4 +368,3 @@ export function useSocialFeed(): UseSocialFeedReturn {
     createPost
   }
 }
-```
\ No newline at end of file
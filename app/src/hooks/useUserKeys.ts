@@ -1,4 +1,3 @@
-```typescript
 import { useState, useEffect, useCallback } from 'react'
 import { useWallet, useConnection } from '@solana/wallet-adapter-react'
 import { PublicKey, Keypair, SystemProgram, Transaction } from '@solana/web3.js'
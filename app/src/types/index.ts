@@ -1,4 +1,3 @@
-```typescript
 import { PublicKey } from '@solana/web3.js';
 
 // User and Profile Types
@@ -516,4 +515,3 @@ export interface FilterOptions {
   verified?: boolean;
   hasToken?: boolean;
 }
-```
\ No newline at end of file
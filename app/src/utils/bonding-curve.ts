@@ -1,4 +1,3 @@
-```typescript
 import { BN } from '@coral-xyz/anchor';
 
 // Constants for bonding curve calculations
@@ -323,4 +322,3 @@ export function calculateOptimalTradeSize(
   
   return optimal;
 }
-```
\ No newline at end of file
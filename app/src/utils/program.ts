@@ -1,4 +1,3 @@
-```typescript
 import { Program, AnchorProvider, web3, BN, IdlAccounts } from '@coral-xyz/anchor';
 import { Connection, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
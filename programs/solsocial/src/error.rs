@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -15,30 +14,111 @@ pub enum SolSocialError {
     #[msg("Post not found")]
     PostNotFound,
     
+    #[msg("Maximum number of pinned posts reached")]
+    MaxPinnedPostsExceeded,
+    
+    #[msg("Maximum number of pinned messages reached")]
+    MaxPinnedMessagesExceeded,
+    
     #[msg("Comment not found")]
     CommentNotFound,
     
+    #[msg("Message not found")]
+    MessageNotFound,
+    
+    #[msg("Invalid emoji")]
+    InvalidEmoji,
+    
+    #[msg("Already reacted to this message")]
+    AlreadyReacted,
+    
+    #[msg("Reaction not found")]
+    ReactionNotFound,
+    
+    #[msg("Reaction limit reached")]
+    ReactionLimitReached,
+    
+    #[msg("Not a member of this chat")]
+    NotChatMember,
+    
+    #[msg("Chat room is full")]
+    ChatRoomFull,
+    
+    #[msg("Transfer ownership of the chat room before leaving")]
+    SoleAdminCannotLeave,
+    
+    #[msg("Member is muted in this chat")]
+    MemberMuted,
+    
+    #[msg("Replies cannot be replied to")]
+    ReplyDepthExceeded,
+    
     #[msg("Invalid post content length")]
     InvalidPostContentLength,
     
     #[msg("Invalid comment content length")]
     InvalidCommentContentLength,
     
+    #[msg("Edit window has expired")]
+    EditWindowExpired,
+    
     #[msg("Invalid profile name length")]
     InvalidProfileNameLength,
     
     #[msg("Invalid profile bio length")]
     InvalidProfileBioLength,
     
+    #[msg("Invalid social link platform length")]
+    InvalidSocialPlatformLength,
+    
+    #[msg("Invalid social link URL length")]
+    InvalidSocialUrlLength,
+    
+    #[msg("A link for this platform already exists")]
+    SocialLinkAlreadyExists,
+    
+    #[msg("Social link not found")]
+    SocialLinkNotFound,
+    
+    #[msg("Maximum number of social links reached")]
+    MaxSocialLinksExceeded,
+    
+    #[msg("Media URL must use https:// or ipfs://")]
+    InvalidMediaUrl,
+    
+    #[msg("Media URL too long")]
+    MediaUrlTooLong,
+    
+    #[msg("Too many media files")]
+    TooManyMediaFiles,
+    
     #[msg("Cannot follow yourself")]
     CannotFollowSelf,
     
+    #[msg("This user has blocked you")]
+    UserBlocked,
+    
+    #[msg("Cannot block yourself")]
+    CannotBlockSelf,
+    
+    #[msg("Cannot mute yourself")]
+    CannotMuteSelf,
+    
     #[msg("Already following this user")]
     AlreadyFollowing,
     
     #[msg("Not following this user")]
     NotFollowing,
     
+    #[msg("This profile is private; send a follow request instead")]
+    FollowRequestRequired,
+    
+    #[msg("This profile is public; follow it directly")]
+    ProfileNotPrivate,
+    
+    #[msg("Follow request has expired")]
+    FollowRequestExpired,
+    
     #[msg("Cannot like your own post")]
     CannotLikeOwnPost,
     
@@ -48,6 +128,21 @@ pub enum SolSocialError {
     #[msg("Like not found")]
     LikeNotFound,
     
+    #[msg("Post has not been liked")]
+    NotLiked,
+    
+    #[msg("Already shared this post")]
+    AlreadyShared,
+    
+    #[msg("Reposts cannot be reposted")]
+    RepostDepthExceeded,
+    
+    #[msg("Already bookmarked this post")]
+    AlreadyBookmarked,
+    
+    #[msg("Post has not been bookmarked")]
+    NotBookmarked,
+    
     #[msg("Insufficient token balance")]
     InsufficientTokenBalance,
     
@@ -57,21 +152,39 @@ pub enum SolSocialError {
     #[msg("Cannot tip yourself")]
     CannotTipSelf,
     
+    #[msg("Batch tip amounts do not match the post accounts")]
+    BatchTipLengthMismatch,
+    
+    #[msg("Too many posts in one batch tip")]
+    TooManyBatchTips,
+    
+    #[msg("Batch tip account does not match the expected address")]
+    InvalidBatchTipAccount,
+    
     #[msg("Token price calculation failed")]
     TokenPriceCalculationFailed,
     
     #[msg("Invalid bonding curve parameters")]
     InvalidBondingCurveParameters,
     
+    #[msg("Curve parameters are locked once keys are in circulation")]
+    CurveParamsLocked,
+    
     #[msg("Token supply overflow")]
     TokenSupplyOverflow,
     
     #[msg("Token supply underflow")]
     TokenSupplyUnderflow,
     
+    #[msg("Supply exceeds maximum allowed")]
+    SupplyExceedsMax,
+    
     #[msg("Slippage tolerance exceeded")]
     SlippageToleranceExceeded,
     
+    #[msg("Transaction deadline has passed")]
+    TransactionExpired,
+    
     #[msg("Minimum purchase amount not met")]
     MinimumPurchaseAmountNotMet,
     
@@ -99,6 +212,22 @@ pub enum SolSocialError {
     #[msg("Creator cannot sell all shares")]
     CreatorCannotSellAllShares,
     
+    #[msg("Cannot close profile while tokens are held by others")]
+    OutstandingTokenSupply,
+    
+    #[msg("Key holding still has a balance")]
+    KeyHoldingNotEmpty,
+    #[msg("Cannot transfer keys to yourself")]
+    CannotTransferKeysToSelf,
+    #[msg("Key holdings belong to different subjects")]
+    KeyOwnerMismatch,
+    
+    #[msg("Creator keys have not been created yet")]
+    KeysNotCreated,
+    
+    #[msg("Creator keys have already been created")]
+    KeysAlreadyCreated,
+    
     #[msg("Invalid creator fee percentage")]
     InvalidCreatorFeePercentage,
     
@@ -177,6 +306,12 @@ pub enum SolSocialError {
     #[msg("Token mint not found")]
     TokenMintNotFound,
     
+    #[msg("Mint does not match the protocol payment mint")]
+    InvalidPaymentMint,
+    
+    #[msg("Account is not the platform treasury")]
+    InvalidTreasury,
+    
     #[msg("Invalid token mint authority")]
     InvalidTokenMintAuthority,
     
@@ -210,6 +345,12 @@ pub enum SolSocialError {
     #[msg("Reward distribution failed")]
     RewardDistributionFailed,
     
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    
+    #[msg("Vesting cliff must fall within a positive duration")]
+    InvalidVestingSchedule,
+    
     #[msg("Invalid staking period")]
     InvalidStakingPeriod,
     
@@ -273,6 +414,9 @@ pub enum SolSocialError {
     #[msg("Content moderation failed")]
     ContentModerationFailed,
     
+    #[msg("Invalid report status transition")]
+    InvalidReportTransition,
+    
     #[msg("Invalid content type")]
     InvalidContentType,
     
@@ -342,6 +486,15 @@ pub enum SolSocialError {
     #[msg("Notification delivery failed")]
     NotificationDeliveryFailed,
     
+    #[msg("Too many mentions in one post")]
+    TooManyMentions,
+    
+    #[msg("User mentioned more than once")]
+    DuplicateMention,
+    
+    #[msg("Notification account does not match the expected address")]
+    InvalidNotificationAccount,
+    
     #[msg("Push notification not supported")]
     PushNotificationNotSupported,
     
@@ -423,6 +576,9 @@ pub enum SolSocialError {
     #[msg("Invalid encryption key")]
     InvalidEncryptionKey,
     
+    #[msg("Recipient has not registered an encryption key")]
+    RecipientEncryptionKeyMissing,
+    
     #[msg("Key derivation failed")]
     KeyDerivationFailed,
     
@@ -0,0 +1,420 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateGlobalState<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUser<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", target.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Only used to derive the profile being verified
+    pub target: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorTokenFrozen<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Only used to derive the creator's profile
+    pub creator: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint = protocol_config.payment_mint,
+        associated_token::authority = protocol_config
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == protocol_config.payment_mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn require_platform_authority(authority: Pubkey, signer: Pubkey) -> Result<()> {
+    require_keys_eq!(signer, authority, SolSocialError::UnauthorizedAccess);
+    Ok(())
+}
+
+/// Every trading instruction checks this first so a single flag halts buys and sells.
+pub fn check_trading_open(global_state: &GlobalState) -> Result<()> {
+    require!(!global_state.is_paused, SolSocialError::TradingPaused);
+    Ok(())
+}
+
+pub fn update_fees(
+    ctx: Context<UpdateGlobalState>,
+    trading_fee_bps: u16,
+    creator_fee_bps: u16,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.update_fees(
+        ctx.accounts.authority.key(),
+        trading_fee_bps,
+        creator_fee_bps,
+        protocol_fee_bps,
+    )?;
+
+    emit!(FeesUpdated {
+        authority: ctx.accounts.authority.key(),
+        trading_fee_bps,
+        creator_fee_bps,
+        protocol_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_paused(ctx: Context<UpdateGlobalState>, paused: bool) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.set_paused(ctx.accounts.authority.key(), paused)?;
+
+    emit!(TradingPauseSet {
+        authority: ctx.accounts.authority.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_emergency_pause(ctx: Context<UpdateGlobalState>, paused: bool) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.set_emergency_pause(ctx.accounts.authority.key(), paused)?;
+
+    emit!(EmergencyPauseSet {
+        authority: ctx.accounts.authority.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_min_post_interval(ctx: Context<UpdateGlobalState>, min_post_interval_secs: i64) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+    require!(min_post_interval_secs >= 0, SolSocialError::InvalidTimestamp);
+
+    ctx.accounts.global_state.min_post_interval_secs = min_post_interval_secs;
+    Ok(())
+}
+
+pub fn set_oracle_authority(ctx: Context<UpdateGlobalState>, oracle_authority: Pubkey) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+
+    ctx.accounts.global_state.oracle_authority = oracle_authority;
+    Ok(())
+}
+
+pub fn set_message_price_boost(ctx: Context<UpdateGlobalState>, enabled: bool) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+
+    ctx.accounts.global_state.message_price_boost_enabled = enabled;
+    Ok(())
+}
+
+pub fn set_tip_bounds(ctx: Context<UpdateGlobalState>, min_tip: u64, max_tip: u64) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+    // A zero max keeps the built-in cap, so only a real max must clear the min
+    require!(max_tip == 0 || min_tip <= max_tip, SolSocialError::InvalidTipAmount);
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.min_tip = min_tip;
+    global_state.max_tip = max_tip;
+    Ok(())
+}
+
+pub fn set_platform_tip_fee(
+    ctx: Context<UpdateGlobalState>,
+    platform_tip_fee_bps: u16,
+    treasury: Pubkey,
+) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+    require!(
+        platform_tip_fee_bps <= GlobalState::MAX_PLATFORM_TIP_FEE_BPS,
+        SolSocialError::InvalidProtocolFeePercentage
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.platform_tip_fee_bps = platform_tip_fee_bps;
+    global_state.treasury = treasury;
+    Ok(())
+}
+
+pub fn set_max_post_length(ctx: Context<UpdateGlobalState>, max_post_length: u16) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+    require!(max_post_length > 0, SolSocialError::InvalidPostContentLength);
+
+    ctx.accounts.global_state.max_post_length = max_post_length;
+    Ok(())
+}
+
+pub fn propose_authority(ctx: Context<UpdateGlobalState>, new_authority: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.propose_authority(ctx.accounts.authority.key(), new_authority)?;
+
+    emit!(AuthorityProposed {
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_authority = global_state.authority;
+    global_state.accept_authority(ctx.accounts.new_authority.key())?;
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: ctx.accounts.new_authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.withdraw_fees(ctx.accounts.authority.key(), amount)?;
+
+    let protocol_seeds = &[b"protocol".as_ref(), &[ctx.accounts.protocol_config.bump]];
+    let signer = &[&protocol_seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_fee_account.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.protocol_config.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    emit!(ProtocolFeesWithdrawn {
+        authority: ctx.accounts.authority.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        remaining: global_state.total_fees_collected,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn verify_user(ctx: Context<VerifyUser>, verified: bool) -> Result<()> {
+    // Verification is platform-issued; a profile owner can never set it on themselves
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.is_verified = verified;
+
+    emit!(UserVerified {
+        user: ctx.accounts.target.key(),
+        verified,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn freeze_creator_token(ctx: Context<SetCreatorTokenFrozen>) -> Result<()> {
+    set_creator_token_frozen(ctx, true)
+}
+
+pub fn thaw_creator_token(ctx: Context<SetCreatorTokenFrozen>) -> Result<()> {
+    set_creator_token_frozen(ctx, false)
+}
+
+// The creator mint has no freeze authority, so the freeze is enforced by buy/sell
+fn set_creator_token_frozen(ctx: Context<SetCreatorTokenFrozen>, frozen: bool) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.authority.key())?;
+
+    ctx.accounts.creator_profile.token_frozen = frozen;
+
+    emit!(CreatorTokenFrozenSet {
+        creator: ctx.accounts.creator.key(),
+        frozen,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub authority: Pubkey,
+    pub trading_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingPauseSet {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyPauseSet {
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserVerified {
+    pub user: Pubkey,
+    pub verified: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorTokenFrozenSet {
+    pub creator: Pubkey,
+    pub frozen: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authority_can_verify() {
+        let authority = Pubkey::new_unique();
+        assert!(require_platform_authority(authority, authority).is_ok());
+    }
+
+    #[test]
+    fn test_non_authority_cannot_verify() {
+        let authority = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        assert_eq!(
+            require_platform_authority(authority, user).unwrap_err(),
+            error!(SolSocialError::UnauthorizedAccess)
+        );
+    }
+
+    #[test]
+    fn test_trades_blocked_while_paused() {
+        let authority = Pubkey::new_unique();
+        let mut global_state = GlobalState::default();
+        global_state.initialize(authority, 255).unwrap();
+        assert!(check_trading_open(&global_state).is_ok());
+
+        global_state.set_paused(authority, true).unwrap();
+        assert_eq!(
+            check_trading_open(&global_state).unwrap_err(),
+            error!(SolSocialError::TradingPaused)
+        );
+
+        global_state.set_paused(authority, false).unwrap();
+        assert!(check_trading_open(&global_state).is_ok());
+    }
+}
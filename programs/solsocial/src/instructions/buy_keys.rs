@@ -1,31 +1,58 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::instructions::admin::check_trading_open;
+use crate::utils::bonding_curve::{calculate_buy_cost_with_mode, calculate_price_impact_with_mode};
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, max_cost: u64)]
 pub struct BuyKeys<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"user", subject.key().as_ref()],
+        seeds = [b"user_profile", subject.key().as_ref()],
         bump = user_account.bump,
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
+    
+    /// Buyer's own profile; `sell_keys` draws the same totals back down
+    #[account(
+        mut,
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump = buyer_account.bump,
+    )]
+    pub buyer_account: Account<'info, UserProfile>,
     
     /// CHECK: This is the subject whose keys are being bought
     pub subject: AccountInfo<'info>,
     
+    /// Buyer's holding in this subject; the same account `sell_keys` draws down
     #[account(
-        mut,
+        init_if_needed,
+        payer = buyer,
+        space = KeyHolding::LEN,
         seeds = [b"keys", subject.key().as_ref(), buyer.key().as_ref()],
         bump,
     )]
-    pub key_account: Account<'info, KeyAccount>,
+    pub key_holding: Account<'info, KeyHolding>,
+    
+    /// Subject's key stats; `holders_count` follows first buys and full exits
+    #[account(
+        mut,
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
     
     #[account(
         mut,
@@ -55,36 +82,112 @@ pub struct BuyKeys<'info> {
     )]
     pub protocol_fee_account: Account<'info, TokenAccount>,
     
+    /// CHECK: PDA that owns the bonding-curve reserve for this subject
+    #[account(
+        seeds = [b"reserve", subject.key().as_ref()],
+        bump,
+    )]
+    pub reserve_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = protocol_config.payment_mint,
+        associated_token::authority = reserve_authority,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    /// Present when the buyer signed up through a referral
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref(), buyer.key().as_ref()],
+        bump = referral.bump,
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+    
+    #[account(mut)]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TokenPrice::LEN,
+        seeds = [b"token_price", subject.key().as_ref()],
+        bump,
+    )]
+    pub token_price: Account<'info, TokenPrice>,
+    
+    /// Subject's curve; created with the default curve if they never configured one
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = CreatorToken::LEN,
+        seeds = [b"creator_token", subject.key().as_ref()],
+        bump,
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+    
+    /// Holder reward accumulator for this creator; buys settle against it
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = RewardDistributionCursor::LEN,
+        seeds = [b"reward_cursor", subject.key().as_ref()],
+        bump,
+    )]
+    pub reward_cursor: Account<'info, RewardDistributionCursor>,
+    
+    /// Buyer's holder record for this creator; created on their first buy
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TokenHolder::LEN,
+        seeds = [b"token_holder", subject.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub token_holder: Account<'info, TokenHolder>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
+pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64, max_cost: u64) -> Result<()> {
     let user_account = &mut ctx.accounts.user_account;
-    let key_account = &mut ctx.accounts.key_account;
-    let protocol_config = &ctx.accounts.protocol_config;
+    let key_holding = &mut ctx.accounts.key_holding;
     
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    check_trading_open(&ctx.accounts.global_state)?;
     require!(amount > 0, SolSocialError::InvalidAmount);
     require!(user_account.is_active, SolSocialError::UserNotActive);
+    user_account.require_not_frozen()?;
     
     // Calculate current supply and price
-    let current_supply = user_account.keys_supply;
-    let price = get_price(current_supply, amount)?;
+    let current_supply = user_account.token_supply;
+    check_keys_created(current_supply)?;
+    check_supply_cap(current_supply, amount, user_account.max_supply)?;
+    let creator_token = &mut ctx.accounts.creator_token;
+    creator_token.ensure_initialized(
+        ctx.accounts.subject.key(),
+        Clock::get()?.unix_timestamp,
+        ctx.bumps.creator_token,
+    );
+    let price = get_price(current_supply, amount, creator_token)?;
     
     require!(price > 0, SolSocialError::InvalidPrice);
     
-    // Calculate fees
-    let protocol_fee = price
-        .checked_mul(protocol_config.protocol_fee_percent as u64)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let subject_fee = price
-        .checked_mul(protocol_config.subject_fee_percent as u64)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // Fees are charged at the rates the admin set through `update_fees`, with the
+    // protocol's cut discounted by the buyer's volume tier
+    let price_impact = calculate_price_impact_with_mode(
+        current_supply,
+        amount,
+        true,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+    )?;
+    let (protocol_fee, subject_fee) = ctx.accounts.global_state.key_trade_fees(
+        price,
+        price_impact,
+        ctx.accounts.buyer_account.total_volume,
+    )?;
     
     let total_cost = price
         .checked_add(protocol_fee)
@@ -92,16 +195,19 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
         .checked_add(subject_fee)
         .ok_or(SolSocialError::MathOverflow)?;
     
-    // Transfer payment from buyer to subject
-    let transfer_to_subject_ctx = CpiContext::new(
+    // Abort before any transfer if the price moved past what the buyer signed for
+    check_max_cost(total_cost, max_cost)?;
+    
+    // Deposit the curve price into the reserve so sells can be paid out of it
+    let transfer_to_reserve_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
             from: ctx.accounts.buyer_token_account.to_account_info(),
-            to: ctx.accounts.subject_token_account.to_account_info(),
+            to: ctx.accounts.reserve_vault.to_account_info(),
             authority: ctx.accounts.buyer.to_account_info(),
         },
     );
-    token::transfer(transfer_to_subject_ctx, price)?;
+    token::transfer(transfer_to_reserve_ctx, price)?;
     
     // Transfer protocol fee
     if protocol_fee > 0 {
@@ -129,20 +235,104 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
         token::transfer(transfer_subject_fee_ctx, subject_fee)?;
     }
     
-    // Update key account
-    key_account.amount = key_account.amount
-        .checked_add(amount)
-        .ok_or(SolSocialError::MathOverflow)?;
-    key_account.last_trade_timestamp = Clock::get()?.unix_timestamp;
+    // Pay the referrer a one-time bonus out of the protocol treasury
+    let mut referral_bonus = 0;
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        let referrer_token_account = ctx.accounts.referrer_token_account
+            .as_ref()
+            .ok_or(SolSocialError::InvalidReferralCode)?;
+        require_keys_eq!(
+            referrer_token_account.owner,
+            referral.referrer,
+            SolSocialError::InvalidTokenAccountOwner
+        );
+        require_keys_eq!(
+            referrer_token_account.mint,
+            ctx.accounts.protocol_config.payment_mint,
+            SolSocialError::InvalidReferralCode
+        );
+
+        let bonus = referral.take_bonus(protocol_fee)?;
+        if bonus > 0 {
+            let protocol_seeds = &[b"protocol".as_ref(), &[ctx.accounts.protocol_config.bump]];
+            let signer = &[&protocol_seeds[..]];
+            let transfer_bonus_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.protocol_fee_account.to_account_info(),
+                    to: referrer_token_account.to_account_info(),
+                    authority: ctx.accounts.protocol_config.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_bonus_ctx, bonus)?;
+        }
+        referral_bonus = bonus;
+    }
+    
+    // Update key holding
+    key_holding.ensure_initialized(
+        ctx.accounts.buyer.key(),
+        ctx.accounts.subject.key(),
+        ctx.bumps.key_holding,
+    );
+    key_holding.record_buy(amount, total_cost, Clock::get()?.unix_timestamp)?;
+    
+    let creator_token = &ctx.accounts.creator_token;
+    ctx.accounts.user_keys.update_after_trade(
+        i64::try_from(amount).map_err(|_| SolSocialError::MathOverflow)?,
+        true,
+        key_holding.amount,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    let reward_cursor = &mut ctx.accounts.reward_cursor;
+    reward_cursor.profile_owner = ctx.accounts.subject.key();
+    reward_cursor.bump = ctx.bumps.reward_cursor;
+    let now = Clock::get()?.unix_timestamp;
+    let token_holder = &mut ctx.accounts.token_holder;
+    token_holder.ensure_initialized(
+        ctx.accounts.buyer.key(),
+        ctx.accounts.subject.key(),
+        user_account.token_mint,
+        reward_cursor.rewards_per_token,
+        now,
+        ctx.bumps.token_holder,
+    );
+    token_holder.record_buy(
+        amount,
+        price,
+        reward_cursor.rewards_per_token,
+        current_supply,
+        now,
+    )?;
     
     // Update user account supply
-    user_account.keys_supply = user_account.keys_supply
+    ctx.accounts.creator_token.record_trade(amount, true)?;
+    user_account.token_supply = user_account.token_supply
         .checked_add(amount)
         .ok_or(SolSocialError::MathOverflow)?;
     user_account.total_volume = user_account.total_volume
         .checked_add(total_cost)
         .ok_or(SolSocialError::MathOverflow)?;
-    user_account.last_activity_timestamp = Clock::get()?.unix_timestamp;
+    user_account.last_active = Clock::get()?.unix_timestamp;
+    
+    // Update buyer's total keys held and volume
+    let buyer_account = &mut ctx.accounts.buyer_account;
+    buyer_account.total_keys_held = buyer_account.total_keys_held
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    buyer_account.total_volume = buyer_account.total_volume
+        .checked_add(total_cost)
+        .ok_or(SolSocialError::MathOverflow)?;
+    
+    // Roll the 24h price/volume window at the average price paid per key
+    let token_price = &mut ctx.accounts.token_price;
+    token_price.mint = ctx.accounts.subject.key();
+    token_price.bump = ctx.bumps.token_price;
+    token_price.roll_price_window(price / amount, price, Clock::get()?.unix_timestamp)?;
     
     // Update protocol stats
     let protocol_config = &mut ctx.accounts.protocol_config;
@@ -152,6 +342,11 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
     protocol_config.total_fees_collected = protocol_config.total_fees_collected
         .checked_add(protocol_fee)
         .ok_or(SolSocialError::MathOverflow)?;
+    // Whatever the referrer didn't take stays withdrawable by the protocol
+    let retained_fee = protocol_fee
+        .checked_sub(referral_bonus)
+        .ok_or(SolSocialError::MathOverflow)?;
+    ctx.accounts.global_state.add_fees(retained_fee)?;
     
     emit!(KeysBought {
         buyer: ctx.accounts.buyer.key(),
@@ -160,55 +355,34 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
         price,
         protocol_fee,
         subject_fee,
-        supply_after: user_account.keys_supply,
+        supply_after: user_account.token_supply,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
     Ok(())
 }
 
-fn get_price(supply: u64, amount: u64) -> Result<u64> {
-    if supply == 0 && amount == 1 {
-        return Ok(0); // First key is free
-    }
-    
-    let sum1 = if supply == 0 {
-        0
-    } else {
-        supply
-            .checked_sub(1)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_mul(supply)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_mul(supply.checked_mul(2).ok_or(SolSocialError::MathOverflow)?.checked_sub(1).ok_or(SolSocialError::MathOverflow)?)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_div(6)
-            .ok_or(SolSocialError::MathOverflow)?
-    };
-    
-    let sum2 = supply
+fn get_price(supply: u64, amount: u64, curve: &CreatorToken) -> Result<u64> {
+    calculate_buy_cost_with_mode(supply, amount, curve.price_curve_type, &curve.curve_params())
+}
+
+/// The genesis key is minted free to the creator by `create_keys`; no buy may come before it.
+fn check_keys_created(supply: u64) -> Result<()> {
+    require!(supply >= 1, SolSocialError::KeysNotCreated);
+    Ok(())
+}
+
+fn check_supply_cap(supply: u64, amount: u64, max_supply: u64) -> Result<()> {
+    let new_supply = supply
         .checked_add(amount)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_sub(1)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let sum2_squared = sum2
-        .checked_mul(sum2.checked_add(1).ok_or(SolSocialError::MathOverflow)?)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_mul(sum2.checked_mul(2).ok_or(SolSocialError::MathOverflow)?.checked_add(1).ok_or(SolSocialError::MathOverflow)?)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(6)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let price_in_wei = sum2_squared
-        .checked_sub(sum1)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_mul(1_000_000) // Convert to micro-lamports for precision
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(16000)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    Ok(price_in_wei)
+    require!(new_supply <= max_supply, SolSocialError::SupplyExceedsMax);
+    Ok(())
+}
+
+fn check_max_cost(total_cost: u64, max_cost: u64) -> Result<()> {
+    require!(total_cost <= max_cost, SolSocialError::SlippageToleranceExceeded);
+    Ok(())
 }
 
 #[event]
@@ -222,4 +396,84 @@ pub struct KeysBought {
     pub supply_after: u64,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::create_keys::genesis_key_supply;
+    use crate::utils::bonding_curve::CURVE_TYPE_EXPONENTIAL;
+
+    fn default_curve() -> CreatorToken {
+        let mut curve = CreatorToken::default();
+        curve.ensure_initialized(Pubkey::new_unique(), 0, 255);
+        curve
+    }
+
+    #[test]
+    fn test_buy_before_key_creation_fails() {
+        assert!(check_keys_created(0).is_err());
+        assert!(check_keys_created(1).is_ok());
+    }
+
+    #[test]
+    fn test_buy_after_create_keys() {
+        let supply = genesis_key_supply(0).unwrap();
+        assert!(check_keys_created(supply).is_ok());
+        // The second key costs the base price; the curve term rounds away this low
+        assert_eq!(get_price(supply, 1, &default_curve()).unwrap(), 1_000);
+        // Creating again can't reset the supply buys have priced against
+        assert_eq!(
+            genesis_key_supply(supply).unwrap_err(),
+            SolSocialError::KeysAlreadyCreated.into()
+        );
+    }
+
+    #[test]
+    fn test_max_cost_rejects_supply_increase_after_quote() {
+        let quoted = get_price(10, 5, &default_curve()).unwrap();
+        // Another buyer lands first and pushes supply up before execution
+        let executed = get_price(15, 5, &default_curve()).unwrap();
+
+        assert!(executed > quoted);
+        assert!(check_max_cost(executed, quoted).is_err());
+    }
+
+    #[test]
+    fn test_supply_cap_rejects_buy_past_max() {
+        let max_supply = 10;
+        let mut supply = 0;
+
+        while supply < max_supply {
+            assert!(check_supply_cap(supply, 1, max_supply).is_ok());
+            supply += 1;
+        }
+
+        assert!(check_supply_cap(supply, 1, max_supply).is_err());
+        assert!(check_supply_cap(5, 6, max_supply).is_err());
+    }
+
+    #[test]
+    fn test_max_cost_allows_unchanged_quote() {
+        let quoted = get_price(10, 5, &default_curve()).unwrap();
+        assert!(check_max_cost(quoted, quoted).is_ok());
+    }
+
+    #[test]
+    fn test_creator_curve_sets_the_buy_cost() {
+        let default = default_curve();
+        // 1_000 * 5 + (15^3 - 10^3) / 3_000_000 rounds the curve term away
+        assert_eq!(get_price(10, 5, &default).unwrap(), 5_000);
+
+        let mut steep = default_curve();
+        steep.base_price = 10_000;
+        steep.price_multiplier = 1;
+        // 10_000 * 5 + (3_375 - 1_000) / 3
+        assert_eq!(get_price(10, 5, &steep).unwrap(), 50_791);
+
+        let mut exponential = default_curve();
+        exponential.price_curve_type = CURVE_TYPE_EXPONENTIAL;
+        exponential.price_multiplier = 10_000; // each key doubles the price
+        // 1_000 * (2^10 + ... + 2^14)
+        assert_eq!(get_price(10, 5, &exponential).unwrap(), 31_744_000);
+    }
+}
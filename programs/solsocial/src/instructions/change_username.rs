@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(new_username: String)]
+pub struct ChangeUsername<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Released on success so the old name can be claimed again
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"username", user_profile.username.as_bytes()],
+        bump = old_username_registry.bump,
+        constraint = old_username_registry.owner == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub old_username_registry: Account<'info, UsernameRegistry>,
+
+    /// Fails to initialize when another profile already holds the name
+    #[account(
+        init,
+        payer = user,
+        space = UsernameRegistry::LEN,
+        seeds = [b"username", new_username.as_bytes()],
+        bump
+    )]
+    pub new_username_registry: Account<'info, UsernameRegistry>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn change_username(ctx: Context<ChangeUsername>, new_username: String) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+
+    let old_username = user_profile.username.clone();
+    apply_rename(&ctx.accounts.global_state, user_profile, new_username, clock.unix_timestamp)?;
+
+    let new_username_registry = &mut ctx.accounts.new_username_registry;
+    new_username_registry.owner = ctx.accounts.user.key();
+    new_username_registry.username = user_profile.username.clone();
+    new_username_registry.created_at = clock.unix_timestamp;
+    new_username_registry.bump = ctx.bumps.new_username_registry;
+
+    emit!(UsernameChanged {
+        user: ctx.accounts.user.key(),
+        old_username,
+        new_username: user_profile.username.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn apply_rename(
+    global_state: &GlobalState,
+    user_profile: &mut UserProfile,
+    new_username: String,
+    now: i64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    user_profile.rename(new_username, now)
+}
+
+#[event]
+pub struct UsernameChanged {
+    pub user: Pubkey,
+    pub old_username: String,
+    pub new_username: String,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut user_profile = UserProfile { username: "alice".to_string(), ..Default::default() };
+        assert_eq!(
+            apply_rename(&global_state, &mut user_profile, "alice2".to_string(), 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(user_profile.username, "alice");
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user_stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+    let user_stats = &mut ctx.accounts.user_stats;
+    record_check_in(&ctx.accounts.global_state, user_stats, ctx.accounts.user.key(), ctx.bumps.user_stats)?;
+
+    emit!(CheckedIn {
+        user: ctx.accounts.user.key(),
+        streak_days: user_stats.streak_days,
+        daily_active_days: user_stats.daily_active_days,
+        timestamp: user_stats.last_active,
+    });
+
+    Ok(())
+}
+
+/// The first check-in creates the stats; later ones extend the streak.
+fn record_check_in(global_state: &GlobalState, user_stats: &mut UserStats, user: Pubkey, bump: u8) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    if user_stats.user == Pubkey::default() {
+        user_stats.initialize(user, bump)
+    } else {
+        user_stats.update_activity()
+    }
+}
+
+#[event]
+pub struct CheckedIn {
+    pub user: Pubkey,
+    pub streak_days: u64,
+    pub daily_active_days: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_in_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut user_stats = UserStats::default();
+        assert_eq!(
+            record_check_in(&global_state, &mut user_stats, Pubkey::new_unique(), 255).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(user_stats.user, Pubkey::default());
+    }
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::revenue_share::record_holder_payout;
+
+#[derive(Accounts)]
+pub struct ClaimHolderRewards<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_holder", token_holder.profile_owner.as_ref(), holder.key().as_ref()],
+        bump = token_holder.bump,
+        constraint = token_holder.holder == holder.key() @ SolSocialError::UnauthorizedAccess,
+    )]
+    pub token_holder: Account<'info, TokenHolder>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_cursor", token_holder.profile_owner.as_ref()],
+        bump = reward_cursor.bump,
+    )]
+    pub reward_cursor: Account<'info, RewardDistributionCursor>,
+    
+    #[account(address = token_holder.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that signs for the reward vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        token::mint = token_holder.token_mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ SolSocialError::InvalidTokenAccountOwner,
+        constraint = holder_token_account.mint == vault_token_account.mint @ SolSocialError::InvalidTokenMint,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_holder_rewards(ctx: Context<ClaimHolderRewards>) -> Result<()> {
+    let token_holder = &mut ctx.accounts.token_holder;
+    let reward_cursor = &mut ctx.accounts.reward_cursor;
+    let rewards_per_token = reward_cursor.rewards_per_token;
+    
+    // Checkpoint moves before the transfer, so a second claim in the same slot owes nothing
+    let reward = token_holder.take_rewards(rewards_per_token, ctx.accounts.token_mint.supply)?;
+    check_claim(&ctx.accounts.global_state, reward, ctx.accounts.vault_token_account.amount)?;
+    record_holder_payout(reward_cursor, reward)?;
+    
+    token_holder.updated_at = Clock::get()?.unix_timestamp;
+    
+    let vault_authority_seeds = &[
+        b"vault_authority".as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let signer_seeds = &[&vault_authority_seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.holder_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, reward)?;
+    
+    emit!(HolderRewardsClaimed {
+        holder: ctx.accounts.holder.key(),
+        profile_owner: token_holder.profile_owner,
+        amount: reward,
+        rewards_per_token,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn check_claim(global_state: &GlobalState, reward: u64, vault_balance: u64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(reward > 0, SolSocialError::NoRewardsToClaim);
+    require!(vault_balance >= reward, SolSocialError::InsufficientVaultBalance);
+    Ok(())
+}
+
+#[event]
+pub struct HolderRewardsClaimed {
+    pub holder: Pubkey,
+    pub profile_owner: Pubkey,
+    pub amount: u64,
+    pub rewards_per_token: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        assert!(check_claim(&global_state, 500, 1_000).is_ok());
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            check_claim(&global_state, 500, 1_000).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CloseUser<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_token", user.key().as_ref()],
+        bump
+    )]
+    pub user_token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"user_token_vault", user.key().as_ref()],
+        bump
+    )]
+    pub user_token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"social_stats", user.key().as_ref()],
+        bump = social_stats.bump
+    )]
+    pub social_stats: Account<'info, SocialStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn close_user(ctx: Context<CloseUser>) -> Result<()> {
+    let vault_amount = ctx.accounts.user_token_vault.amount;
+    check_can_close(&ctx.accounts.global_state, ctx.accounts.user_token_mint.supply, vault_amount)?;
+
+    let user_key = ctx.accounts.user.key();
+    let seeds = &[
+        b"user_profile".as_ref(),
+        user_key.as_ref(),
+        &[ctx.accounts.user_profile.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Burn the unsold supply so the vault can be closed
+    if vault_amount > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.user_token_mint.to_account_info(),
+                    from: ctx.accounts.user_token_vault.to_account_info(),
+                    authority: ctx.accounts.user_profile.to_account_info(),
+                },
+                signer,
+            ),
+            vault_amount,
+        )?;
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.user_token_vault.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user_profile.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    // The SPL mint itself cannot be closed; with zero supply and its authority
+    // (the profile PDA) gone, no further tokens can ever be minted.
+    emit!(UserClosed {
+        user: user_key,
+        token_mint: ctx.accounts.user_token_mint.key(),
+        burned: vault_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Tokens outside the vault belong to holders; a profile with holders can't be closed.
+fn check_can_close(global_state: &GlobalState, mint_supply: u64, vault_amount: u64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    let circulating_supply = mint_supply
+        .checked_sub(vault_amount)
+        .ok_or(SolSocialError::TokenSupplyMismatch)?;
+    require!(circulating_supply == 0, SolSocialError::OutstandingTokenSupply);
+    Ok(())
+}
+
+#[event]
+pub struct UserClosed {
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub burned: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_close_with_all_supply_in_vault() {
+        assert!(check_can_close(&GlobalState::default(), 1_000_000, 1_000_000).is_ok());
+        assert!(check_can_close(&GlobalState::default(), 0, 0).is_ok());
+    }
+
+    #[test]
+    fn test_close_rejected_with_outstanding_supply() {
+        assert!(check_can_close(&GlobalState::default(), 1_000_000, 999_999).is_err());
+    }
+
+    #[test]
+    fn test_close_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            check_can_close(&global_state, 0, 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
@@ -30,21 +29,21 @@ pub struct CreateChat<'info> {
         mut,
         seeds = [b"user_profile", creator.key().as_ref()],
         bump,
-        constraint = user_profile.owner == creator.key() @ SolSocialError::UnauthorizedAccess
+        constraint = user_profile.authority == creator.key() @ SolSocialError::UnauthorizedAccess
     )]
     pub user_profile: Account<'info, UserProfile>,
 
     #[account(
         mut,
         constraint = creator_token_account.owner == creator.key() @ SolSocialError::InvalidTokenAccount,
-        constraint = creator_token_account.mint == user_profile.social_token_mint @ SolSocialError::InvalidTokenMint
+        constraint = creator_token_account.mint == user_profile.token_mint @ SolSocialError::InvalidTokenMint
     )]
     pub creator_token_account: Account<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = creator,
-        associated_token::mint = user_profile.social_token_mint,
+        associated_token::mint = user_profile.token_mint,
         associated_token::authority = chat
     )]
     pub chat_token_vault: Account<'info, TokenAccount>,
@@ -52,6 +51,12 @@ pub struct CreateChat<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
@@ -67,10 +72,7 @@ pub fn create_chat(
     entry_fee: u64,
     max_members: u32,
 ) -> Result<()> {
-    require!(chat_id.len() <= 32, SolSocialError::ChatIdTooLong);
-    require!(name.len() <= 64, SolSocialError::ChatNameTooLong);
-    require!(description.len() <= 256, SolSocialError::ChatDescriptionTooLong);
-    require!(max_members > 0 && max_members <= 1000, SolSocialError::InvalidMaxMembers);
+    check_chat_params(&ctx.accounts.global_state, &chat_id, &name, &description, max_members)?;
 
     let chat = &mut ctx.accounts.chat;
     let creator_membership = &mut ctx.accounts.creator_membership;
@@ -89,7 +91,7 @@ pub fn create_chat(
     chat.created_at = Clock::get()?.unix_timestamp;
     chat.last_activity = Clock::get()?.unix_timestamp;
     chat.is_active = true;
-    chat.social_token_mint = user_profile.social_token_mint;
+    chat.social_token_mint = user_profile.token_mint;
     chat.token_vault = ctx.accounts.chat_token_vault.key();
     chat.bump = ctx.bumps.chat;
 
@@ -134,6 +136,21 @@ pub fn create_chat(
     Ok(())
 }
 
+fn check_chat_params(
+    global_state: &GlobalState,
+    chat_id: &str,
+    name: &str,
+    description: &str,
+    max_members: u32,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(chat_id.len() <= 32, SolSocialError::ChatIdTooLong);
+    require!(name.len() <= 64, SolSocialError::ChatNameTooLong);
+    require!(description.len() <= 256, SolSocialError::ChatDescriptionTooLong);
+    require!(max_members > 0 && max_members <= 1000, SolSocialError::InvalidMaxMembers);
+    Ok(())
+}
+
 #[event]
 pub struct ChatCreatedEvent {
     pub chat: Pubkey,
@@ -145,4 +162,20 @@ pub struct ChatCreatedEvent {
     pub max_members: u32,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_chat_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        assert!(check_chat_params(&global_state, "general", "General", "", 100).is_ok());
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            check_chat_params(&global_state, "general", "General", "", 100).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use crate::state::*;
@@ -60,6 +59,13 @@ pub struct CreateKeys<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
 
+    #[account(
+        mut,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = creator_profile.bump,
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
@@ -122,6 +128,10 @@ pub fn handler(ctx: Context<CreateKeys>, name: String) -> Result<()> {
     user_keys.total_supply = initial_supply;
     user_keys.holders_count = 1;
 
+    // buy_keys and sell_keys price off the profile's supply, counted in whole keys
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.token_supply = genesis_key_supply(creator_profile.token_supply)?;
+
     // Emit event
     emit!(KeysCreated {
         creator: ctx.accounts.creator.key(),
@@ -136,6 +146,12 @@ pub fn handler(ctx: Context<CreateKeys>, name: String) -> Result<()> {
     Ok(())
 }
 
+/// The creator's free first key; keys can only be created once per profile.
+pub(crate) fn genesis_key_supply(token_supply: u64) -> Result<u64> {
+    require!(token_supply == 0, SolSocialError::KeysAlreadyCreated);
+    Ok(1)
+}
+
 #[event]
 pub struct KeysCreated {
     pub creator: Pubkey,
@@ -146,4 +162,3 @@ pub struct KeysCreated {
     pub initial_supply: u64,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
@@ -1,17 +1,18 @@
-```rust
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::system_program::{self, CreateAccount};
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::*;
 
 #[derive(Accounts)]
-#[instruction(content: String)]
+#[instruction(content: String, media_urls: Vec<String>)]
 pub struct CreatePost<'info> {
     #[account(
         init,
         payer = author,
-        space = Post::LEN + content.len() + 8,
+        space = Post::space_for(content.len(), &media_urls),
         seeds = [
             b"post",
             author.key().as_ref(),
@@ -21,11 +22,20 @@ pub struct CreatePost<'info> {
     )]
     pub post: Account<'info, Post>,
 
+    #[account(
+        init_if_needed,
+        payer = author,
+        space = RecentPostHash::LEN,
+        seeds = [b"post_hash", author.key().as_ref(), hash(content.as_bytes()).as_ref()],
+        bump
+    )]
+    pub recent_post_hash: Account<'info, RecentPostHash>,
+
     #[account(
         mut,
         seeds = [b"user_profile", author.key().as_ref()],
         bump = user_profile.bump,
-        constraint = user_profile.owner == author.key() @ SolSocialError::UnauthorizedUser
+        constraint = user_profile.authority == author.key() @ SolSocialError::UnauthorizedUser
     )]
     pub user_profile: Account<'info, UserProfile>,
 
@@ -36,6 +46,13 @@ pub struct CreatePost<'info> {
     )]
     pub social_token: Account<'info, SocialToken>,
 
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
         associated_token::mint = social_token.mint,
@@ -50,6 +67,14 @@ pub struct CreatePost<'info> {
     )]
     pub token_vault: Account<'info, TokenAccount>,
 
+    /// Keeps the daily streak going when supplied
+    #[account(
+        mut,
+        seeds = [b"user_stats", author.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Option<Account<'info, UserStats>>,
+
     #[account(mut)]
     pub author: Signer<'info>,
 
@@ -58,13 +83,42 @@ pub struct CreatePost<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<CreatePost>, content: String, media_urls: Vec<String>) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreatePost<'info>>,
+    content: String,
+    media_urls: Vec<String>,
+    mentions: Vec<Pubkey>,
+    is_nsfw: bool,
+) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     let clock = Clock::get()?;
     
     // Validate content length
     require!(content.len() > 0, SolSocialError::EmptyContent);
-    require!(content.len() <= 2000, SolSocialError::ContentTooLong);
-    require!(media_urls.len() <= 10, SolSocialError::TooManyMediaFiles);
+    ctx.accounts.global_state.check_post_length(&content)?;
+    Post::validate_media_urls(&media_urls)?;
+    Post::check_nsfw_allowed(is_nsfw, ctx.accounts.user_profile.blocks_nsfw)?;
+
+    // Mentions are parsed client-side; each needs its notification PDA in remaining_accounts
+    Notification::validate_mentions(&mentions)?;
+    require!(
+        ctx.remaining_accounts.len() == mentions.len(),
+        SolSocialError::InvalidNotificationAccount
+    );
+
+    ctx.accounts.global_state.check_post_interval(
+        ctx.accounts.user_profile.last_post_timestamp,
+        clock.unix_timestamp,
+    )?;
+
+    // Reject the same content from the same author within the duplicate window
+    let recent_post_hash = &mut ctx.accounts.recent_post_hash;
+    recent_post_hash.record_post(
+        ctx.accounts.author.key(),
+        hash(content.as_bytes()).to_bytes(),
+        clock.unix_timestamp,
+    )?;
+    recent_post_hash.bump = ctx.bumps.recent_post_hash;
 
     // Calculate post creation cost based on content length and media
     let base_cost = 1_000_000; // 0.001 tokens
@@ -103,6 +157,7 @@ pub fn handler(ctx: Context<CreatePost>, content: String, media_urls: Vec<String
     post.is_pinned = false;
     post.is_deleted = false;
     post.engagement_score = 0;
+    post.is_nsfw = is_nsfw;
     post.bump = ctx.bumps.post;
 
     // Update user profile stats
@@ -110,21 +165,41 @@ pub fn handler(ctx: Context<CreatePost>, content: String, media_urls: Vec<String
     user_profile.posts_count = user_profile.posts_count.checked_add(1).unwrap();
     user_profile.last_post_timestamp = clock.unix_timestamp;
 
+    if let Some(user_stats) = ctx.accounts.user_stats.as_mut() {
+        user_stats.update_activity()?;
+    }
+
+    ctx.accounts.global_state.record_post()?;
+
     // Update social token metrics
     let social_token = &mut ctx.accounts.social_token;
     social_token.total_posts = social_token.total_posts.checked_add(1).unwrap();
     social_token.total_volume = social_token.total_volume.checked_add(total_cost).unwrap();
 
+    for (index, (recipient, notification_info)) in mentions
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .enumerate()
+    {
+        create_mention_notification(
+            notification_info,
+            &ctx.accounts.author,
+            &ctx.accounts.system_program,
+            *recipient,
+            post.key(),
+            index as u64,
+            &post.content,
+            clock.unix_timestamp,
+        )?;
+    }
+
     // Emit post creation event
     emit!(PostCreated {
         post: post.key(),
         author: ctx.accounts.author.key(),
         user_profile: ctx.accounts.user_profile.key(),
-        content_preview: if post.content.len() > 100 {
-            post.content[..100].to_string()
-        } else {
-            post.content.clone()
-        },
+        // Cut on a char boundary; a byte slice can split a multi-byte character
+        content_preview: post.content.chars().take(100).collect(),
         timestamp: clock.unix_timestamp,
         cost: total_cost,
     });
@@ -132,6 +207,64 @@ pub fn handler(ctx: Context<CreatePost>, content: String, media_urls: Vec<String
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+fn create_mention_notification<'info>(
+    notification_info: &AccountInfo<'info>,
+    author: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    recipient: Pubkey,
+    post: Pubkey,
+    id: u64,
+    content: &str,
+    now: i64,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"notification", recipient.as_ref(), post.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        notification_info.key(),
+        expected,
+        SolSocialError::InvalidNotificationAccount
+    );
+
+    let notification_seeds = &[
+        b"notification".as_ref(),
+        recipient.as_ref(),
+        post.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&notification_seeds[..]];
+    let create_ctx = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        CreateAccount {
+            from: author.to_account_info(),
+            to: notification_info.clone(),
+        },
+        signer,
+    );
+    system_program::create_account(
+        create_ctx,
+        Rent::get()?.minimum_balance(Notification::LEN),
+        Notification::LEN as u64,
+        &crate::ID,
+    )?;
+
+    let notification = Notification::mention(id, recipient, author.key(), content, now, bump);
+    let mut data = notification_info.try_borrow_mut_data()?;
+    notification.try_serialize(&mut &mut data[..])?;
+
+    emit!(UserMentioned {
+        notification: expected,
+        post,
+        recipient,
+        sender: author.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
 #[event]
 pub struct PostCreated {
     pub post: Pubkey,
@@ -141,4 +274,12 @@ pub struct PostCreated {
     pub timestamp: i64,
     pub cost: u64,
 }
-```
\ No newline at end of file
+
+#[event]
+pub struct UserMentioned {
+    pub notification: Pubkey,
+    pub post: Pubkey,
+    pub recipient: Pubkey,
+    pub sender: Pubkey,
+    pub timestamp: i64,
+}
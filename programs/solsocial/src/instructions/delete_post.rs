@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::pin_post::pinned_count_after;
+
+#[derive(Accounts)]
+pub struct DeletePost<'info> {
+    #[account(
+        mut,
+        constraint = !post.is_deleted @ SolSocialError::PostNotFound
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub author: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<DeletePost>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let post = &mut ctx.accounts.post;
+    let user_profile = &mut ctx.accounts.user_profile;
+    check_delete(&ctx.accounts.global_state, post, ctx.accounts.author.key())?;
+
+    if post.is_pinned {
+        user_profile.pinned_count = pinned_count_after(user_profile.pinned_count, false)?;
+    }
+
+    // Soft delete: the account stays so replies and interactions pointing at it remain valid
+    post.is_deleted = true;
+    post.is_pinned = false;
+    post.content = String::new();
+    post.media_urls = Vec::new();
+
+    user_profile.posts_count = user_profile.posts_count
+        .checked_sub(1)
+        .ok_or(SolSocialError::MathUnderflow)?;
+
+    emit!(PostDeleted {
+        post: post.key(),
+        author: ctx.accounts.author.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Only the author may delete, and not while the platform is paused. Moderated
+/// posts stay up so the moderation remains auditable.
+fn check_delete(global_state: &GlobalState, post: &Post, author: Pubkey) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require_keys_eq!(post.author, author, SolSocialError::UnauthorizedUser);
+    post.require_not_moderated()
+}
+
+#[event]
+pub struct PostDeleted {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_blocked_while_paused() {
+        let author = Pubkey::new_unique();
+        let post = Post { author, ..Default::default() };
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            check_delete(&global_state, &post, author).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+
+    #[test]
+    fn test_author_can_delete() {
+        let author = Pubkey::new_unique();
+        let post = Post { author, ..Default::default() };
+        assert!(check_delete(&GlobalState::default(), &post, author).is_ok());
+    }
+
+    #[test]
+    fn test_non_author_cannot_delete() {
+        let post = Post { author: Pubkey::new_unique(), ..Default::default() };
+        assert_eq!(
+            check_delete(&GlobalState::default(), &post, Pubkey::new_unique()).unwrap_err(),
+            SolSocialError::UnauthorizedUser.into()
+        );
+    }
+
+    #[test]
+    fn test_moderated_post_cannot_be_deleted() {
+        let author = Pubkey::new_unique();
+        let mut post = Post { author, ..Default::default() };
+        post.moderate(ModerationAction::Remove).unwrap();
+        assert_eq!(
+            check_delete(&GlobalState::default(), &post, author).unwrap_err(),
+            SolSocialError::ContentModerationFailed.into()
+        );
+    }
+}
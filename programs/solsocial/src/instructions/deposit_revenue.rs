@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+/// Moves creator revenue (premium unlocks, subscription payments) into the reward
+/// vault and queues it for the next `process_revenue_distribution`.
+#[derive(Accounts)]
+pub struct DepositRevenue<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"user_profile", creator_profile.authority.as_ref()],
+        bump = creator_profile.bump,
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"revenue_pool", creator_profile.authority.as_ref()],
+        bump = revenue_pool.bump
+    )]
+    pub revenue_pool: Account<'info, RevenuePool>,
+    
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ SolSocialError::InvalidTokenAccountOwner,
+        constraint = depositor_token_account.mint == creator_profile.token_mint @ SolSocialError::InvalidTokenMint,
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA that signs for the reward vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        token::mint = creator_profile.token_mint,
+        token::authority = vault_authority,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+    let revenue_pool = &mut ctx.accounts.revenue_pool;
+    book_deposit(&ctx.accounts.global_state, revenue_pool, amount)?;
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    emit!(RevenueDeposited {
+        creator: revenue_pool.creator,
+        depositor: ctx.accounts.depositor.key(),
+        amount,
+        pending_revenue: revenue_pool.pending_revenue,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn book_deposit(global_state: &GlobalState, revenue_pool: &mut RevenuePool, amount: u64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    revenue_pool.deposit(amount)
+}
+
+#[event]
+pub struct RevenueDeposited {
+    pub creator: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub pending_revenue: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposit_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut revenue_pool = RevenuePool::default();
+        assert_eq!(
+            book_deposit(&global_state, &mut revenue_pool, 1_000).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(revenue_pool.pending_revenue, 0);
+    }
+}
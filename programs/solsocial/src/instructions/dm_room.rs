@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+pub const DM_ROOM_PARTICIPANTS: u32 = 2;
+
+#[derive(Accounts)]
+pub struct GetOrCreateDmRoom<'info> {
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ChatRoom::LEN,
+        seeds = [
+            b"chat_room",
+            canonical_dm_pair(sender.key(), recipient.key()).0.as_ref(),
+            canonical_dm_pair(sender.key(), recipient.key()).1.as_ref()
+        ],
+        bump
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ChatParticipant::LEN,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub sender_participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ChatParticipant::LEN,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_participant: Account<'info, ChatParticipant>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Only used as a PDA seed for the room and the recipient's membership
+    #[account(constraint = recipient.key() != sender.key() @ SolSocialError::InvalidPublicKey)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn get_or_create_dm_room(ctx: Context<GetOrCreateDmRoom>) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    if !needs_setup(&ctx.accounts.global_state, chat_room.created_at)? {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    let (first, _) = canonical_dm_pair(ctx.accounts.sender.key(), ctx.accounts.recipient.key());
+
+    chat_room.authority = first;
+    chat_room.name = String::new();
+    chat_room.description = String::new();
+    chat_room.token_mint = Pubkey::default();
+    chat_room.entry_fee = 0;
+    chat_room.total_messages = 0;
+    chat_room.created_at = clock.unix_timestamp;
+    chat_room.is_active = true;
+    chat_room.max_participants = DM_ROOM_PARTICIPANTS;
+    chat_room.current_participants = DM_ROOM_PARTICIPANTS;
+    chat_room.is_moderated = false;
+    chat_room.pinned_messages = 0;
+    chat_room.bump = ctx.bumps.chat_room;
+
+    let room_key = chat_room.key();
+    let members = [
+        (&mut ctx.accounts.sender_participant, ctx.accounts.sender.key(), ctx.bumps.sender_participant),
+        (&mut ctx.accounts.recipient_participant, ctx.accounts.recipient.key(), ctx.bumps.recipient_participant),
+    ];
+    for (participant, user, bump) in members {
+        participant.user = user;
+        participant.chat_room = room_key;
+        participant.joined_at = clock.unix_timestamp;
+        participant.last_active = clock.unix_timestamp;
+        participant.message_count = 0;
+        participant.total_tips_sent = 0;
+        participant.total_tips_received = 0;
+        participant.is_moderator = false;
+        participant.is_muted = false;
+        participant.reputation_score = 0;
+        participant.last_read_message = 0;
+        participant.window_started_at = 0;
+        participant.window_message_count = 0;
+        participant.bump = bump;
+    }
+
+    emit!(DmRoomCreated {
+        chat_room: room_key,
+        first_member: first,
+        second_member: canonical_dm_pair(ctx.accounts.sender.key(), ctx.accounts.recipient.key()).1,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// A room with a creation time was already set up by an earlier message from either side.
+fn needs_setup(global_state: &GlobalState, created_at: i64) -> Result<bool> {
+    require_not_emergency_paused(global_state)?;
+    Ok(created_at == 0)
+}
+
+#[event]
+pub struct DmRoomCreated {
+    pub chat_room: Pubkey,
+    pub first_member: Pubkey,
+    pub second_member: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dm_room_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        assert!(needs_setup(&global_state, 0).unwrap());
+        assert!(!needs_setup(&global_state, 1_700_000_000).unwrap());
+
+        // Paused even when the room already exists, so the call never silently succeeds
+        global_state.emergency_paused = true;
+        assert_eq!(
+            needs_setup(&global_state, 1_700_000_000).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(new_content: String)]
+pub struct EditPost<'info> {
+    // Posts are allocated at their exact size, so a longer edit grows the account
+    #[account(
+        mut,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser,
+        realloc = Post::space_for(new_content.len(), &post.media_urls),
+        realloc::payer = author,
+        realloc::zero = false,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(mut)]
+    pub author: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<EditPost>, new_content: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let post = &mut ctx.accounts.post;
+
+    check_edit(&ctx.accounts.global_state, post, &new_content, clock.unix_timestamp)?;
+
+    post.content = new_content;
+    post.updated_at = clock.unix_timestamp;
+    post.edit_count = post.edit_count
+        .checked_add(1)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    emit!(PostEdited {
+        post: post.key(),
+        author: ctx.accounts.author.key(),
+        edit_count: post.edit_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Everything an edit has to pass before the post is written.
+fn check_edit(global_state: &GlobalState, post: &Post, new_content: &str, now: i64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    post.require_not_moderated()?;
+    global_state.check_post_length(new_content)?;
+    post.validate_edit(new_content, now)
+}
+
+#[event]
+pub struct PostEdited {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub edit_count: u8,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_blocked_while_paused() {
+        let post = Post::default();
+        let mut global_state = GlobalState::default();
+        assert!(check_edit(&global_state, &post, "fixed a typo", 0).is_ok());
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            check_edit(&global_state, &post, "fixed a typo", 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+
+    #[test]
+    fn test_moderated_post_cannot_be_edited() {
+        let mut post = Post::default();
+        post.moderate(ModerationAction::Hide).unwrap();
+        assert_eq!(
+            check_edit(&GlobalState::default(), &post, "fixed a typo", 0).unwrap_err(),
+            SolSocialError::ContentModerationFailed.into()
+        );
+    }
+
+    #[test]
+    fn test_edit_length_follows_global_limit() {
+        let post = Post::default();
+        let global_state = GlobalState { max_post_length: 280, ..Default::default() };
+        assert!(check_edit(&global_state, &post, &"a".repeat(280), 0).is_ok());
+        assert_eq!(
+            check_edit(&global_state, &post, &"a".repeat(281), 0).unwrap_err(),
+            SolSocialError::ContentTooLong.into()
+        );
+    }
+}
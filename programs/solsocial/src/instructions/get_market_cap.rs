@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use crate::state::*;
+use crate::utils::bonding_curve::calculate_market_cap_with_mode;
+
+#[derive(Accounts)]
+pub struct GetMarketCap<'info> {
+    /// CHECK: Only used to derive the creator token being priced
+    pub creator: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"creator_token", creator.key().as_ref()],
+        bump = creator_token.bump,
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+}
+
+pub fn get_market_cap(ctx: Context<GetMarketCap>) -> Result<()> {
+    let creator_token = &ctx.accounts.creator_token;
+    let market_cap = market_cap_of(creator_token)?;
+    
+    // Clients read this via simulateTransaction instead of re-deriving the curve
+    set_return_data(&market_cap.to_le_bytes());
+    
+    emit!(MarketCapComputed {
+        creator: ctx.accounts.creator.key(),
+        creator_token: creator_token.key(),
+        supply: creator_token.circulating_supply,
+        market_cap,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn market_cap_of(creator_token: &CreatorToken) -> Result<u64> {
+    calculate_market_cap_with_mode(
+        creator_token.circulating_supply,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+    )
+}
+
+#[event]
+pub struct MarketCapComputed {
+    pub creator: Pubkey,
+    pub creator_token: Pubkey,
+    pub supply: u64,
+    pub market_cap: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bonding_curve::calculate_price;
+
+    fn creator_token(circulating_supply: u64) -> CreatorToken {
+        CreatorToken {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: String::new(),
+            symbol: String::new(),
+            total_supply: 1_000_000,
+            circulating_supply,
+            price_curve_type: 0,
+            base_price: 1_000,
+            price_multiplier: 1_000_000,
+            trading_fee_percentage: 0,
+            creator_fee_percentage: 0,
+            created_at: 0,
+            is_tradeable: true,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_market_cap_is_supply_times_spot_price() {
+        for &supply in &[0u64, 1, 10, 1_000, 250_000] {
+            let token = creator_token(supply);
+            let price = calculate_price(supply, &token.curve_params()).unwrap();
+            assert_eq!(market_cap_of(&token).unwrap(), supply * price);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [b"proposal", creator.key().as_ref(), &proposal_id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Only key holders of this creator may open proposals
+    #[account(
+        seeds = [b"keys", creator.key().as_ref(), proposer.key().as_ref()],
+        bump = key_holding.bump,
+    )]
+    pub key_holding: Account<'info, KeyHolding>,
+
+    /// CHECK: The creator whose key holders govern this proposal
+    pub creator: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    // One vote per voter; a second vote fails on init
+    #[account(
+        init,
+        payer = voter,
+        space = Vote::LEN,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+
+    /// Voting power is the voter's key holding in the proposal's creator
+    #[account(
+        seeds = [b"keys", proposal.creator.as_ref(), voter.key().as_ref()],
+        bump = key_holding.bump,
+    )]
+    pub key_holding: Account<'info, KeyHolding>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    voting_period: i64,
+) -> Result<()> {
+    check_proposal(
+        &ctx.accounts.global_state,
+        &title,
+        &description,
+        voting_period,
+        ctx.accounts.key_holding.amount,
+    )?;
+
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = proposal_id;
+    proposal.creator = ctx.accounts.creator.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.title = title;
+    proposal.description = description;
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.voting_ends_at = clock.unix_timestamp
+        .checked_add(voting_period)
+        .ok_or(SolSocialError::MathOverflow)?;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        creator: proposal.creator,
+        proposer: proposal.proposer,
+        voting_ends_at: proposal.voting_ends_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let voting_power = ctx.accounts.key_holding.amount;
+
+    let proposal = &mut ctx.accounts.proposal;
+    cast_vote(&ctx.accounts.global_state, proposal, voting_power, approve, clock.unix_timestamp)?;
+
+    let vote = &mut ctx.accounts.vote;
+    vote.proposal = proposal.key();
+    vote.voter = ctx.accounts.voter.key();
+    vote.voting_power = voting_power;
+    vote.approve = approve;
+    vote.voted_at = clock.unix_timestamp;
+    vote.bump = ctx.bumps.vote;
+
+    emit!(VoteCast {
+        proposal: proposal.key(),
+        voter: vote.voter,
+        voting_power,
+        approve,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    execute(&ctx.accounts.global_state, proposal, clock.unix_timestamp)?;
+
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        executor: ctx.accounts.executor.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn check_proposal(
+    global_state: &GlobalState,
+    title: &str,
+    description: &str,
+    voting_period: i64,
+    voting_power: u64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(
+        !title.trim().is_empty() && title.len() <= Proposal::MAX_TITLE_LENGTH,
+        SolSocialError::InvalidGovernanceProposal
+    );
+    require!(
+        description.len() <= Proposal::MAX_DESCRIPTION_LENGTH,
+        SolSocialError::InvalidGovernanceProposal
+    );
+    require!(
+        (Proposal::MIN_VOTING_PERIOD..=Proposal::MAX_VOTING_PERIOD).contains(&voting_period),
+        SolSocialError::InvalidGovernanceProposal
+    );
+    require!(voting_power > 0, SolSocialError::InsufficientVotingPower);
+    Ok(())
+}
+
+fn cast_vote(
+    global_state: &GlobalState,
+    proposal: &mut Proposal,
+    voting_power: u64,
+    approve: bool,
+    now: i64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    proposal.record_vote(voting_power, approve, now)
+}
+
+fn execute(global_state: &GlobalState, proposal: &mut Proposal, now: i64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    proposal.execute(now)
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub creator: Pubkey,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub voting_power: u64,
+    pub approve: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote_address(proposal: Pubkey, voter: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"vote", proposal.as_ref(), voter.as_ref()], &crate::ID).0
+    }
+
+    #[test]
+    fn test_double_vote_maps_to_existing_account() {
+        let proposal = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+
+        // The vote PDA already exists after the first vote, so the second init is rejected
+        assert_eq!(vote_address(proposal, voter), vote_address(proposal, voter));
+        assert_ne!(vote_address(proposal, voter), vote_address(proposal, Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_governance_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            check_proposal(&global_state, "Raise fees", "", Proposal::MIN_VOTING_PERIOD, 10).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+
+        let mut proposal = Proposal { voting_ends_at: 100, ..Default::default() };
+        assert_eq!(
+            cast_vote(&global_state, &mut proposal, 10, true, 50).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(proposal.yes_votes, 0);
+
+        // A passed proposal waits out the pause too
+        proposal.yes_votes = 10;
+        assert_eq!(
+            execute(&global_state, &mut proposal, 100).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert!(!proposal.executed);
+    }
+}
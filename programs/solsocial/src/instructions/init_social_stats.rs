@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+/// Backfills `SocialStats` for profiles created before it existed. Safe to call
+/// again: an already populated stats account is left untouched.
+#[derive(Accounts)]
+pub struct InitSocialStats<'info> {
+    #[account(
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SocialStats::LEN,
+        seeds = [b"social_stats", user.key().as_ref()],
+        bump
+    )]
+    pub social_stats: Account<'info, SocialStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_social_stats(ctx: Context<InitSocialStats>) -> Result<()> {
+    let social_stats = &mut ctx.accounts.social_stats;
+    if !social_stats.backfill(&ctx.accounts.user_profile, ctx.bumps.social_stats) {
+        return Ok(());
+    }
+
+    emit!(SocialStatsBackfilled {
+        user: ctx.accounts.user.key(),
+        followers_count: social_stats.followers_count,
+        posts_count: social_stats.posts_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SocialStatsBackfilled {
+    pub user: Pubkey,
+    pub followers_count: u64,
+    pub posts_count: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Opens the pool that collects a creator's revenue until it is distributed.
+#[derive(Accounts)]
+pub struct InitializeRevenuePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.authority == creator.key() @ SolSocialError::UnauthorizedAccess,
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = RevenuePool::LEN,
+        seeds = [b"revenue_pool", creator.key().as_ref()],
+        bump
+    )]
+    pub revenue_pool: Account<'info, RevenuePool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_revenue_pool(ctx: Context<InitializeRevenuePool>) -> Result<()> {
+    let revenue_pool = &mut ctx.accounts.revenue_pool;
+    revenue_pool.initialize(ctx.accounts.creator.key(), ctx.bumps.revenue_pool);
+    
+    emit!(RevenuePoolInitialized {
+        creator: revenue_pool.creator,
+        platform_fee_percentage: revenue_pool.platform_fee_percentage,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct RevenuePoolInitialized {
+    pub creator: Pubkey,
+    pub platform_fee_percentage: u8,
+    pub timestamp: i64,
+}
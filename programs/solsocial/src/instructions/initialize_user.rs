@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
@@ -6,7 +5,14 @@ use crate::state::*;
 use crate::errors::*;
 
 #[derive(Accounts)]
-#[instruction(username: String)]
+#[instruction(
+    username: String,
+    display_name: String,
+    bio: String,
+    avatar_url: String,
+    initial_token_supply: u64,
+    referrer: Option<Pubkey>
+)]
 pub struct InitializeUser<'info> {
     #[account(
         init,
@@ -17,6 +23,15 @@ pub struct InitializeUser<'info> {
     )]
     pub user_profile: Account<'info, UserProfile>,
 
+    #[account(
+        init,
+        payer = user,
+        space = UsernameRegistry::LEN,
+        seeds = [b"username", username.as_bytes()],
+        bump
+    )]
+    pub username_registry: Account<'info, UsernameRegistry>,
+
     #[account(
         init,
         payer = user,
@@ -46,6 +61,30 @@ pub struct InitializeUser<'info> {
     )]
     pub social_stats: Account<'info, SocialStats>,
 
+    /// Required only when signing up with a referrer
+    #[account(
+        init,
+        payer = user,
+        space = Referral::LEN,
+        seeds = [b"referral", referrer.unwrap_or_default().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", referrer.unwrap_or_default().as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -61,11 +100,14 @@ pub fn initialize_user(
     bio: String,
     avatar_url: String,
     initial_token_supply: u64,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
-    require!(username.len() <= 32, SolSocialError::UsernameTooLong);
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    validate_username(&username)?;
     require!(display_name.len() <= 64, SolSocialError::DisplayNameTooLong);
     require!(bio.len() <= 280, SolSocialError::BioTooLong);
     require!(avatar_url.len() <= 200, SolSocialError::AvatarUrlTooLong);
+    validate_url(&avatar_url)?;
     require!(initial_token_supply > 0, SolSocialError::InvalidTokenSupply);
     require!(initial_token_supply <= 1_000_000_000_000, SolSocialError::TokenSupplyTooHigh);
 
@@ -74,26 +116,58 @@ pub fn initialize_user(
     let clock = Clock::get()?;
 
     // Initialize user profile
-    user_profile.owner = ctx.accounts.user.key();
+    user_profile.authority = ctx.accounts.user.key();
     user_profile.username = username;
     user_profile.display_name = display_name;
     user_profile.bio = bio;
     user_profile.avatar_url = avatar_url;
     user_profile.token_mint = ctx.accounts.user_token_mint.key();
     user_profile.token_vault = ctx.accounts.user_token_vault.key();
-    user_profile.total_token_supply = initial_token_supply;
-    user_profile.circulating_supply = 0;
-    user_profile.base_price = 1_000_000; // 0.001 SOL in lamports
-    user_profile.current_price = 1_000_000;
-    user_profile.price_multiplier = 1100; // 1.1x multiplier (basis points)
-    user_profile.creator_fee_percentage = 500; // 5% creator fee
-    user_profile.protocol_fee_percentage = 250; // 2.5% protocol fee
+    user_profile.max_supply = initial_token_supply;
+    user_profile.token_supply = 0;
+    user_profile.token_price = 1_000_000; // 0.001 SOL in lamports
+    user_profile.revenue_share_percentage = UserProfile::DEFAULT_REVENUE_SHARE_PERCENTAGE;
+    user_profile.reputation_score = UserProfile::INITIAL_REPUTATION;
+    user_profile.creator_tip_percentage = UserProfile::DEFAULT_CREATOR_TIP_PERCENTAGE;
+    user_profile.reputation_last_decayed = clock.unix_timestamp;
     user_profile.is_verified = false;
     user_profile.is_active = true;
+    user_profile.blocks_nsfw = false;
+    user_profile.referral_count = 0;
+    user_profile.encryption_pubkey = None;
+    user_profile.social_links = Vec::new();
     user_profile.created_at = clock.unix_timestamp;
     user_profile.updated_at = clock.unix_timestamp;
+    user_profile.last_active = clock.unix_timestamp;
     user_profile.bump = ctx.bumps.user_profile;
 
+    let username_registry = &mut ctx.accounts.username_registry;
+    username_registry.owner = ctx.accounts.user.key();
+    username_registry.username = user_profile.username.clone();
+    username_registry.created_at = clock.unix_timestamp;
+    username_registry.bump = ctx.bumps.username_registry;
+
+    ctx.accounts.global_state.record_user()?;
+
+    // Record referral attribution
+    match (referrer, ctx.accounts.referral.as_mut(), ctx.accounts.referrer_profile.as_mut()) {
+        (Some(referrer), Some(referral), Some(referrer_profile)) => {
+            Referral::validate(referrer, ctx.accounts.user.key(), referrer_profile.referral_count)?;
+
+            referral.referrer = referrer;
+            referral.referee = ctx.accounts.user.key();
+            referral.created_at = clock.unix_timestamp;
+            referral.bonus_paid = false;
+            referral.bump = ctx.bumps.referral.ok_or(SolSocialError::InvalidReferralCode)?;
+
+            referrer_profile.referral_count = referrer_profile.referral_count
+                .checked_add(1)
+                .ok_or(SolSocialError::MathOverflow)?;
+        }
+        (None, None, None) => {}
+        _ => return err!(SolSocialError::InvalidReferralCode),
+    }
+
     // Initialize social stats
     social_stats.user = ctx.accounts.user.key();
     social_stats.followers_count = 0;
@@ -145,4 +219,3 @@ pub fn initialize_user(
 
     Ok(())
 }
-```
\ No newline at end of file
@@ -1,9 +1,11 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::revenue_share::{
+    apply_batch_tip, calculate_tip_distribution, validate_batch_tip, BATCH_TIP_ACCOUNTS_PER_POST,
+};
 
 #[derive(Accounts)]
 #[instruction(interaction_type: u8)]
@@ -21,7 +23,7 @@ pub struct InteractPost<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + PostInteraction::INIT_SPACE,
+        space = PostInteraction::LEN,
         seeds = [b"interaction", user.key().as_ref(), post.key().as_ref()],
         bump
     )]
@@ -29,14 +31,21 @@ pub struct InteractPost<'info> {
     
     #[account(
         mut,
-        seeds = [b"profile", post.creator.as_ref()],
+        seeds = [b"user_profile", post.creator.as_ref()],
         bump = creator_profile.bump
     )]
     pub creator_profile: Account<'info, UserProfile>,
     
     #[account(
         mut,
-        seeds = [b"profile", user.key().as_ref()],
+        seeds = [b"social_stats", post.creator.as_ref()],
+        bump = creator_social_stats.bump
+    )]
+    pub creator_social_stats: Option<Account<'info, SocialStats>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
         bump = user_profile.bump
     )]
     pub user_profile: Account<'info, UserProfile>,
@@ -55,7 +64,123 @@ pub struct InteractPost<'info> {
     )]
     pub creator_token_account: Account<'info, TokenAccount>,
     
+    /// CHECK: PDA holding like rewards so an unlike can refund them
+    #[account(
+        seeds = [b"reward_escrow", post.key().as_ref()],
+        bump
+    )]
+    pub reward_escrow_authority: AccountInfo<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = creator_profile.token_mint,
+        associated_token::authority = reward_escrow_authority
+    )]
+    pub reward_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEngagementRewards<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"post", post.creator.as_ref(), &post.post_id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.creator == creator.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub post: Account<'info, Post>,
+    
+    /// The like whose escrowed reward is being released
+    #[account(
+        mut,
+        seeds = [b"interaction", interaction.user.as_ref(), post.key().as_ref()],
+        bump = interaction.bump
+    )]
+    pub interaction: Account<'info, PostInteraction>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"social_stats", creator.key().as_ref()],
+        bump = creator_social_stats.bump
+    )]
+    pub creator_social_stats: Option<Account<'info, SocialStats>>,
+    
+    /// CHECK: PDA holding like rewards so an unlike can refund them
+    #[account(
+        seeds = [b"reward_escrow", post.key().as_ref()],
+        bump
+    )]
+    pub reward_escrow_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = creator_profile.token_mint,
+        associated_token::authority = reward_escrow_authority
+    )]
+    pub reward_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = creator_profile.token_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
     pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BookmarkPost<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"post", post.creator.as_ref(), &post.post_id.to_le_bytes()],
+        bump = post.bump
+    )]
+    pub post: Account<'info, Post>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PostInteraction::LEN,
+        seeds = [b"interaction", user.key().as_ref(), post.key().as_ref()],
+        bump
+    )]
+    pub interaction: Account<'info, PostInteraction>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -82,14 +207,108 @@ pub struct CommentPost<'info> {
     
     #[account(
         mut,
-        seeds = [b"profile", user.key().as_ref()],
+        seeds = [b"user_profile", user.key().as_ref()],
         bump = user_profile.bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    #[account(
+        mut,
+        seeds = [b"social_stats", post.creator.as_ref()],
+        bump = creator_social_stats.bump
+    )]
+    pub creator_social_stats: Option<Account<'info, SocialStats>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ReplyToComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"post", post.creator.as_ref(), &post.post_id.to_le_bytes()],
+        bump = post.bump
+    )]
+    pub post: Account<'info, Post>,
+    
+    #[account(
+        mut,
+        constraint = parent_comment.post == post.key() @ SolSocialError::CommentNotFound,
+        constraint = parent_comment.accepts_replies() @ SolSocialError::ReplyDepthExceeded
+    )]
+    pub parent_comment: Account<'info, Comment>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Comment::INIT_SPACE,
+        seeds = [b"comment", user.key().as_ref(), post.key().as_ref(), &post.comment_count.to_le_bytes()],
+        bump
+    )]
+    pub comment: Account<'info, Comment>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"social_stats", post.creator.as_ref()],
+        bump = creator_social_stats.bump
+    )]
+    pub creator_social_stats: Option<Account<'info, SocialStats>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Each tipped post supplies `BATCH_TIP_ACCOUNTS_PER_POST` accounts in remaining_accounts
+#[derive(Accounts)]
+pub struct BatchTip<'info> {
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", tipper.key().as_ref()],
+        bump = tipper_profile.bump
+    )]
+    pub tipper_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: PDA that owns every holder rewards vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct TipPost<'info> {
     #[account(mut)]
@@ -104,14 +323,14 @@ pub struct TipPost<'info> {
     
     #[account(
         mut,
-        seeds = [b"profile", post.creator.as_ref()],
+        seeds = [b"user_profile", post.creator.as_ref()],
         bump = creator_profile.bump
     )]
     pub creator_profile: Account<'info, UserProfile>,
     
     #[account(
         mut,
-        seeds = [b"profile", tipper.key().as_ref()],
+        seeds = [b"user_profile", tipper.key().as_ref()],
         bump = tipper_profile.bump
     )]
     pub tipper_profile: Account<'info, UserProfile>,
@@ -130,32 +349,79 @@ pub struct TipPost<'info> {
     )]
     pub creator_token_account: Account<'info, TokenAccount>,
     
+    /// Receives the platform's cut of the tip
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == creator_profile.token_mint,
+        constraint = treasury_token_account.owner == global_state.treasury @ SolSocialError::InvalidTokenAccountOwner
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"revenue_pool", post.creator.as_ref()],
+        bump = revenue_pool.bump
+    )]
+    pub revenue_pool: Account<'info, RevenuePool>,
+    
+    /// CHECK: PDA that signs for the reward vault
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    /// Holds the holders' share until they claim it
+    #[account(
+        mut,
+        token::mint = creator_profile.token_mint,
+        token::authority = vault_authority
+    )]
+    pub holder_rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
     #[account(
         init,
         payer = tipper,
         space = 8 + Tip::INIT_SPACE,
-        seeds = [b"tip", tipper.key().as_ref(), post.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        seeds = [b"tip", tipper.key().as_ref(), post.key().as_ref(), &tipper_profile.tip_nonce.to_le_bytes()],
         bump
     )]
     pub tip: Account<'info, Tip>,
     
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = TopTipper::LEN,
+        seeds = [b"top_tipper", post.key().as_ref(), tipper.key().as_ref()],
+        bump
+    )]
+    pub top_tipper: Account<'info, TopTipper>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn like_post(ctx: Context<InteractPost>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     let post = &mut ctx.accounts.post;
+    post.require_not_moderated()?;
     let interaction = &mut ctx.accounts.interaction;
     let user_profile = &mut ctx.accounts.user_profile;
-    let creator_profile = &mut ctx.accounts.creator_profile;
     
-    require!(!interaction.has_liked, SolSocialError::AlreadyLiked);
+    check_not_own_like(ctx.accounts.user.key(), post.creator)?;
+    require!(!interaction.has(InteractionType::Like), SolSocialError::AlreadyLiked);
     
     // Update interaction state
     interaction.user = ctx.accounts.user.key();
     interaction.post = post.key();
-    interaction.has_liked = true;
-    interaction.liked_at = Clock::get()?.unix_timestamp;
+    interaction.insert(InteractionType::Like, Clock::get()?.unix_timestamp);
     interaction.bump = ctx.bumps.interaction;
     
     // Update post stats
@@ -169,14 +435,11 @@ pub fn like_post(ctx: Context<InteractPost>) -> Result<()> {
     // Reward creator with tokens for engagement
     let reward_amount = calculate_engagement_reward(post.like_count, InteractionType::Like);
     if reward_amount > 0 {
-        creator_profile.total_earnings = creator_profile.total_earnings
-            .checked_add(reward_amount)
-            .ok_or(SolSocialError::Overflow)?;
-        
-        // Transfer tokens from user to creator
+        // Escrow the reward so it can be returned if the like is reversed; the
+        // creator is credited when it is released
         let cpi_accounts = Transfer {
             from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.reward_escrow.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -184,6 +447,12 @@ pub fn like_post(ctx: Context<InteractPost>) -> Result<()> {
         
         token::transfer(cpi_ctx, reward_amount)?;
     }
+    interaction.record_reward(reward_amount);
+    
+    if let Some(stats) = ctx.accounts.creator_social_stats.as_mut() {
+        stats.likes_received = stats.likes_received.checked_add(1).ok_or(SolSocialError::Overflow)?;
+        stats.recompute_engagement()?;
+    }
     
     emit!(PostLiked {
         post: post.key(),
@@ -197,15 +466,15 @@ pub fn like_post(ctx: Context<InteractPost>) -> Result<()> {
 }
 
 pub fn unlike_post(ctx: Context<InteractPost>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     let post = &mut ctx.accounts.post;
     let interaction = &mut ctx.accounts.interaction;
     let user_profile = &mut ctx.accounts.user_profile;
     
-    require!(interaction.has_liked, SolSocialError::NotLiked);
+    require!(interaction.has(InteractionType::Like), SolSocialError::NotLiked);
     
     // Update interaction state
-    interaction.has_liked = false;
-    interaction.unliked_at = Clock::get()?.unix_timestamp;
+    interaction.remove(InteractionType::Like, Clock::get()?.unix_timestamp);
     
     // Update post stats
     post.like_count = post.like_count.checked_sub(1).ok_or(SolSocialError::Underflow)?;
@@ -214,6 +483,33 @@ pub fn unlike_post(ctx: Context<InteractPost>) -> Result<()> {
     user_profile.engagement_score = user_profile.engagement_score
         .saturating_sub(LIKE_ENGAGEMENT_POINTS);
     
+    // Refund what this like deposited and is still escrowed; released rewards are final
+    let refund_amount = interaction.take_unreleased_reward();
+    if refund_amount > 0 {
+        let post_key = post.key();
+        let escrow_seeds = &[
+            b"reward_escrow".as_ref(),
+            post_key.as_ref(),
+            &[ctx.bumps.reward_escrow_authority],
+        ];
+        let signer_seeds = &[&escrow_seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_escrow.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.reward_escrow_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::transfer(cpi_ctx, refund_amount)?;
+    }
+    
+    if let Some(stats) = ctx.accounts.creator_social_stats.as_mut() {
+        stats.likes_received = stats.likes_received.checked_sub(1).ok_or(SolSocialError::Overflow)?;
+        stats.recompute_engagement()?;
+    }
+    
     emit!(PostUnliked {
         post: post.key(),
         user: ctx.accounts.user.key(),
@@ -224,23 +520,56 @@ pub fn unlike_post(ctx: Context<InteractPost>) -> Result<()> {
     Ok(())
 }
 
+pub fn release_engagement_rewards(ctx: Context<ReleaseEngagementRewards>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    // Only this like's own deposit leaves the shared escrow
+    let amount = ctx.accounts.interaction.take_unreleased_reward();
+    require!(amount > 0, SolSocialError::InsufficientVaultBalance);
+    credit_creator_earnings(
+        &mut ctx.accounts.creator_profile,
+        ctx.accounts.creator_social_stats.as_deref_mut(),
+        amount,
+    )?;
+    
+    let post_key = ctx.accounts.post.key();
+    let escrow_seeds = &[
+        b"reward_escrow".as_ref(),
+        post_key.as_ref(),
+        &[ctx.bumps.reward_escrow_authority],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_escrow.to_account_info(),
+        to: ctx.accounts.creator_token_account.to_account_info(),
+        authority: ctx.accounts.reward_escrow_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    token::transfer(cpi_ctx, amount)?;
+    
+    Ok(())
+}
+
 pub fn share_post(ctx: Context<InteractPost>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     let post = &mut ctx.accounts.post;
+    post.require_not_moderated()?;
     let interaction = &mut ctx.accounts.interaction;
     let user_profile = &mut ctx.accounts.user_profile;
     let creator_profile = &mut ctx.accounts.creator_profile;
     
-    require!(!interaction.has_shared, SolSocialError::AlreadyShared);
+    require!(!interaction.has(InteractionType::Share), SolSocialError::AlreadyShared);
     
     // Update interaction state
     interaction.user = ctx.accounts.user.key();
     interaction.post = post.key();
-    interaction.has_shared = true;
-    interaction.shared_at = Clock::get()?.unix_timestamp;
+    interaction.insert(InteractionType::Share, Clock::get()?.unix_timestamp);
     interaction.bump = ctx.bumps.interaction;
     
     // Update post stats
-    post.share_count = post.share_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+    post.share()?;
     
     // Update user engagement score
     user_profile.engagement_score = user_profile.engagement_score
@@ -250,9 +579,11 @@ pub fn share_post(ctx: Context<InteractPost>) -> Result<()> {
     // Reward creator with tokens for share
     let reward_amount = calculate_engagement_reward(post.share_count, InteractionType::Share);
     if reward_amount > 0 {
-        creator_profile.total_earnings = creator_profile.total_earnings
-            .checked_add(reward_amount)
-            .ok_or(SolSocialError::Overflow)?;
+        credit_creator_earnings(
+            creator_profile,
+            ctx.accounts.creator_social_stats.as_deref_mut(),
+            reward_amount,
+        )?;
         
         // Transfer tokens from user to creator
         let cpi_accounts = Transfer {
@@ -266,6 +597,11 @@ pub fn share_post(ctx: Context<InteractPost>) -> Result<()> {
         token::transfer(cpi_ctx, reward_amount)?;
     }
     
+    if let Some(stats) = ctx.accounts.creator_social_stats.as_mut() {
+        stats.shares_count = stats.shares_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+        stats.recompute_engagement()?;
+    }
+    
     emit!(PostShared {
         post: post.key(),
         user: ctx.accounts.user.key(),
@@ -277,11 +613,55 @@ pub fn share_post(ctx: Context<InteractPost>) -> Result<()> {
     Ok(())
 }
 
+pub fn bookmark_post(ctx: Context<BookmarkPost>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let post = &mut ctx.accounts.post;
+    let interaction = &mut ctx.accounts.interaction;
+    
+    require!(!interaction.has(InteractionType::Bookmark), SolSocialError::AlreadyBookmarked);
+    
+    // Shares the (user, post) interaction PDA with likes and shares
+    interaction.user = ctx.accounts.user.key();
+    interaction.post = post.key();
+    interaction.insert(InteractionType::Bookmark, Clock::get()?.unix_timestamp);
+    interaction.bump = ctx.bumps.interaction;
+    post.add_bookmark()?;
+    
+    emit!(PostBookmarked {
+        post: post.key(),
+        user: ctx.accounts.user.key(),
+        timestamp: interaction.timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn unbookmark_post(ctx: Context<BookmarkPost>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let post = &mut ctx.accounts.post;
+    let interaction = &mut ctx.accounts.interaction;
+    
+    require!(interaction.has(InteractionType::Bookmark), SolSocialError::NotBookmarked);
+    
+    interaction.remove(InteractionType::Bookmark, Clock::get()?.unix_timestamp);
+    post.remove_bookmark()?;
+    
+    emit!(PostUnbookmarked {
+        post: post.key(),
+        user: ctx.accounts.user.key(),
+        timestamp: interaction.timestamp,
+    });
+    
+    Ok(())
+}
+
 pub fn comment_post(ctx: Context<CommentPost>, content: String) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     require!(content.len() <= MAX_COMMENT_LENGTH, SolSocialError::CommentTooLong);
     require!(!content.trim().is_empty(), SolSocialError::EmptyComment);
     
     let post = &mut ctx.accounts.post;
+    post.require_not_moderated()?;
     let comment = &mut ctx.accounts.comment;
     let user_profile = &mut ctx.accounts.user_profile;
     
@@ -292,6 +672,7 @@ pub fn comment_post(ctx: Context<CommentPost>, content: String) -> Result<()> {
     comment.created_at = Clock::get()?.unix_timestamp;
     comment.like_count = 0;
     comment.reply_count = 0;
+    comment.reply_to = None;
     comment.is_deleted = false;
     comment.bump = ctx.bumps.comment;
     
@@ -303,6 +684,55 @@ pub fn comment_post(ctx: Context<CommentPost>, content: String) -> Result<()> {
         .checked_add(COMMENT_ENGAGEMENT_POINTS)
         .ok_or(SolSocialError::Overflow)?;
     
+    if let Some(stats) = ctx.accounts.creator_social_stats.as_mut() {
+        stats.comments_count = stats.comments_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+        stats.recompute_engagement()?;
+    }
+    
+    emit!(PostCommented {
+        post: post.key(),
+        comment: comment.key(),
+        author: ctx.accounts.user.key(),
+        content: comment.content.clone(),
+        comment_count: post.comment_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn reply_to_comment(ctx: Context<ReplyToComment>, content: String) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    require!(content.len() <= MAX_COMMENT_LENGTH, SolSocialError::CommentTooLong);
+    require!(!content.trim().is_empty(), SolSocialError::EmptyComment);
+    
+    let post = &mut ctx.accounts.post;
+    let parent_comment = &mut ctx.accounts.parent_comment;
+    let comment = &mut ctx.accounts.comment;
+    let user_profile = &mut ctx.accounts.user_profile;
+    
+    comment.author = ctx.accounts.user.key();
+    comment.post = post.key();
+    comment.content = content;
+    comment.created_at = Clock::get()?.unix_timestamp;
+    comment.like_count = 0;
+    comment.reply_count = 0;
+    comment.reply_to = Some(parent_comment.key());
+    comment.is_deleted = false;
+    comment.bump = ctx.bumps.comment;
+    
+    parent_comment.reply_count = parent_comment.reply_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+    post.comment_count = post.comment_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+    
+    user_profile.engagement_score = user_profile.engagement_score
+        .checked_add(COMMENT_ENGAGEMENT_POINTS)
+        .ok_or(SolSocialError::Overflow)?;
+    
+    if let Some(stats) = ctx.accounts.creator_social_stats.as_mut() {
+        stats.comments_count = stats.comments_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
+        stats.recompute_engagement()?;
+    }
+    
     emit!(PostCommented {
         post: post.key(),
         comment: comment.key(),
@@ -316,8 +746,10 @@ pub fn comment_post(ctx: Context<CommentPost>, content: String) -> Result<()> {
 }
 
 pub fn tip_post(ctx: Context<TipPost>, amount: u64, message: Option<String>) -> Result<()> {
-    require!(amount > 0, SolSocialError::InvalidTipAmount);
-    require!(amount <= MAX_TIP_AMOUNT, SolSocialError::TipAmountTooHigh);
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    ctx.accounts.global_state.check_tip_amount(amount, MAX_TIP_AMOUNT)?;
+    check_not_own_tip(ctx.accounts.tipper.key(), ctx.accounts.post.creator)?;
+    ctx.accounts.post.require_not_moderated()?;
     
     if let Some(ref msg) = message {
         require!(msg.len() <= MAX_TIP_MESSAGE_LENGTH, SolSocialError::TipMessageTooLong);
@@ -337,25 +769,49 @@ pub fn tip_post(ctx: Context<TipPost>, amount: u64, message: Option<String>) ->
     tip.created_at = Clock::get()?.unix_timestamp;
     tip.bump = ctx.bumps.tip;
     
-    // Transfer tokens from tipper to creator
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.tipper_token_account.to_account_info(),
-        to: ctx.accounts.creator_token_account.to_account_info(),
-        authority: ctx.accounts.tipper.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    // Split the tip between the creator, the platform and the creator's holders
+    let (creator_share, platform_share, holder_share) = calculate_tip_distribution(
+        amount,
+        creator_profile.creator_tip_percentage,
+        ctx.accounts.global_state.platform_tip_fee_bps,
+    )?;
     
-    token::transfer(cpi_ctx, amount)?;
+    let payouts = [
+        (&ctx.accounts.creator_token_account, creator_share),
+        (&ctx.accounts.treasury_token_account, platform_share),
+        (&ctx.accounts.holder_rewards_vault, holder_share),
+    ];
+    for (destination, share) in payouts {
+        if share == 0 {
+            continue;
+        }
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.tipper_token_account.to_account_info(),
+            to: destination.to_account_info(),
+            authority: ctx.accounts.tipper.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), share)?;
+    }
+    
+    let revenue_pool = &mut ctx.accounts.revenue_pool;
+    revenue_pool.holder_rewards_pool = revenue_pool.holder_rewards_pool
+        .checked_add(holder_share)
+        .ok_or(SolSocialError::Overflow)?;
     
     // Update post stats
     post.tip_count = post.tip_count.checked_add(1).ok_or(SolSocialError::Overflow)?;
     post.total_tips = post.total_tips.checked_add(amount).ok_or(SolSocialError::Overflow)?;
     
+    // Aggregate this tipper's running total for the post leaderboard
+    let top_tipper = &mut ctx.accounts.top_tipper;
+    top_tipper.post = post.key();
+    top_tipper.tipper = ctx.accounts.tipper.key();
+    top_tipper.bump = ctx.bumps.top_tipper;
+    top_tipper.record_tip(amount, tip.created_at)?;
+    
     // Update profiles
-    creator_profile.total_earnings = creator_profile.total_earnings
-        .checked_add(amount)
-        .ok_or(SolSocialError::Overflow)?;
+    credit_creator_earnings(creator_profile, None, creator_share)?;
     creator_profile.tips_received = creator_profile.tips_received
         .checked_add(1)
         .ok_or(SolSocialError::Overflow)?;
@@ -363,24 +819,229 @@ pub fn tip_post(ctx: Context<TipPost>, amount: u64, message: Option<String>) ->
     tipper_profile.tips_given = tipper_profile.tips_given
         .checked_add(1)
         .ok_or(SolSocialError::Overflow)?;
+    tipper_profile.advance_tip_nonce()?;
     tipper_profile.engagement_score = tipper_profile.engagement_score
         .checked_add(TIP_ENGAGEMENT_POINTS)
         .ok_or(SolSocialError::Overflow)?;
     
+    ctx.accounts.global_state.record_tips(1, amount)?;
+    
     emit!(PostTipped {
         post: post.key(),
         tip: tip.key(),
         tipper: ctx.accounts.tipper.key(),
         recipient: post.creator,
         amount,
+        platform_fee: platform_share,
         message: tip.message.clone(),
         tip_count: post.tip_count,
         total_tips: post.total_tips,
+        tipper_total: top_tipper.total_tipped,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
     Ok(())
 }
 
+/// Tips several posts in one transaction with the same split as `tip_post`.
+/// Batch tips skip the per-tip record and top-tipper leaderboard.
+pub fn batch_tip<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchTip<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    validate_batch_tip(&amounts, ctx.remaining_accounts.len())?;
+    
+    let tipper = ctx.accounts.tipper.key();
+    let mut total_amount: u64 = 0;
+    
+    for (&amount, group) in amounts
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(BATCH_TIP_ACCOUNTS_PER_POST))
+    {
+        ctx.accounts.global_state.check_tip_amount(amount, MAX_TIP_AMOUNT)?;
+        
+        let [post_info, creator_profile_info, tipper_token_info, creator_token_info, treasury_token_info, revenue_pool_info, holder_vault_info] = group else {
+            return err!(SolSocialError::BatchTipLengthMismatch);
+        };
+        
+        let mut post = Account::<Post>::try_from(post_info)?;
+        check_not_own_tip(tipper, post.creator)?;
+        post.require_not_moderated()?;
+        
+        let mut creator_profile = Account::<UserProfile>::try_from(creator_profile_info)?;
+        let expected_profile = Pubkey::create_program_address(
+            &[b"user_profile", post.creator.as_ref(), &[creator_profile.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(SolSocialError::InvalidBatchTipAccount))?;
+        require_keys_eq!(creator_profile.key(), expected_profile, SolSocialError::InvalidBatchTipAccount);
+        
+        let mut revenue_pool = Account::<RevenuePool>::try_from(revenue_pool_info)?;
+        let expected_pool = Pubkey::create_program_address(
+            &[b"revenue_pool", post.creator.as_ref(), &[revenue_pool.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(SolSocialError::InvalidBatchTipAccount))?;
+        require_keys_eq!(revenue_pool.key(), expected_pool, SolSocialError::InvalidBatchTipAccount);
+        
+        let mint = creator_profile.token_mint;
+        let tipper_token = Account::<TokenAccount>::try_from(tipper_token_info)?;
+        let creator_token = Account::<TokenAccount>::try_from(creator_token_info)?;
+        let treasury_token = Account::<TokenAccount>::try_from(treasury_token_info)?;
+        let holder_vault = Account::<TokenAccount>::try_from(holder_vault_info)?;
+        require!(
+            tipper_token.owner == tipper
+                && creator_token.owner == post.creator
+                && treasury_token.owner == ctx.accounts.global_state.treasury
+                && holder_vault.owner == ctx.accounts.vault_authority.key()
+                && [&tipper_token, &creator_token, &treasury_token, &holder_vault]
+                    .iter()
+                    .all(|account| account.mint == mint),
+            SolSocialError::InvalidBatchTipAccount
+        );
+        
+        let (creator_share, platform_share, holder_share) = apply_batch_tip(
+            &mut post,
+            &mut revenue_pool,
+            amount,
+            creator_profile.creator_tip_percentage,
+            ctx.accounts.global_state.platform_tip_fee_bps,
+        )?;
+        
+        let payouts = [
+            (creator_token_info, creator_share),
+            (treasury_token_info, platform_share),
+            (holder_vault_info, holder_share),
+        ];
+        for (destination, share) in payouts {
+            if share == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: tipper_token_info.clone(),
+                to: destination.clone(),
+                authority: ctx.accounts.tipper.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            token::transfer(CpiContext::new(cpi_program, cpi_accounts), share)?;
+        }
+        
+        credit_creator_earnings(&mut creator_profile, None, creator_share)?;
+        creator_profile.tips_received = creator_profile.tips_received
+            .checked_add(1)
+            .ok_or(SolSocialError::Overflow)?;
+        
+        // Accounts loaded from remaining_accounts are not written back automatically
+        post.exit(&crate::ID)?;
+        creator_profile.exit(&crate::ID)?;
+        revenue_pool.exit(&crate::ID)?;
+        
+        total_amount = total_amount.checked_add(amount).ok_or(SolSocialError::Overflow)?;
+    }
+    
+    let tipper_profile = &mut ctx.accounts.tipper_profile;
+    tipper_profile.tips_given = tipper_profile.tips_given
+        .checked_add(amounts.len() as u64)
+        .ok_or(SolSocialError::Overflow)?;
+    tipper_profile.engagement_score = tipper_profile.engagement_score
+        .checked_add(TIP_ENGAGEMENT_POINTS * amounts.len() as u64)
+        .ok_or(SolSocialError::Overflow)?;
+    
+    ctx.accounts.global_state.record_tips(amounts.len() as u64, total_amount)?;
+    
+    emit!(BatchTipped {
+        tipper,
+        posts: amounts.len() as u8,
+        total_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct BatchTipped {
+    pub tipper: Pubkey,
+    pub posts: u8,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostBookmarked {
+    pub post: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostUnbookmarked {
+    pub post: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Likes pay the creator a reward, so a creator liking their own post would mint engagement
+fn check_not_own_like(user: Pubkey, creator: Pubkey) -> Result<()> {
+    require_keys_neq!(user, creator, SolSocialError::CannotLikeOwnPost);
+    Ok(())
+}
+
+/// Self-tips would only inflate tip stats and the top-tipper board
+fn check_not_own_tip(tipper: Pubkey, creator: Pubkey) -> Result<()> {
+    require_keys_neq!(tipper, creator, SolSocialError::CannotTipSelf);
+    Ok(())
+}
+
+/// Credits what the creator actually received, keeping the social stats mirror in step
+fn credit_creator_earnings(
+    profile: &mut UserProfile,
+    stats: Option<&mut SocialStats>,
+    amount: u64,
+) -> Result<()> {
+    profile.total_earned = profile.total_earned
+        .checked_add(amount)
+        .ok_or(SolSocialError::Overflow)?;
+    if let Some(stats) = stats {
+        stats.total_earnings = stats.total_earnings
+            .checked_add(amount)
+            .ok_or(SolSocialError::Overflow)?;
+    }
+    Ok(())
+}
+
 fn calculate_engagement_reward(interaction_count: u64, interaction_type: InteractionType) -> u64 {
-    let base_reward = match interaction_type {
\ No newline at end of file
+    let base_reward = match interaction_type {
+        InteractionType::Like => 1_000,
+        InteractionType::Share => 5_000,
+        InteractionType::Tip | InteractionType::Bookmark => 0,
+    };
+    // Early engagement is worth more; the reward halves every 100 interactions
+    base_reward >> (interaction_count / 100).min(63)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creator_cannot_like_own_post() {
+        let creator = Pubkey::new_unique();
+        assert_eq!(
+            check_not_own_like(creator, creator).unwrap_err(),
+            SolSocialError::CannotLikeOwnPost.into()
+        );
+        assert!(check_not_own_like(Pubkey::new_unique(), creator).is_ok());
+    }
+
+    #[test]
+    fn test_creator_cannot_tip_own_post() {
+        let creator = Pubkey::new_unique();
+        assert_eq!(
+            check_not_own_tip(creator, creator).unwrap_err(),
+            SolSocialError::CannotTipSelf.into()
+        );
+        assert!(check_not_own_tip(Pubkey::new_unique(), creator).is_ok());
+    }
+}
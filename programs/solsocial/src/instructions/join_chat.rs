@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct JoinChat<'info> {
+    #[account(
+        mut,
+        constraint = chat_room.is_active @ SolSocialError::UnauthorizedAccess
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        init,
+        payer = user,
+        space = ChatParticipant::LEN,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ SolSocialError::InvalidTokenAccountOwner,
+        constraint = user_token_account.mint == chat_room.token_mint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = chat_room.token_mint,
+        associated_token::authority = chat_room
+    )]
+    pub chat_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn join_chat(ctx: Context<JoinChat>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let clock = Clock::get()?;
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    let entry_fee = chat_room.admit_participant()?;
+
+    if entry_fee > 0 {
+        require!(
+            ctx.accounts.user_token_account.amount >= entry_fee,
+            SolSocialError::InsufficientTokenBalance
+        );
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.chat_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, entry_fee)?;
+    }
+
+    let participant = &mut ctx.accounts.participant;
+    participant.user = ctx.accounts.user.key();
+    participant.chat_room = chat_room.key();
+    participant.joined_at = clock.unix_timestamp;
+    participant.last_active = clock.unix_timestamp;
+    participant.message_count = 0;
+    participant.total_tips_sent = 0;
+    participant.total_tips_received = 0;
+    participant.is_moderator = false;
+    participant.is_muted = false;
+    participant.reputation_score = 0;
+    participant.last_read_message = 0;
+    participant.window_started_at = 0;
+    participant.window_message_count = 0;
+    participant.bump = ctx.bumps.participant;
+
+    emit!(ChatJoined {
+        chat_room: chat_room.key(),
+        user: ctx.accounts.user.key(),
+        entry_fee,
+        current_participants: chat_room.current_participants,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ChatJoined {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub entry_fee: u64,
+    pub current_participants: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct LeaveChat<'info> {
+    #[account(mut)]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.is_member_of(chat_room.key(), user.key()) @ SolSocialError::NotChatMember
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn leave_chat(ctx: Context<LeaveChat>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let chat_room = &mut ctx.accounts.chat_room;
+    chat_room.release_participant(ctx.accounts.user.key())?;
+
+    emit!(ChatLeft {
+        chat_room: chat_room.key(),
+        user: ctx.accounts.user.key(),
+        current_participants: chat_room.current_participants,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ChatLeft {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub current_participants: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct LikeComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub comment: Account<'info, Comment>,
+    
+    // init fails if the PDA already exists, which rejects a second like
+    #[account(
+        init,
+        payer = user,
+        space = CommentInteraction::LEN,
+        seeds = [b"comment_like", user.key().as_ref(), comment.key().as_ref()],
+        bump
+    )]
+    pub comment_interaction: Account<'info, CommentInteraction>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlikeComment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub comment: Account<'info, Comment>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"comment_like", user.key().as_ref(), comment.key().as_ref()],
+        bump = comment_interaction.bump
+    )]
+    pub comment_interaction: Account<'info, CommentInteraction>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn like_comment(ctx: Context<LikeComment>) -> Result<()> {
+    let comment = &mut ctx.accounts.comment;
+    let like_count = like_count_after(&ctx.accounts.global_state, comment.likes_count, true)?;
+    let comment_interaction = &mut ctx.accounts.comment_interaction;
+    
+    comment_interaction.user = ctx.accounts.user.key();
+    comment_interaction.comment = comment.key();
+    comment_interaction.timestamp = Clock::get()?.unix_timestamp;
+    comment_interaction.bump = ctx.bumps.comment_interaction;
+    
+    comment.likes_count = like_count;
+    
+    emit!(CommentLiked {
+        comment: comment.key(),
+        user: ctx.accounts.user.key(),
+        like_count: comment.likes_count,
+        timestamp: comment_interaction.timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn unlike_comment(ctx: Context<UnlikeComment>) -> Result<()> {
+    let comment = &mut ctx.accounts.comment;
+    
+    comment.likes_count = like_count_after(&ctx.accounts.global_state, comment.likes_count, false)?;
+    
+    emit!(CommentUnliked {
+        comment: comment.key(),
+        user: ctx.accounts.user.key(),
+        like_count: comment.likes_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn like_count_after(global_state: &GlobalState, like_count: u64, like: bool) -> Result<u64> {
+    require_not_emergency_paused(global_state)?;
+    if like {
+        like_count.checked_add(1).ok_or_else(|| error!(SolSocialError::MathOverflow))
+    } else {
+        like_count.checked_sub(1).ok_or_else(|| error!(SolSocialError::MathUnderflow))
+    }
+}
+
+#[event]
+pub struct CommentLiked {
+    pub comment: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommentUnliked {
+    pub comment: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment_like_address(user: Pubkey, comment: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"comment_like", user.as_ref(), comment.as_ref()], &crate::ID).0
+    }
+
+    #[test]
+    fn test_double_like_maps_to_existing_account() {
+        let user = Pubkey::new_unique();
+        let comment = Pubkey::new_unique();
+
+        // The like PDA already exists after the first like, so the second init is rejected
+        assert_eq!(comment_like_address(user, comment), comment_like_address(user, comment));
+        assert_ne!(comment_like_address(user, comment), comment_like_address(Pubkey::new_unique(), comment));
+        assert_ne!(comment_like_address(user, comment), comment_like_address(user, Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_unlike_without_like_rejected() {
+        let global_state = GlobalState::default();
+        assert_eq!(like_count_after(&global_state, 0, true).unwrap(), 1);
+        assert_eq!(like_count_after(&global_state, 1, false).unwrap(), 0);
+        assert_eq!(
+            like_count_after(&global_state, 0, false).unwrap_err(),
+            SolSocialError::MathUnderflow.into()
+        );
+    }
+
+    #[test]
+    fn test_like_and_unlike_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            like_count_after(&global_state, 0, true).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(
+            like_count_after(&global_state, 1, false).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MarkMessagesRead<'info> {
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), reader.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.is_member_of(chat_room.key(), reader.key()) @ SolSocialError::NotChatMember
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    pub reader: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn mark_messages_read(ctx: Context<MarkMessagesRead>, up_to_message_id: u64) -> Result<()> {
+    let total_messages = ctx.accounts.chat_room.total_messages;
+    let participant = &mut ctx.accounts.participant;
+
+    if !advance_read(&ctx.accounts.global_state, participant, up_to_message_id, total_messages)? {
+        return Ok(());
+    }
+
+    emit!(MessagesRead {
+        chat_room: ctx.accounts.chat_room.key(),
+        reader: ctx.accounts.reader.key(),
+        last_read_message: participant.last_read_message,
+        unread_count: participant.unread_count(total_messages),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn advance_read(
+    global_state: &GlobalState,
+    participant: &mut ChatParticipant,
+    up_to_message_id: u64,
+    total_messages: u64,
+) -> Result<bool> {
+    require_not_emergency_paused(global_state)?;
+    participant.mark_read(up_to_message_id, total_messages)
+}
+
+#[event]
+pub struct MessagesRead {
+    pub chat_room: Pubkey,
+    pub reader: Pubkey,
+    pub last_read_message: u64,
+    pub unread_count: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_read_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut participant = ChatParticipant::default();
+        assert_eq!(
+            advance_read(&global_state, &mut participant, 3, 5).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(participant.last_read_message, 0);
+    }
+}
@@ -1,25 +1,198 @@
+pub mod initialize_user;
 pub mod create_profile;
 pub mod update_profile;
 pub mod create_post;
+pub mod interact_post;
 pub mod like_post;
 pub mod tip_user;
 pub mod buy_creator_tokens;
 pub mod sell_creator_tokens;
+pub mod create_keys;
+pub mod buy_keys;
+pub mod sell_keys;
 pub mod follow_user;
 pub mod unfollow_user;
 pub mod create_comment;
 pub mod initialize_creator_token;
 pub mod update_token_price;
+pub mod claim_holder_rewards;
+pub mod set_curve_params;
+pub mod delete_post;
+pub mod edit_post;
+pub mod like_comment;
+pub mod pin_post;
+pub mod create_chat;
+pub mod send_message;
+pub mod update_message;
+pub mod react_message;
+pub mod join_chat;
+pub mod leave_chat;
+pub mod transfer_chat_ownership;
+pub mod moderation;
+pub mod pin_message;
+pub mod dm_room;
+pub mod mark_messages_read;
+pub mod register_encryption_key;
+pub mod social_links;
+pub mod nsfw_filter;
+pub mod change_username;
+pub mod init_social_stats;
+pub mod vesting;
+pub mod set_revenue_share;
+pub mod deposit_revenue;
+pub mod transfer_keys;
+pub mod snapshot_holders;
+pub mod admin;
+pub mod report_content;
+pub mod premium_post;
+pub mod stake;
+pub mod governance;
+pub mod price_feed;
+pub mod check_in;
+pub mod close_user;
+pub mod get_market_cap;
+pub mod repost;
+pub mod notifications;
+pub mod query_pnl;
+pub mod initialize_revenue_pool;
 
+pub use initialize_user::*;
 pub use create_profile::*;
 pub use update_profile::*;
 pub use create_post::*;
+pub use interact_post::*;
 pub use like_post::*;
 pub use tip_user::*;
 pub use buy_creator_tokens::*;
 pub use sell_creator_tokens::*;
+pub use create_keys::*;
+pub use buy_keys::*;
+pub use sell_keys::*;
 pub use follow_user::*;
 pub use unfollow_user::*;
 pub use create_comment::*;
 pub use initialize_creator_token::*;
-pub use update_token_price::*;
\ No newline at end of file
+pub use update_token_price::*;
+pub use claim_holder_rewards::*;
+pub use set_curve_params::*;
+pub use delete_post::*;
+pub use edit_post::*;
+pub use like_comment::*;
+pub use pin_post::*;
+pub use create_chat::*;
+pub use send_message::*;
+pub use update_message::*;
+pub use react_message::*;
+pub use join_chat::*;
+pub use leave_chat::*;
+pub use transfer_chat_ownership::*;
+pub use moderation::*;
+pub use pin_message::*;
+pub use dm_room::*;
+pub use mark_messages_read::*;
+pub use register_encryption_key::*;
+pub use social_links::*;
+pub use nsfw_filter::*;
+pub use change_username::*;
+pub use init_social_stats::*;
+pub use vesting::*;
+pub use set_revenue_share::*;
+pub use deposit_revenue::*;
+pub use transfer_keys::*;
+pub use snapshot_holders::*;
+pub use admin::*;
+pub use report_content::*;
+pub use premium_post::*;
+pub use stake::*;
+pub use governance::*;
+pub use price_feed::*;
+pub use check_in::*;
+pub use close_user::*;
+pub use get_market_cap::*;
+pub use repost::*;
+pub use notifications::*;
+pub use query_pnl::*;
+pub use initialize_revenue_pool::*;
+
+/// Compile-level guard: each instruction's profile account must resolve to the
+/// canonical `UserProfile`, so reintroducing a parallel profile type breaks the build.
+#[cfg(test)]
+#[allow(dead_code)]
+mod canonical_profile {
+    use anchor_lang::prelude::*;
+
+    use super::*;
+    use crate::state::UserProfile;
+
+    fn profile(_: &Account<'_, UserProfile>) {}
+
+    fn initialize_user(accounts: &InitializeUser<'_>) {
+        profile(&accounts.user_profile);
+        if let Some(referrer_profile) = &accounts.referrer_profile {
+            profile(referrer_profile);
+        }
+    }
+
+    fn posts(create: &CreatePost<'_>, delete: &DeletePost<'_>, pin: &PinPost<'_>) {
+        profile(&create.user_profile);
+        profile(&delete.user_profile);
+        profile(&pin.user_profile);
+    }
+
+    fn interactions(
+        interact: &InteractPost<'_>,
+        release: &ReleaseEngagementRewards<'_>,
+        comment: &CommentPost<'_>,
+        reply: &ReplyToComment<'_>,
+        tip: &TipPost<'_>,
+    ) {
+        profile(&interact.creator_profile);
+        profile(&interact.user_profile);
+        profile(&release.creator_profile);
+        profile(&comment.user_profile);
+        profile(&reply.user_profile);
+        profile(&tip.creator_profile);
+        profile(&tip.tipper_profile);
+    }
+
+    fn keys(buy: &BuyKeys<'_>, sell: &SellKeys<'_>) {
+        profile(&buy.user_account);
+        profile(&sell.user_account);
+        profile(&sell.subject_account);
+    }
+
+    fn messaging(send: &SendMessage<'_>, create_chat: &CreateChat<'_>, register: &RegisterEncryptionKey<'_>) {
+        profile(&send.sender_profile);
+        profile(&send.recipient_profile);
+        profile(&create_chat.user_profile);
+        profile(&register.user_profile);
+    }
+
+    fn account_lifecycle(close: &CloseUser<'_>, verify: &VerifyUser<'_>, stake: &StakeTokens<'_>, unstake: &UnstakeTokens<'_>) {
+        profile(&close.user_profile);
+        profile(&verify.user_profile);
+        profile(&stake.user);
+        profile(&unstake.user);
+    }
+
+    fn root_program(
+        initialize: &crate::InitializeUser<'_>,
+        create_post: &crate::CreatePost<'_>,
+        follow: &crate::FollowUser<'_>,
+        buy: &crate::BuyUserTokens<'_>,
+        sell: &crate::SellUserTokens<'_>,
+    ) {
+        profile(&initialize.user_account);
+        profile(&create_post.user_account);
+        profile(&follow.follower_account);
+        profile(&follow.following_account);
+        profile(&buy.user_account);
+        profile(&sell.user_account);
+    }
+
+    #[test]
+    fn test_canonical_profile_fits_in_one_init() {
+        // Accounts created through CPI are limited to 10 KiB
+        assert!(UserProfile::LEN <= 10 * 1024);
+    }
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct SetModerator<'info> {
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), member.as_ref()],
+        bump = member_participant.bump
+    )]
+    pub member_participant: Account<'info, ChatParticipant>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct MuteMember<'info> {
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"chat_participant", chat_room.key().as_ref(), actor.key().as_ref()],
+        bump = actor_participant.bump
+    )]
+    pub actor_participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), member.as_ref()],
+        bump = member_participant.bump
+    )]
+    pub member_participant: Account<'info, ChatParticipant>,
+
+    pub actor: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn set_moderator(ctx: Context<SetModerator>, member: Pubkey, is_moderator: bool) -> Result<()> {
+    apply_moderator(
+        &ctx.accounts.global_state,
+        &ctx.accounts.chat_room,
+        ctx.accounts.authority.key(),
+        &mut ctx.accounts.member_participant,
+        is_moderator,
+    )?;
+
+    emit!(ModeratorSet {
+        chat_room: ctx.accounts.chat_room.key(),
+        member,
+        is_moderator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn mute_member(ctx: Context<MuteMember>, member: Pubkey, is_muted: bool) -> Result<()> {
+    apply_mute(
+        &ctx.accounts.global_state,
+        &ctx.accounts.chat_room,
+        &ctx.accounts.actor_participant,
+        &mut ctx.accounts.member_participant,
+        is_muted,
+    )?;
+
+    emit!(MemberMuteSet {
+        chat_room: ctx.accounts.chat_room.key(),
+        member,
+        muted_by: ctx.accounts.actor.key(),
+        is_muted,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn apply_moderator(
+    global_state: &GlobalState,
+    chat_room: &ChatRoom,
+    signer: Pubkey,
+    member: &mut ChatParticipant,
+    is_moderator: bool,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    chat_room.set_moderator(signer, member, is_moderator)
+}
+
+fn apply_mute(
+    global_state: &GlobalState,
+    chat_room: &ChatRoom,
+    actor: &ChatParticipant,
+    member: &mut ChatParticipant,
+    is_muted: bool,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    chat_room.set_muted(actor, member, is_muted)
+}
+
+#[event]
+pub struct ModeratorSet {
+    pub chat_room: Pubkey,
+    pub member: Pubkey,
+    pub is_moderator: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MemberMuteSet {
+    pub chat_room: Pubkey,
+    pub member: Pubkey,
+    pub muted_by: Pubkey,
+    pub is_muted: bool,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moderation_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let chat_room = ChatRoom { authority: Pubkey::new_unique(), ..Default::default() };
+        let admin = ChatParticipant { user: chat_room.authority, ..Default::default() };
+        let mut member = ChatParticipant { user: Pubkey::new_unique(), ..Default::default() };
+
+        assert_eq!(
+            apply_moderator(&global_state, &chat_room, chat_room.authority, &mut member, true).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(
+            apply_mute(&global_state, &chat_room, &admin, &mut member, true).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert!(!member.is_moderator && !member.is_muted);
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MarkNotificationRead<'info> {
+    #[account(
+        mut,
+        constraint = notification.recipient == recipient.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub notification: Account<'info, Notification>,
+
+    pub recipient: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClearReadNotifications<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+pub fn mark_notification_read(ctx: Context<MarkNotificationRead>) -> Result<()> {
+    let notification = &mut ctx.accounts.notification;
+    if notification.read {
+        return Ok(());
+    }
+
+    notification.mark_read(ctx.accounts.recipient.key())?;
+
+    emit!(NotificationRead {
+        notification: notification.key(),
+        recipient: ctx.accounts.recipient.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Notifications are passed in remaining_accounts; unread ones are left untouched.
+pub fn clear_read_notifications<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClearReadNotifications<'info>>,
+) -> Result<()> {
+    let recipient = &ctx.accounts.recipient;
+    let mut cleared: u32 = 0;
+
+    for notification_info in ctx.remaining_accounts.iter() {
+        let notification = Account::<Notification>::try_from(notification_info)?;
+        if !notification.is_clearable_by(recipient.key())? {
+            continue;
+        }
+
+        notification.close(recipient.to_account_info())?;
+        cleared = cleared.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
+    }
+
+    emit!(NotificationsCleared {
+        recipient: recipient.key(),
+        cleared,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NotificationRead {
+    pub notification: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NotificationsCleared {
+    pub recipient: Pubkey,
+    pub cleared: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetNsfwFilter<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+/// Opts a profile in or out of NSFW-labelled posts. The label is self-applied by
+/// authors, so this is a filter for clients rather than an enforcement mechanism.
+pub fn set_nsfw_filter(ctx: Context<SetNsfwFilter>, blocks_nsfw: bool) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+
+    user_profile.blocks_nsfw = blocks_nsfw;
+    user_profile.updated_at = clock.unix_timestamp;
+
+    emit!(NsfwFilterSet {
+        user: ctx.accounts.user.key(),
+        blocks_nsfw,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NsfwFilterSet {
+    pub user: Pubkey,
+    pub blocks_nsfw: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct PinMessage<'info> {
+    #[account(mut)]
+    pub message: Account<'info, Message>,
+
+    #[account(mut, address = message.chat_room)]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"chat_participant", chat_room.key().as_ref(), actor.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.can_moderate(&chat_room) @ SolSocialError::UnauthorizedAccess
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub actor: Signer<'info>,
+}
+
+pub fn pin_message(ctx: Context<PinMessage>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let message = &mut ctx.accounts.message;
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    if chat_room.pin_message(message)? {
+        emit!(MessagePinned {
+            message: message.key(),
+            chat_room: chat_room.key(),
+            pinned_by: ctx.accounts.actor.key(),
+            pinned_messages: chat_room.pinned_messages,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn unpin_message(ctx: Context<PinMessage>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let message = &mut ctx.accounts.message;
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    if chat_room.unpin_message(message)? {
+        emit!(MessageUnpinned {
+            message: message.key(),
+            chat_room: chat_room.key(),
+            unpinned_by: ctx.accounts.actor.key(),
+            pinned_messages: chat_room.pinned_messages,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct MessagePinned {
+    pub message: Pubkey,
+    pub chat_room: Pubkey,
+    pub pinned_by: Pubkey,
+    pub pinned_messages: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MessageUnpinned {
+    pub message: Pubkey,
+    pub chat_room: Pubkey,
+    pub unpinned_by: Pubkey,
+    pub pinned_messages: u8,
+    pub timestamp: i64,
+}
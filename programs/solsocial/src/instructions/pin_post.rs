@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+pub const MAX_PINNED_POSTS: u8 = 3;
+
+#[derive(Accounts)]
+pub struct PinPost<'info> {
+    #[account(
+        mut,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser,
+        constraint = !post.is_deleted @ SolSocialError::PostNotFound
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub author: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn pin_post(ctx: Context<PinPost>) -> Result<()> {
+    apply_pin(
+        &ctx.accounts.global_state,
+        &mut ctx.accounts.post.is_pinned,
+        &mut ctx.accounts.user_profile.pinned_count,
+        true,
+    )
+}
+
+pub fn unpin_post(ctx: Context<PinPost>) -> Result<()> {
+    apply_pin(
+        &ctx.accounts.global_state,
+        &mut ctx.accounts.post.is_pinned,
+        &mut ctx.accounts.user_profile.pinned_count,
+        false,
+    )
+}
+
+/// Shared by pin and unpin; asking for the state the post is already in is a no-op.
+fn apply_pin(global_state: &GlobalState, is_pinned: &mut bool, pinned_count: &mut u8, pin: bool) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    if *is_pinned == pin {
+        return Ok(());
+    }
+
+    *pinned_count = pinned_count_after(*pinned_count, pin)?;
+    *is_pinned = pin;
+    Ok(())
+}
+
+pub fn pinned_count_after(pinned_count: u8, pin: bool) -> Result<u8> {
+    if pin {
+        require!(
+            pinned_count < MAX_PINNED_POSTS,
+            SolSocialError::MaxPinnedPostsExceeded
+        );
+        Ok(pinned_count + 1)
+    } else {
+        pinned_count
+            .checked_sub(1)
+            .ok_or_else(|| error!(SolSocialError::MathUnderflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fourth_pin_rejected_until_one_is_unpinned() {
+        let mut pinned = 0;
+        for _ in 0..3 {
+            pinned = pinned_count_after(pinned, true).unwrap();
+        }
+        assert!(pinned_count_after(pinned, true).is_err());
+
+        pinned = pinned_count_after(pinned, false).unwrap();
+        assert_eq!(pinned_count_after(pinned, true).unwrap(), MAX_PINNED_POSTS);
+    }
+
+    #[test]
+    fn test_pin_and_unpin_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let (mut is_pinned, mut pinned_count) = (false, 0);
+        assert_eq!(
+            apply_pin(&global_state, &mut is_pinned, &mut pinned_count, true).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert!(!is_pinned);
+
+        let (mut is_pinned, mut pinned_count) = (true, 1);
+        assert!(apply_pin(&global_state, &mut is_pinned, &mut pinned_count, false).is_err());
+        assert_eq!(pinned_count, 1);
+    }
+}
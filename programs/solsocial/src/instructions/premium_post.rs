@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ConfigurePremium<'info> {
+    #[account(
+        mut,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockPremiumPost<'info> {
+    #[account(
+        constraint = post.is_premium @ SolSocialError::FeatureRequiresSubscription
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        init_if_needed,
+        payer = reader,
+        space = PremiumAccess::LEN,
+        seeds = [b"premium_access", post.author.as_ref(), reader.key().as_ref()],
+        bump
+    )]
+    pub premium_access: Account<'info, PremiumAccess>,
+
+    /// CHECK: Receives the unlock payment; checked against the post author
+    #[account(
+        mut,
+        constraint = author.key() == post.author @ SolSocialError::UnauthorizedUser
+    )]
+    pub author: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub reader: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_premium(
+    ctx: Context<ConfigurePremium>,
+    premium_price: u64,
+    premium_key_ciphertext: Vec<u8>,
+) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    apply_premium(&ctx.accounts.global_state, post, premium_price, premium_key_ciphertext)?;
+    post.updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn unlock_premium_post(ctx: Context<UnlockPremiumPost>) -> Result<()> {
+    check_unlock(&ctx.accounts.global_state, ctx.accounts.reader.key(), ctx.accounts.post.author)?;
+
+    let clock = Clock::get()?;
+    let premium_access = &mut ctx.accounts.premium_access;
+    let newly_created = premium_access.created_at == 0;
+
+    if premium_access.unlock(clock.unix_timestamp)? {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reader.to_account_info(),
+                    to: ctx.accounts.author.to_account_info(),
+                },
+            ),
+            ctx.accounts.post.premium_price,
+        )?;
+    }
+
+    if newly_created {
+        premium_access.user = ctx.accounts.reader.key();
+        premium_access.profile_owner = ctx.accounts.post.author;
+        premium_access.created_at = clock.unix_timestamp;
+        premium_access.bump = ctx.bumps.premium_access;
+    }
+
+    emit!(PremiumPostUnlocked {
+        post: ctx.accounts.post.key(),
+        reader: ctx.accounts.reader.key(),
+        author: ctx.accounts.post.author,
+        premium_key_ciphertext: ctx.accounts.post.premium_key_ciphertext.clone(),
+        expires_at: premium_access.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn apply_premium(
+    global_state: &GlobalState,
+    post: &mut Post,
+    premium_price: u64,
+    premium_key_ciphertext: Vec<u8>,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    post.configure_premium(premium_price, premium_key_ciphertext)
+}
+
+/// Authors already hold the key, so they never pay for their own post.
+fn check_unlock(global_state: &GlobalState, reader: Pubkey, author: Pubkey) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(reader != author, SolSocialError::UnauthorizedAccess);
+    Ok(())
+}
+
+#[event]
+pub struct PremiumPostUnlocked {
+    pub post: Pubkey,
+    pub reader: Pubkey,
+    pub author: Pubkey,
+    pub premium_key_ciphertext: Vec<u8>,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_premium_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut post = Post::default();
+        assert_eq!(
+            apply_premium(&global_state, &mut post, 1_000, vec![1, 2, 3]).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert!(!post.is_premium);
+        assert_eq!(
+            check_unlock(&global_state, Pubkey::new_unique(), Pubkey::new_unique()).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
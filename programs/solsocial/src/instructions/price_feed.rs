@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.oracle_authority == oracle.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = TokenPrice::LEN,
+        seeds = [b"token_price", mint.key().as_ref()],
+        bump
+    )]
+    pub token_price: Account<'info, TokenPrice>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_price_feed(
+    ctx: Context<UpdatePriceFeed>,
+    price_in_sol: u64,
+    price_in_usdc: u64,
+) -> Result<()> {
+    require!(price_in_sol > 0 && price_in_usdc > 0, SolSocialError::InvalidOracleData);
+
+    let clock = Clock::get()?;
+    let token_price = &mut ctx.accounts.token_price;
+    require!(clock.unix_timestamp >= token_price.last_updated, SolSocialError::InvalidOracleData);
+
+    token_price.mint = ctx.accounts.mint.key();
+    token_price.price_in_sol = price_in_sol;
+    token_price.price_in_usdc = price_in_usdc;
+    token_price.last_updated = clock.unix_timestamp;
+    token_price.bump = ctx.bumps.token_price;
+
+    emit!(PriceFeedUpdated {
+        mint: token_price.mint,
+        price_in_sol,
+        price_in_usdc,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PriceFeedUpdated {
+    pub mint: Pubkey,
+    pub price_in_sol: u64,
+    pub price_in_usdc: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::bonding_curve::calculate_price_with_mode;
+
+#[derive(Accounts)]
+pub struct QueryPnl<'info> {
+    pub token_holder: Account<'info, TokenHolder>,
+
+    #[account(
+        seeds = [b"user_profile", token_holder.profile_owner.as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"creator_token", token_holder.profile_owner.as_ref()],
+        bump = creator_token.bump,
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+}
+
+pub fn query_pnl(ctx: Context<QueryPnl>) -> Result<()> {
+    let token_holder = &ctx.accounts.token_holder;
+    let creator_token = &ctx.accounts.creator_token;
+    let current_price = calculate_price_with_mode(
+        ctx.accounts.user_account.token_supply,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+    )?;
+
+    // Portfolio UIs subscribe to this rather than re-deriving cost basis and the curve
+    emit!(HolderPnL {
+        holder: token_holder.holder,
+        key_owner: token_holder.profile_owner,
+        realized_pnl: token_holder.realized_pnl,
+        unrealized_pnl: token_holder.unrealized_pnl(current_price)?,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct HolderPnL {
+    pub holder: Pubkey,
+    pub key_owner: Pubkey,
+    pub realized_pnl: i64,
+    pub unrealized_pnl: i64,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReactToMessage<'info> {
+    #[account(
+        mut,
+        constraint = !message.is_deleted @ SolSocialError::MessageNotFound
+    )]
+    pub message: Account<'info, Message>,
+
+    #[account(
+        constraint = participant.user == reactor.key() @ SolSocialError::UnauthorizedAccess,
+        constraint = participant.chat_room == message.chat_room @ SolSocialError::UnauthorizedAccess
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    pub reactor: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn add_reaction(ctx: Context<ReactToMessage>, emoji: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let message = &mut ctx.accounts.message;
+
+    react(
+        &ctx.accounts.global_state,
+        message,
+        ctx.accounts.reactor.key(),
+        Some(emoji.clone()),
+        clock.unix_timestamp,
+    )?;
+
+    emit!(ReactionAdded {
+        message: message.key(),
+        reactor: ctx.accounts.reactor.key(),
+        emoji,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn remove_reaction(ctx: Context<ReactToMessage>) -> Result<()> {
+    let clock = Clock::get()?;
+    let message = &mut ctx.accounts.message;
+
+    react(&ctx.accounts.global_state, message, ctx.accounts.reactor.key(), None, clock.unix_timestamp)?;
+
+    emit!(ReactionRemoved {
+        message: message.key(),
+        reactor: ctx.accounts.reactor.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Adds `emoji` as the reactor's reaction, or clears their reaction when there is none.
+fn react(
+    global_state: &GlobalState,
+    message: &mut Message,
+    reactor: Pubkey,
+    emoji: Option<String>,
+    now: i64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    match emoji {
+        Some(emoji) => message.add_reaction(reactor, emoji, now),
+        None => message.remove_reaction(reactor),
+    }
+}
+
+#[event]
+pub struct ReactionAdded {
+    pub message: Pubkey,
+    pub reactor: Pubkey,
+    pub emoji: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReactionRemoved {
+    pub message: Pubkey,
+    pub reactor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reactions_blocked_while_paused() {
+        let reactor = Pubkey::new_unique();
+        let mut global_state = GlobalState::default();
+        let mut message = Message::default();
+        react(&global_state, &mut message, reactor, Some("🔥".to_string()), 0).unwrap();
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            react(&global_state, &mut message, reactor, None, 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(
+            react(&global_state, &mut Message::default(), reactor, Some("👍".to_string()), 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(message.reactions.len(), 1);
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RegisterEncryptionKey<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+pub fn register_encryption_key(ctx: Context<RegisterEncryptionKey>, encryption_pubkey: [u8; 32]) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.register_encryption_key(encryption_pubkey)?;
+
+    emit!(EncryptionKeyRegistered {
+        user: ctx.accounts.user.key(),
+        encryption_pubkey,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct EncryptionKeyRegistered {
+    pub user: Pubkey,
+    pub encryption_pubkey: [u8; 32],
+    pub timestamp: i64,
+}
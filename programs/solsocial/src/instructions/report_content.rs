@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::admin::require_platform_authority;
+
+#[derive(Accounts)]
+#[instruction(content_type: ContentType, content_id: u64)]
+pub struct CreateReport<'info> {
+    // One report per reporter and content id; a repeat report fails on init
+    #[account(
+        init,
+        payer = reporter,
+        space = ReportedContent::LEN,
+        seeds = [b"report", reporter.key().as_ref(), &content_id.to_le_bytes()],
+        bump
+    )]
+    pub report: Account<'info, ReportedContent>,
+
+    /// CHECK: The reported post, comment or profile; only its address is recorded
+    pub content: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveReport<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"report", report.reporter.as_ref(), &report.content_id.to_le_bytes()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, ReportedContent>,
+
+    pub moderator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ModeratePost<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [b"report", report.reporter.as_ref(), &report.content_id.to_le_bytes()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, ReportedContent>,
+
+    // Kept rather than closed so the moderated content remains auditable
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    pub moderator: Signer<'info>,
+}
+
+pub fn create_report(
+    ctx: Context<CreateReport>,
+    content_type: ContentType,
+    content_id: u64,
+    reason: String,
+) -> Result<()> {
+    check_report(&ctx.accounts.global_state, &reason)?;
+
+    let clock = Clock::get()?;
+    let report = &mut ctx.accounts.report;
+    report.id = clock.slot;
+    report.reporter = ctx.accounts.reporter.key();
+    report.content_type = content_type.clone();
+    report.content_id = content_id;
+    report.content = ctx.accounts.content.key();
+    report.reason = reason;
+    report.status = ReportStatus::Pending;
+    report.created_at = clock.unix_timestamp;
+    report.resolved_at = None;
+    report.bump = ctx.bumps.report;
+
+    emit!(ContentReported {
+        report: report.key(),
+        reporter: report.reporter,
+        content_type,
+        content_id,
+        content: report.content,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn check_report(global_state: &GlobalState, reason: &str) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(
+        !reason.trim().is_empty() && reason.len() <= ReportedContent::MAX_REASON_LENGTH,
+        SolSocialError::ContentSizeLimitExceeded
+    );
+    Ok(())
+}
+
+pub fn resolve_report(ctx: Context<ResolveReport>, new_status: ReportStatus) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.moderator.key())?;
+
+    let clock = Clock::get()?;
+    let report = &mut ctx.accounts.report;
+    report.transition(new_status.clone(), clock.unix_timestamp)?;
+
+    emit!(ReportStatusChanged {
+        report: report.key(),
+        moderator: ctx.accounts.moderator.key(),
+        status: new_status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn moderate_post(ctx: Context<ModeratePost>, action: ModerationAction) -> Result<()> {
+    require_platform_authority(ctx.accounts.global_state.authority, ctx.accounts.moderator.key())?;
+
+    let post = &mut ctx.accounts.post;
+    ctx.accounts.report.require_upheld_against(ContentType::Post, post.key())?;
+    post.moderate(action)?;
+
+    emit!(PostModerated {
+        post: post.key(),
+        report: ctx.accounts.report.key(),
+        moderator: ctx.accounts.moderator.key(),
+        action,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ContentReported {
+    pub report: Pubkey,
+    pub reporter: Pubkey,
+    pub content_type: ContentType,
+    pub content_id: u64,
+    pub content: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostModerated {
+    pub post: Pubkey,
+    pub report: Pubkey,
+    pub moderator: Pubkey,
+    pub action: ModerationAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReportStatusChanged {
+    pub report: Pubkey,
+    pub moderator: Pubkey,
+    pub status: ReportStatus,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_address(reporter: Pubkey, content_id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"report", reporter.as_ref(), &content_id.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    #[test]
+    fn test_duplicate_report_maps_to_existing_account() {
+        let reporter = Pubkey::new_unique();
+
+        // Same reporter and content resolve to the same PDA, so the second init is rejected
+        assert_eq!(report_address(reporter, 7), report_address(reporter, 7));
+        assert_ne!(report_address(reporter, 7), report_address(reporter, 8));
+        assert_ne!(report_address(reporter, 7), report_address(Pubkey::new_unique(), 7));
+    }
+
+    #[test]
+    fn test_report_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        assert!(check_report(&global_state, "spam").is_ok());
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            check_report(&global_state, "spam").unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(comment: Option<String>)]
+pub struct Repost<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+
+    #[account(mut)]
+    pub original: Account<'info, Post>,
+
+    #[account(
+        init,
+        payer = author,
+        space = Post::space_for(comment.as_ref().map_or(0, String::len), &[]),
+        seeds = [b"repost", author.key().as_ref(), original.key().as_ref()],
+        bump
+    )]
+    pub repost: Account<'info, Post>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn repost(ctx: Context<Repost>, comment: Option<String>) -> Result<()> {
+    let original = &mut ctx.accounts.original;
+    check_repost(&ctx.accounts.global_state, original, comment.as_deref())?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let repost = &mut ctx.accounts.repost;
+    repost.initialize_repost(
+        ctx.accounts.author.key(),
+        original.key(),
+        comment,
+        timestamp,
+        ctx.bumps.repost,
+    )?;
+
+    original.share()?;
+
+    emit!(PostReposted {
+        post: repost.key(),
+        original: original.key(),
+        author: ctx.accounts.author.key(),
+        is_quote: !repost.content.is_empty(),
+        share_count: original.share_count,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+fn check_repost(global_state: &GlobalState, original: &Post, comment: Option<&str>) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    global_state.check_post_length(comment.unwrap_or_default())?;
+    original.validate_repost_target()
+}
+
+#[event]
+pub struct PostReposted {
+    pub post: Pubkey,
+    pub original: Pubkey,
+    pub author: Pubkey,
+    pub is_quote: bool,
+    pub share_count: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repost_blocked_while_paused() {
+        let original = Post::default();
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            check_repost(&global_state, &original, None).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+
+    #[test]
+    fn test_plain_repost() {
+        let mut original = Post { content: "gm".to_string(), ..Default::default() };
+        let original_key = Pubkey::new_unique();
+        check_repost(&GlobalState::default(), &original, None).unwrap();
+
+        let mut repost = Post::default();
+        repost.initialize_repost(Pubkey::new_unique(), original_key, None, 10, 254).unwrap();
+        original.share().unwrap();
+
+        assert_eq!(repost.repost_of, Some(original_key));
+        assert!(repost.content.is_empty());
+        assert_eq!(original.share_count, 1);
+    }
+
+    #[test]
+    fn test_quote_repost_keeps_comment() {
+        let mut repost = Post::default();
+        repost
+            .initialize_repost(Pubkey::new_unique(), Pubkey::new_unique(), Some("this".to_string()), 10, 254)
+            .unwrap();
+        assert_eq!(repost.content, "this");
+
+        // The repost cannot itself be reposted
+        assert_eq!(
+            check_repost(&GlobalState::default(), &repost, None).unwrap_err(),
+            SolSocialError::RepostDepthExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_quote_length_follows_global_limit() {
+        let original = Post::default();
+        let global_state = GlobalState { max_post_length: 10, ..Default::default() };
+        assert!(check_repost(&global_state, &original, Some(&"a".repeat(10))).is_ok());
+        assert_eq!(
+            check_repost(&global_state, &original, Some(&"a".repeat(11))).unwrap_err(),
+            SolSocialError::ContentTooLong.into()
+        );
+    }
+
+    #[test]
+    fn test_moderated_original_cannot_be_reposted() {
+        let original = Post { is_hidden: true, ..Default::default() };
+        assert_eq!(
+            check_repost(&GlobalState::default(), &original, None).unwrap_err(),
+            SolSocialError::ContentModerationFailed.into()
+        );
+    }
+}
@@ -1,27 +1,29 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::instructions::admin::check_trading_open;
+use crate::utils::bonding_curve::{calculate_price_impact_with_mode, calculate_sell_proceeds_with_mode};
 
 #[derive(Accounts)]
+#[instruction(amount: u64, min_proceeds: u64)]
 pub struct SellKeys<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"user", seller.key().as_ref()],
+        seeds = [b"user_profile", seller.key().as_ref()],
         bump = user_account.bump,
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
     
     #[account(
         mut,
-        seeds = [b"subject", subject.key().as_ref()],
+        seeds = [b"user_profile", subject.key().as_ref()],
         bump = subject_account.bump,
     )]
-    pub subject_account: Account<'info, UserAccount>,
+    pub subject_account: Account<'info, UserProfile>,
     
     /// CHECK: This is the subject whose keys are being sold
     pub subject: AccountInfo<'info>,
@@ -34,6 +36,26 @@ pub struct SellKeys<'info> {
     )]
     pub key_holding: Account<'info, KeyHolding>,
     
+    /// Subject's key stats; `holders_count` follows first buys and full exits
+    #[account(
+        mut,
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [b"protocol"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
     #[account(
         mut,
         associated_token::mint = sol_mint,
@@ -51,81 +73,192 @@ pub struct SellKeys<'info> {
     #[account(
         mut,
         associated_token::mint = sol_mint,
-        associated_token::authority = protocol_fee_destination,
+        associated_token::authority = protocol_config,
     )]
     pub protocol_fee_account: Account<'info, TokenAccount>,
     
-    /// CHECK: This is the protocol fee destination
-    pub protocol_fee_destination: AccountInfo<'info>,
+    /// CHECK: PDA that owns the bonding-curve reserve for this subject
+    #[account(
+        seeds = [b"reserve", subject.key().as_ref()],
+        bump,
+    )]
+    pub reserve_authority: AccountInfo<'info>,
     
-    /// CHECK: This is the SOL mint
+    #[account(
+        mut,
+        associated_token::mint = sol_mint,
+        associated_token::authority = reserve_authority,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Pinned to the protocol's payment mint
+    #[account(address = protocol_config.payment_mint @ SolSocialError::InvalidPaymentMint)]
     pub sol_mint: AccountInfo<'info>,
     
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = TokenPrice::LEN,
+        seeds = [b"token_price", subject.key().as_ref()],
+        bump,
+    )]
+    pub token_price: Account<'info, TokenPrice>,
+    
+    /// Subject's curve; sells pay out along the same curve buys paid into
+    #[account(
+        mut,
+        seeds = [b"creator_token", subject.key().as_ref()],
+        bump = creator_token.bump,
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+    
+    /// Holder reward accumulator for this creator; sells settle against it
+    #[account(
+        seeds = [b"reward_cursor", subject.key().as_ref()],
+        bump = reward_cursor.bump,
+    )]
+    pub reward_cursor: Account<'info, RewardDistributionCursor>,
+    
+    /// Seller's holder record for this creator; the average price survives the sale
+    #[account(
+        mut,
+        seeds = [b"token_holder", subject.key().as_ref(), seller.key().as_ref()],
+        bump = token_holder.bump,
+    )]
+    pub token_holder: Account<'info, TokenHolder>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
+#[derive(Accounts)]
+pub struct CloseKeyHolding<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    /// CHECK: This is the subject whose keys were held
+    pub subject: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"keys", subject.key().as_ref(), seller.key().as_ref()],
+        bump = key_holding.bump,
+    )]
+    pub key_holding: Account<'info, KeyHolding>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn sell_keys(ctx: Context<SellKeys>, amount: u64, min_proceeds: u64) -> Result<()> {
     let seller = &ctx.accounts.seller;
     let user_account = &mut ctx.accounts.user_account;
     let subject_account = &mut ctx.accounts.subject_account;
     let key_holding = &mut ctx.accounts.key_holding;
     
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    check_trading_open(&ctx.accounts.global_state)?;
+    subject_account.require_not_frozen()?;
     require!(amount > 0, SolSocialError::InvalidAmount);
     require!(key_holding.amount >= amount, SolSocialError::InsufficientKeys);
+    check_creator_floor(seller.key(), ctx.accounts.subject.key(), key_holding.amount, amount)?;
     
     // Calculate the current supply before selling
-    let supply = subject_account.key_supply;
+    let supply = subject_account.token_supply;
     require!(supply > 0, SolSocialError::NoKeysInCirculation);
     
     // Calculate sell price using bonding curve
-    let sell_price = get_sell_price(supply, amount)?;
-    
-    // Calculate fees
-    let protocol_fee = sell_price
-        .checked_mul(PROTOCOL_FEE_PERCENT)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
+    let sell_price = get_sell_price(supply, amount, &ctx.accounts.creator_token)?;
     
-    let subject_fee = sell_price
-        .checked_mul(SUBJECT_FEE_PERCENT)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // Calculate fees; the protocol's cut is discounted by the seller's volume tier
+    let creator_token = &ctx.accounts.creator_token;
+    let price_impact = calculate_price_impact_with_mode(
+        supply,
+        amount,
+        false,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+    )?;
+    let (protocol_fee, subject_fee, seller_proceeds) = split_sell_price(
+        &ctx.accounts.global_state,
+        sell_price,
+        price_impact,
+        user_account.total_volume,
+    )?;
     
-    let seller_proceeds = sell_price
-        .checked_sub(protocol_fee)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_sub(subject_fee)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // Abort before any state change if proceeds fell below what the seller signed for
+    check_min_proceeds(seller_proceeds, min_proceeds)?;
     
     // Update key holding
     key_holding.amount = key_holding.amount
         .checked_sub(amount)
         .ok_or(SolSocialError::MathOverflow)?;
+    key_holding.sale_count = key_holding.sale_count
+        .checked_add(1)
+        .ok_or(SolSocialError::MathOverflow)?;
+    key_holding.total_earned = key_holding.total_earned
+        .checked_add(seller_proceeds)
+        .ok_or(SolSocialError::MathOverflow)?;
+    key_holding.last_trade_timestamp = Clock::get()?.unix_timestamp;
+    
+    // A seller who exits fully no longer counts as a holder
+    ctx.accounts.user_keys.update_after_trade(
+        i64::try_from(amount).map_err(|_| SolSocialError::MathOverflow)?,
+        false,
+        key_holding.amount,
+        creator_token.price_curve_type,
+        &creator_token.curve_params(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    ctx.accounts.token_holder.record_sell(
+        amount,
+        sell_price,
+        ctx.accounts.reward_cursor.rewards_per_token,
+        supply,
+        Clock::get()?.unix_timestamp,
+    )?;
     
     // Update subject's key supply
-    subject_account.key_supply = subject_account.key_supply
+    ctx.accounts.creator_token.record_trade(amount, false)?;
+    subject_account.token_supply = subject_account.token_supply
         .checked_sub(amount)
         .ok_or(SolSocialError::MathOverflow)?;
     
     // Update trading volume
-    subject_account.trading_volume = subject_account.trading_volume
+    subject_account.total_volume = subject_account.total_volume
         .checked_add(sell_price)
         .ok_or(SolSocialError::MathOverflow)?;
     
+    require!(
+        ctx.accounts.reserve_vault.amount >= sell_price,
+        SolSocialError::InsufficientVaultBalance
+    );
+    
+    let subject_key = ctx.accounts.subject.key();
+    let reserve_seeds = &[
+        b"reserve".as_ref(),
+        subject_key.as_ref(),
+        &[ctx.bumps.reserve_authority],
+    ];
+    let signer_seeds = &[&reserve_seeds[..]];
+    
     // Transfer seller proceeds
     if seller_proceeds > 0 {
         let transfer_instruction = Transfer {
-            from: ctx.accounts.protocol_fee_account.to_account_info(),
+            from: ctx.accounts.reserve_vault.to_account_info(),
             to: ctx.accounts.seller_token_account.to_account_info(),
-            authority: ctx.accounts.protocol_fee_destination.to_account_info(),
+            authority: ctx.accounts.reserve_authority.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             transfer_instruction,
+            signer_seeds,
         );
         
         token::transfer(cpi_ctx, seller_proceeds)?;
@@ -134,19 +267,43 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
     // Transfer subject fee
     if subject_fee > 0 {
         let transfer_instruction = Transfer {
-            from: ctx.accounts.protocol_fee_account.to_account_info(),
+            from: ctx.accounts.reserve_vault.to_account_info(),
             to: ctx.accounts.subject_token_account.to_account_info(),
-            authority: ctx.accounts.protocol_fee_destination.to_account_info(),
+            authority: ctx.accounts.reserve_authority.to_account_info(),
         };
         
-        let cpi_ctx = CpiContext::new(
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             transfer_instruction,
+            signer_seeds,
         );
         
         token::transfer(cpi_ctx, subject_fee)?;
     }
     
+    // Transfer protocol fee
+    if protocol_fee > 0 {
+        let transfer_instruction = Transfer {
+            from: ctx.accounts.reserve_vault.to_account_info(),
+            to: ctx.accounts.protocol_fee_account.to_account_info(),
+            authority: ctx.accounts.reserve_authority.to_account_info(),
+        };
+        
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_instruction,
+            signer_seeds,
+        );
+        
+        token::transfer(cpi_ctx, protocol_fee)?;
+    }
+    
+    // Roll the 24h price/volume window at the average price received per key
+    let token_price = &mut ctx.accounts.token_price;
+    token_price.mint = subject_key;
+    token_price.bump = ctx.bumps.token_price;
+    token_price.roll_price_window(sell_price / amount, sell_price, Clock::get()?.unix_timestamp)?;
+    
     // Update user's total keys held
     user_account.total_keys_held = user_account.total_keys_held
         .checked_sub(amount)
@@ -165,53 +322,63 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
         price: sell_price,
         protocol_fee,
         subject_fee,
-        supply: subject_account.key_supply,
+        supply: subject_account.token_supply,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
-    // Close key holding account if amount is zero
-    if key_holding.amount == 0 {
-        key_holding.close(seller.to_account_info())?;
-    }
-    
     Ok(())
 }
 
-fn get_sell_price(supply: u64, amount: u64) -> Result<u64> {
-    require!(supply >= amount, SolSocialError::InsufficientSupply);
-    
-    if supply == 0 || amount == 0 {
-        return Ok(0);
-    }
+/// Reclaims rent once a holding is fully sold. Kept separate from `sell_keys` so a
+/// partial sell never closes the account; clients can bundle both in one transaction.
+pub fn close_key_holding(ctx: Context<CloseKeyHolding>) -> Result<()> {
+    // `close = seller` only runs if this returns Ok, so a non-empty holding is kept
+    check_holding_closable(&ctx.accounts.global_state, ctx.accounts.key_holding.amount)
+}
+
+fn get_sell_price(supply: u64, amount: u64, curve: &CreatorToken) -> Result<u64> {
+    calculate_sell_proceeds_with_mode(supply, amount, curve.price_curve_type, &curve.curve_params())
+}
+
+/// Split a curve sell price into (protocol fee, subject fee, seller proceeds)
+fn split_sell_price(
+    global_state: &GlobalState,
+    sell_price: u64,
+    price_impact: u64,
+    trader_volume: u64,
+) -> Result<(u64, u64, u64)> {
+    let (protocol_fee, subject_fee) = global_state.key_trade_fees(sell_price, price_impact, trader_volume)?;
     
-    let mut total_price = 0u64;
+    let seller_proceeds = sell_price
+        .checked_sub(protocol_fee)
+        .ok_or(SolSocialError::MathOverflow)?
+        .checked_sub(subject_fee)
+        .ok_or(SolSocialError::MathOverflow)?;
     
-    for i in 0..amount {
-        let current_supply = supply
-            .checked_sub(i)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        // Bonding curve: price = (supply - 1)^2 / 16000
-        let price_per_key = if current_supply > 1 {
-            let supply_minus_one = current_supply
-                .checked_sub(1)
-                .ok_or(SolSocialError::MathOverflow)?;
-            
-            supply_minus_one
-                .checked_mul(supply_minus_one)
-                .ok_or(SolSocialError::MathOverflow)?
-                .checked_div(16000)
-                .ok_or(SolSocialError::MathOverflow)?
-        } else {
-            0
-        };
-        
-        total_price = total_price
-            .checked_add(price_per_key)
-            .ok_or(SolSocialError::MathOverflow)?;
+    Ok((protocol_fee, subject_fee, seller_proceeds))
+}
+
+fn check_holding_closable(global_state: &GlobalState, remaining: u64) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(remaining == 0, SolSocialError::KeyHoldingNotEmpty);
+    Ok(())
+}
+
+fn check_min_proceeds(seller_proceeds: u64, min_proceeds: u64) -> Result<()> {
+    require!(seller_proceeds >= min_proceeds, SolSocialError::SlippageToleranceExceeded);
+    Ok(())
+}
+
+/// The creator must keep at least one of their own keys so the curve never collapses
+/// under other holders; regular holders may sell everything.
+fn check_creator_floor(seller: Pubkey, subject: Pubkey, holding: u64, amount: u64) -> Result<()> {
+    if seller == subject {
+        let remaining = holding
+            .checked_sub(amount)
+            .ok_or(SolSocialError::InsufficientKeys)?;
+        require!(remaining >= CREATOR_KEY_FLOOR, SolSocialError::CreatorCannotSellAllShares);
     }
-    
-    Ok(total_price)
+    Ok(())
 }
 
 #[event]
@@ -226,6 +393,165 @@ pub struct KeysSold {
     pub timestamp: i64,
 }
 
-const PROTOCOL_FEE_PERCENT: u64 = 500; // 5%
-const SUBJECT_FEE_PERCENT: u64 = 500; // 5%
-```
\ No newline at end of file
+const CREATOR_KEY_FLOOR: u64 = 1;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bonding_curve::{calculate_buy_cost, BondingCurveParams};
+
+    fn default_curve() -> CreatorToken {
+        let mut curve = CreatorToken::default();
+        curve.ensure_initialized(Pubkey::new_unique(), 0, 255);
+        curve
+    }
+
+    /// 5% protocol and 5% subject fee
+    fn fees() -> GlobalState {
+        GlobalState { protocol_fee_bps: 500, creator_fee_bps: 500, ..Default::default() }
+    }
+
+    #[test]
+    fn test_min_proceeds_rejects_supply_drop_after_quote() {
+        let quoted = get_sell_price(1000, 5, &default_curve()).unwrap();
+        // Other holders sell first and pull supply down before execution
+        let executed = get_sell_price(900, 5, &default_curve()).unwrap();
+
+        assert!(executed < quoted);
+        assert!(check_min_proceeds(executed, quoted).is_err());
+    }
+
+    #[test]
+    fn test_creator_cannot_sell_last_key() {
+        let creator = Pubkey::new_unique();
+        assert!(check_creator_floor(creator, creator, 1, 1).is_err());
+        assert!(check_creator_floor(creator, creator, 5, 5).is_err());
+        assert!(check_creator_floor(creator, creator, 5, 4).is_ok());
+    }
+
+    #[test]
+    fn test_holder_can_sell_all_keys() {
+        let creator = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        assert!(check_creator_floor(holder, creator, 3, 3).is_ok());
+    }
+
+    #[test]
+    fn test_selling_to_zero_lets_holding_close() {
+        let creator = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        let holding = 3u64;
+
+        check_creator_floor(holder, creator, holding, 3).unwrap();
+        let remaining = holding - 3;
+
+        // close_key_holding succeeds, so `close = seller` returns the rent to the seller
+        assert!(check_holding_closable(&GlobalState::default(), remaining).is_ok());
+    }
+
+    #[test]
+    fn test_partial_sell_keeps_holding_open() {
+        let creator = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        let holding = 3u64;
+
+        check_creator_floor(holder, creator, holding, 1).unwrap();
+        let remaining = holding - 1;
+
+        assert_eq!(
+            check_holding_closable(&GlobalState::default(), remaining).unwrap_err(),
+            SolSocialError::KeyHoldingNotEmpty.into()
+        );
+        // A creator's floor key can never be sold, so their holding is never closable
+        assert!(check_creator_floor(creator, creator, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_close_holding_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        assert_eq!(
+            check_holding_closable(&global_state, 0).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+
+    #[test]
+    fn test_min_proceeds_allows_unchanged_quote() {
+        let quoted = get_sell_price(1000, 5, &default_curve()).unwrap();
+        assert!(check_min_proceeds(quoted, quoted).is_ok());
+    }
+
+    #[test]
+    fn test_round_trip_loses_only_fees() {
+        let params = BondingCurveParams::default();
+
+        // (supply, amount, curve price, 5% fee) worked out by hand from the default curve:
+        // price = 1_000 * amount + (to^3 - from^3) / 3_000_000
+        for &(supply, amount, price, fee) in &[
+            (0u64, 1u64, 1_000u64, 50u64),
+            (10, 5, 5_000, 250),
+            (10_000, 1_000, 1_110_333, 55_516),
+        ] {
+            let buy_price = calculate_buy_cost(supply, amount, &params).unwrap();
+            let sell_price = get_sell_price(supply + amount, amount, &default_curve()).unwrap();
+            assert_eq!(buy_price, price);
+            assert_eq!(sell_price, price);
+
+            let (protocol_fee, subject_fee, seller_proceeds) = split_sell_price(&fees(), sell_price, 0, 0).unwrap();
+            assert_eq!(protocol_fee, fee);
+            assert_eq!(subject_fee, fee);
+            assert_eq!(seller_proceeds, price - 2 * fee);
+
+            // buy_keys adds protocol and subject fees on top of the price at the same rates
+            let paid = buy_price + 2 * fee;
+            assert_eq!(paid - seller_proceeds, 4 * fee);
+        }
+    }
+
+    #[test]
+    fn test_reserve_balances_after_buying_five_selling_three() {
+        let params = BondingCurveParams::default();
+        let mut supply = 0u64;
+        let mut reserve = 0u64;
+
+        // buy_keys deposits only the curve price into the reserve; fees go elsewhere
+        for _ in 0..5 {
+            reserve += calculate_buy_cost(supply, 1, &params).unwrap();
+            supply += 1;
+        }
+
+        // sell_keys withdraws the full curve price; proceeds and fees all come out of it
+        for _ in 0..3 {
+            let sell_price = get_sell_price(supply, 1, &default_curve()).unwrap();
+            let (protocol_fee, subject_fee, seller_proceeds) = split_sell_price(&fees(), sell_price, 0, 0).unwrap();
+
+            reserve -= seller_proceeds + subject_fee + protocol_fee;
+            supply -= 1;
+        }
+
+        assert_eq!(supply, 2);
+        assert_eq!(reserve, calculate_buy_cost(0, 2, &params).unwrap());
+    }
+
+    #[test]
+    fn test_sell_split_follows_admin_fees() {
+        let authority = Pubkey::new_unique();
+        let mut global_state = GlobalState { authority, ..fees() };
+        assert_eq!(split_sell_price(&global_state, 100_000, 0, 0).unwrap(), (5_000, 5_000, 90_000));
+
+        global_state.update_fees(authority, 0, 200, 100).unwrap();
+        assert_eq!(split_sell_price(&global_state, 100_000, 0, 0).unwrap(), (1_000, 2_000, 97_000));
+    }
+
+    #[test]
+    fn test_high_volume_seller_pays_lower_protocol_fee() {
+        let (new_protocol_fee, new_subject_fee, new_proceeds) = split_sell_price(&fees(), 100_000, 0, 0).unwrap();
+        let (whale_protocol_fee, whale_subject_fee, whale_proceeds) =
+            split_sell_price(&fees(), 100_000, 0, 1_000_000_000_000).unwrap();
+
+        assert_eq!(new_protocol_fee, 5_000);
+        assert_eq!(whale_protocol_fee, 2_500);
+        assert_eq!(whale_subject_fee, new_subject_fee);
+        assert_eq!(whale_proceeds, new_proceeds + 2_500);
+    }
+}
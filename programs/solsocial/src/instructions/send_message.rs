@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
@@ -8,6 +7,13 @@ use crate::errors::*;
 #[derive(Accounts)]
 #[instruction(message_content: String)]
 pub struct SendMessage<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
     #[account(
         init,
         payer = sender,
@@ -15,19 +21,12 @@ pub struct SendMessage<'info> {
         seeds = [
             b"message",
             sender.key().as_ref(),
-            &Clock::get()?.unix_timestamp.to_le_bytes()
+            &sender_profile.message_nonce.to_le_bytes()
         ],
         bump
     )]
     pub message: Account<'info, Message>,
 
-    #[account(
-        mut,
-        seeds = [b"user_profile", sender.key().as_ref()],
-        bump = sender_profile.bump
-    )]
-    pub sender_profile: Account<'info, UserProfile>,
-
     #[account(
         mut,
         seeds = [b"user_profile", recipient.key().as_ref()],
@@ -51,17 +50,64 @@ pub struct SendMessage<'info> {
 
     #[account(
         mut,
-        constraint = platform_fee_account.mint == sender_profile.token_mint
+        constraint = platform_fee_account.mint == sender_profile.token_mint,
+        constraint = platform_fee_account.owner == global_state.treasury @ SolSocialError::InvalidTokenAccountOwner
     )]
     pub platform_fee_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"chat_room", sender.key().min(recipient.key()).as_ref(), sender.key().max(recipient.key()).as_ref()],
+        seeds = [
+            b"chat_room",
+            canonical_dm_pair(sender.key(), recipient.key()).0.as_ref(),
+            canonical_dm_pair(sender.key(), recipient.key()).1.as_ref()
+        ],
         bump = chat_room.bump
     )]
     pub chat_room: Account<'info, ChatRoom>,
 
+    #[account(
+        seeds = [b"chat_settings"],
+        bump = chat_settings.bump
+    )]
+    pub chat_settings: Account<'info, ChatSettings>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The sender's stake in the recipient; only holders move the recipient's price
+    #[account(
+        seeds = [b"token_holder", recipient.key().as_ref(), sender.key().as_ref()],
+        bump = sender_holding.bump
+    )]
+    pub sender_holding: Option<Account<'info, TokenHolder>>,
+
+    /// Sender's membership in the chat room; missing means the sender never joined
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), sender.key().as_ref()],
+        bump = sender_participant.bump
+    )]
+    pub sender_participant: Option<Account<'info, ChatParticipant>>,
+
+    /// CHECK: Only exists if the recipient blocked the sender; must be empty
+    #[account(
+        seeds = [b"block", recipient.key().as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub block_record: UncheckedAccount<'info>,
+
+    /// Keeps the daily streak going when supplied
+    #[account(
+        mut,
+        seeds = [b"user_stats", sender.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Option<Account<'info, UserStats>>,
+
     #[account(mut)]
     pub sender: Signer<'info>,
 
@@ -77,8 +123,35 @@ pub fn send_message(
     ctx: Context<SendMessage>,
     message_content: String,
     tip_amount: Option<u64>,
+    is_encrypted: bool,
 ) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
     let clock = Clock::get()?;
+
+    require!(ctx.accounts.block_record.data_is_empty(), SolSocialError::UserBlocked);
+
+    // Only members who joined (and paid the entry fee) may post
+    require_chat_member(
+        ctx.accounts.sender_participant.as_deref(),
+        ctx.accounts.chat_room.key(),
+        ctx.accounts.sender.key(),
+    )?;
+    require!(
+        !ctx.accounts.sender_participant.as_ref().is_some_and(|participant| participant.is_muted),
+        SolSocialError::MemberMuted
+    );
+
+    // Score the sender on today's reputation before moderated rooms check it
+    ctx.accounts.sender_profile.decay_reputation(clock.unix_timestamp)?;
+    if let Some(participant) = ctx.accounts.sender_participant.as_deref_mut() {
+        enforce_chat_moderation(
+            &ctx.accounts.chat_settings,
+            &ctx.accounts.chat_room,
+            participant,
+            ctx.accounts.sender_profile.reputation_score,
+            clock.unix_timestamp,
+        )?;
+    }
     
     // Validate message content
     require!(
@@ -91,15 +164,34 @@ pub fn send_message(
         SolSocialError::MessageTooLong
     );
 
-    // Calculate message cost based on sender's token price
-    let base_message_cost = 1000; // Base cost in lamports
-    let token_price_multiplier = ctx.accounts.sender_profile.token_price / 1_000_000; // Convert to SOL
-    let message_cost = base_message_cost + (token_price_multiplier * 100);
+    // Encrypted content is only useful if the recipient published a key to encrypt against
+    ctx.accounts.recipient_profile.require_can_receive(is_encrypted)?;
 
-    // Handle tip if provided
+    let message_cost = calculate_message_cost(ctx.accounts.sender_profile.token_price);
     if let Some(tip) = tip_amount {
         require!(tip > 0, SolSocialError::InvalidTipAmount);
-        
+    }
+
+    // The cost and any tip come out of the same account, so check them together
+    let total_charge = total_message_charge(message_cost, tip_amount.unwrap_or(0))?;
+    require!(
+        ctx.accounts.sender_token_account.amount >= total_charge,
+        SolSocialError::InsufficientTokenBalance
+    );
+
+    // Charge the message cost to the platform
+    let charge_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.sender_token_account.to_account_info(),
+            to: ctx.accounts.platform_fee_account.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    token::transfer(charge_ctx, message_cost)?;
+
+    // Handle tip if provided
+    if let Some(tip) = tip_amount {
         // Calculate platform fee (2% of tip)
         let platform_fee = tip * 2 / 100;
         let recipient_amount = tip - platform_fee;
@@ -127,13 +219,21 @@ pub fn send_message(
         token::transfer(transfer_fee_ctx, platform_fee)?;
 
         // Update recipient's earnings
-        ctx.accounts.recipient_profile.total_earnings = ctx.accounts.recipient_profile.total_earnings
+        ctx.accounts.recipient_profile.total_earned = ctx.accounts.recipient_profile.total_earned
             .checked_add(recipient_amount)
             .ok_or(SolSocialError::MathOverflow)?;
     }
 
+    // Each message takes the room's next sequence id, which read cursors refer to
+    let chat_room = &mut ctx.accounts.chat_room;
+    let message_id = chat_room.next_message_id()?;
+    chat_room.last_message_timestamp = clock.unix_timestamp;
+    chat_room.last_message_sender = ctx.accounts.sender.key();
+
     // Initialize message
     let message = &mut ctx.accounts.message;
+    message.chat_room = chat_room.key();
+    message.message_id = message_id;
     message.sender = ctx.accounts.sender.key();
     message.recipient = ctx.accounts.recipient.key();
     message.content = message_content;
@@ -141,22 +241,27 @@ pub fn send_message(
     message.tip_amount = tip_amount.unwrap_or(0);
     message.message_cost = message_cost;
     message.is_read = false;
+    message.is_encrypted = is_encrypted;
     message.bump = ctx.bumps.message;
 
-    // Update chat room
-    let chat_room = &mut ctx.accounts.chat_room;
-    chat_room.last_message_timestamp = clock.unix_timestamp;
-    chat_room.message_count = chat_room.message_count
-        .checked_add(1)
-        .ok_or(SolSocialError::MathOverflow)?;
-    chat_room.last_message_sender = ctx.accounts.sender.key();
+    if let Some(participant) = ctx.accounts.sender_participant.as_mut() {
+        participant.last_active = clock.unix_timestamp;
+        participant.message_count = participant.message_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+    }
 
     // Update sender profile stats
     let sender_profile = &mut ctx.accounts.sender_profile;
     sender_profile.messages_sent = sender_profile.messages_sent
         .checked_add(1)
         .ok_or(SolSocialError::MathOverflow)?;
-    sender_profile.last_activity = clock.unix_timestamp;
+    sender_profile.advance_message_nonce()?;
+    sender_profile.last_active = clock.unix_timestamp;
+
+    if let Some(user_stats) = ctx.accounts.user_stats.as_mut() {
+        user_stats.update_activity()?;
+    }
 
     // Update recipient profile stats
     let recipient_profile = &mut ctx.accounts.recipient_profile;
@@ -164,11 +269,15 @@ pub fn send_message(
         .checked_add(1)
         .ok_or(SolSocialError::MathOverflow)?;
 
-    // Increase token price based on interaction (Friend.tech style)
-    let price_increase = calculate_price_increase(sender_profile.holder_count, sender_profile.messages_sent);
-    sender_profile.token_price = sender_profile.token_price
-        .checked_add(price_increase)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // Holder messages nudge the recipient's price (Friend.tech style), within a daily cap
+    let price_increase = message_price_boost(
+        ctx.accounts.global_state.message_price_boost_enabled,
+        ctx.accounts.sender_holding.as_deref().map(|holding| holding.amount),
+        recipient_profile.holder_count,
+    );
+    if price_increase > 0 {
+        recipient_profile.apply_price_boost(price_increase, clock.unix_timestamp)?;
+    }
 
     // Emit event
     emit!(MessageSentEvent {
@@ -177,20 +286,42 @@ pub fn send_message(
         recipient: ctx.accounts.recipient.key(),
         content: message.content.clone(),
         tip_amount: tip_amount.unwrap_or(0),
+        is_encrypted,
         timestamp: clock.unix_timestamp,
-        new_sender_price: sender_profile.token_price,
+        new_recipient_price: recipient_profile.token_price,
     });
 
     Ok(())
 }
 
-fn calculate_price_increase(holder_count: u32, message_count: u64) -> u64 {
+fn calculate_message_cost(token_price: u64) -> u64 {
+    // Calculate message cost based on sender's token price
+    let base_message_cost = 1000; // Base cost in lamports
+    let token_price_multiplier = token_price / 1_000_000; // Convert to SOL
+    base_message_cost + (token_price_multiplier * 100)
+}
+
+fn total_message_charge(message_cost: u64, tip_amount: u64) -> Result<u64> {
+    message_cost
+        .checked_add(tip_amount)
+        .ok_or_else(|| error!(SolSocialError::MathOverflow))
+}
+
+/// Zero unless the platform enabled the boost and the sender actually holds
+/// the recipient's tokens.
+fn message_price_boost(enabled: bool, sender_holding: Option<u64>, holder_count: u32) -> u64 {
+    match sender_holding {
+        Some(amount) if enabled && amount > 0 => calculate_price_increase(holder_count),
+        _ => 0,
+    }
+}
+
+fn calculate_price_increase(holder_count: u32) -> u64 {
     // Friend.tech style bonding curve calculation
     let base_increase = 1000; // Base increase in lamports
     let holder_multiplier = (holder_count as u64) * 100;
-    let activity_multiplier = (message_count % 100) * 50;
-    
-    base_increase + holder_multiplier + activity_multiplier
+
+    base_increase + holder_multiplier
 }
 
 #[event]
@@ -200,7 +331,31 @@ pub struct MessageSentEvent {
     pub recipient: Pubkey,
     pub content: String,
     pub tip_amount: u64,
+    pub is_encrypted: bool,
     pub timestamp: i64,
-    pub new_sender_price: u64,
+    pub new_recipient_price: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_charged_message_cost_plus_tip() {
+        let message_cost = calculate_message_cost(5_000_000);
+        assert_eq!(message_cost, 1_500);
+
+        let balance: u64 = 10_000;
+        assert_eq!(balance - total_message_charge(message_cost, 0).unwrap(), 8_500);
+        assert_eq!(balance - total_message_charge(message_cost, 2_000).unwrap(), 6_500);
+        assert!(total_message_charge(u64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn test_price_unchanged_when_boost_disabled() {
+        assert_eq!(message_price_boost(false, Some(10), 5), 0);
+        assert_eq!(message_price_boost(true, None, 5), 0);
+        assert_eq!(message_price_boost(true, Some(0), 5), 0);
+        assert_eq!(message_price_boost(true, Some(10), 5), 1_500);
+    }
 }
-```
\ No newline at end of file
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::bonding_curve::validate_curve_params;
+
+#[derive(Accounts)]
+pub struct SetCurveParams<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// Seeded by the creator, so only they can reach their own curve
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorToken::LEN,
+        seeds = [b"creator_token", creator.key().as_ref()],
+        bump,
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_curve_params(
+    ctx: Context<SetCurveParams>,
+    base_price: u64,
+    price_multiplier: u64,
+) -> Result<()> {
+    let creator_token = &mut ctx.accounts.creator_token;
+    creator_token.ensure_initialized(
+        ctx.accounts.creator.key(),
+        Clock::get()?.unix_timestamp,
+        ctx.bumps.creator_token,
+    );
+    
+    // Re-pricing after anyone has bought would move value between holders and creator
+    require!(
+        creator_token.circulating_supply == 0,
+        SolSocialError::CurveParamsLocked
+    );
+    
+    let mut params = creator_token.curve_params();
+    params.base_price = base_price;
+    params.curve_factor = price_multiplier;
+    validate_curve_params(&params)?;
+    
+    creator_token.base_price = base_price;
+    creator_token.price_multiplier = price_multiplier;
+    
+    emit!(CurveParamsUpdated {
+        creator: ctx.accounts.creator.key(),
+        creator_token: creator_token.key(),
+        base_price,
+        price_multiplier,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CurveParamsUpdated {
+    pub creator: Pubkey,
+    pub creator_token: Pubkey,
+    pub base_price: u64,
+    pub price_multiplier: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetRevenueShare<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == creator.key() @ SolSocialError::UnauthorizedAccess,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Supplies the platform fee the new share is checked against
+    #[account(
+        seeds = [b"revenue_pool", creator.key().as_ref()],
+        bump = revenue_pool.bump,
+    )]
+    pub revenue_pool: Account<'info, RevenuePool>,
+}
+
+pub fn set_revenue_share(ctx: Context<SetRevenueShare>, percentage: u8) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+    
+    user_profile.set_revenue_share(percentage, ctx.accounts.revenue_pool.platform_fee_percentage)?;
+    user_profile.updated_at = clock.unix_timestamp;
+    
+    emit!(RevenueShareUpdated {
+        creator: ctx.accounts.creator.key(),
+        revenue_share_percentage: percentage,
+        platform_fee_percentage: ctx.accounts.revenue_pool.platform_fee_percentage,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct RevenueShareUpdated {
+    pub creator: Pubkey,
+    pub revenue_share_percentage: u8,
+    pub platform_fee_percentage: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SnapshotHolders<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_keys", creator.key().as_ref()],
+        bump = user_keys.bump,
+        constraint = user_keys.owner == creator.key() @ SolSocialError::UnauthorizedAccess,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    /// Creator's profile; its `token_supply` is the key supply buys and sells move
+    #[account(
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserProfile>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = HolderSnapshot::LEN,
+        seeds = [
+            b"holder_snapshot",
+            creator.key().as_ref(),
+            user_keys.snapshot_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn snapshot_holders(ctx: Context<SnapshotHolders>) -> Result<()> {
+    let user_keys = &mut ctx.accounts.user_keys;
+    let holder_snapshot = &mut ctx.accounts.holder_snapshot;
+    let now = Clock::get()?.unix_timestamp;
+    
+    let snapshot_id = next_snapshot(&ctx.accounts.global_state, user_keys)?;
+    holder_snapshot.capture(
+        user_keys,
+        ctx.accounts.user_account.token_supply,
+        snapshot_id,
+        now,
+        ctx.bumps.holder_snapshot,
+    );
+    
+    emit!(HoldersSnapshotted {
+        creator: ctx.accounts.creator.key(),
+        snapshot: holder_snapshot.key(),
+        snapshot_id,
+        total_supply: holder_snapshot.total_supply,
+        holders_count: holder_snapshot.holders_count,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+fn next_snapshot(global_state: &GlobalState, user_keys: &mut UserKeys) -> Result<u64> {
+    require_not_emergency_paused(global_state)?;
+    user_keys.next_snapshot_id()
+}
+
+#[event]
+pub struct HoldersSnapshotted {
+    pub creator: Pubkey,
+    pub snapshot: Pubkey,
+    pub snapshot_id: u64,
+    pub total_supply: u64,
+    pub holders_count: u32,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let mut user_keys = UserKeys::default();
+        assert_eq!(
+            next_snapshot(&global_state, &mut user_keys).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(user_keys.snapshot_count, 0);
+    }
+}
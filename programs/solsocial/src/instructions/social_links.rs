@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ManageSocialLinks<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn add_social_link(ctx: Context<ManageSocialLinks>, platform: String, url: String) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+
+    update_link(&ctx.accounts.global_state, user_profile, platform.clone(), Some(url.clone()))?;
+    user_profile.updated_at = clock.unix_timestamp;
+
+    emit!(SocialLinkAdded {
+        user: ctx.accounts.user.key(),
+        platform,
+        url,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn remove_social_link(ctx: Context<ManageSocialLinks>, platform: String) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let clock = Clock::get()?;
+
+    update_link(&ctx.accounts.global_state, user_profile, platform.clone(), None)?;
+    user_profile.updated_at = clock.unix_timestamp;
+
+    emit!(SocialLinkRemoved {
+        user: ctx.accounts.user.key(),
+        platform,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Sets the link for `platform`, or removes it when there is no URL.
+fn update_link(
+    global_state: &GlobalState,
+    user_profile: &mut UserProfile,
+    platform: String,
+    url: Option<String>,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    match url {
+        Some(url) => user_profile.add_social_link(platform, url),
+        None => user_profile.remove_social_link(&platform),
+    }
+}
+
+#[event]
+pub struct SocialLinkAdded {
+    pub user: Pubkey,
+    pub platform: String,
+    pub url: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SocialLinkRemoved {
+    pub user: Pubkey,
+    pub platform: String,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_social_links_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        let mut user_profile = UserProfile::default();
+        update_link(&global_state, &mut user_profile, "x".to_string(), Some("https://x.com/a".to_string())).unwrap();
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            update_link(&global_state, &mut user_profile, "x".to_string(), None).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(
+            update_link(&global_state, &mut user_profile, "gh".to_string(), Some("https://github.com/a".to_string()))
+                .unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(user_profile.social_links.len(), 1);
+    }
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(
+        init,
+        payer = staker,
+        space = Stake::LEN,
+        seeds = [b"stake", staker.key().as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, Stake>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", staker.key().as_ref()],
+        bump = user.bump,
+        constraint = user.authority == staker.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user: Account<'info, UserProfile>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = staker
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA that owns the stake vault for this mint
+    #[account(
+        seeds = [b"stake_vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = staker,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", staker.key().as_ref(), token_mint.key().as_ref()],
+        bump = stake.bump,
+        close = staker
+    )]
+    pub stake: Account<'info, Stake>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", staker.key().as_ref()],
+        bump = user.bump,
+        constraint = user.authority == staker.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user: Account<'info, UserProfile>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = staker
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA that owns the stake vault for this mint
+    #[account(
+        seeds = [b"stake_vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64, lock_period: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let balance = ctx.accounts.staker_token_account.amount;
+
+    let stake = &mut ctx.accounts.stake;
+    open_stake(&ctx.accounts.global_state, stake, balance, amount, lock_period, clock.unix_timestamp)?;
+    stake.staker = ctx.accounts.staker.key();
+    stake.token_mint = ctx.accounts.token_mint.key();
+    stake.bump = ctx.bumps.stake;
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.staker_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let reputation_boost = i64::try_from(stake.reputation_boost)
+        .map_err(|_| error!(SolSocialError::MathOverflow))?;
+    let user = &mut ctx.accounts.user;
+    user.decay_reputation(clock.unix_timestamp)?;
+    user.update_reputation(reputation_boost)?;
+    user.last_active = clock.unix_timestamp;
+
+    emit!(TokensStaked {
+        staker: ctx.accounts.staker.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        amount,
+        unlock_at: stake.unlock_at,
+        reputation_boost: stake.reputation_boost,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn unstake_tokens(ctx: Context<UnstakeTokens>) -> Result<()> {
+    let clock = Clock::get()?;
+    let stake = &mut ctx.accounts.stake;
+    let principal = release_stake(&ctx.accounts.global_state, stake, clock.unix_timestamp)?;
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[
+        b"stake_vault".as_ref(),
+        mint_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.staker_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, principal)?;
+
+    // Reputation may have dropped for other reasons since staking; never underflow
+    let user = &mut ctx.accounts.user;
+    user.decay_reputation(clock.unix_timestamp)?;
+    let removed = stake.reputation_boost.min(user.reputation_score);
+    let removed = i64::try_from(removed).map_err(|_| error!(SolSocialError::MathOverflow))?;
+    user.update_reputation(-removed)?;
+    user.last_active = clock.unix_timestamp;
+
+    emit!(TokensUnstaked {
+        staker: ctx.accounts.staker.key(),
+        token_mint: mint_key,
+        amount: principal,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn open_stake(
+    global_state: &GlobalState,
+    stake: &mut Stake,
+    balance: u64,
+    amount: u64,
+    lock_period: i64,
+    now: i64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    require!(balance >= amount, SolSocialError::InsufficientTokenBalance);
+    stake.open(amount, lock_period, now)
+}
+
+fn release_stake(global_state: &GlobalState, stake: &mut Stake, now: i64) -> Result<u64> {
+    require_not_emergency_paused(global_state)?;
+    stake.release(now)
+}
+
+#[event]
+pub struct TokensStaked {
+    pub staker: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub reputation_boost: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensUnstaked {
+    pub staker: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_and_unstake_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        let mut stake = Stake::default();
+        open_stake(&global_state, &mut stake, 1_000, 1_000, Stake::MIN_LOCK_PERIOD, 0).unwrap();
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            open_stake(&global_state, &mut Stake::default(), 1_000, 1_000, Stake::MIN_LOCK_PERIOD, 0)
+                .unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        // Even a matured stake stays locked until the pause lifts
+        assert_eq!(
+            release_stake(&global_state, &mut stake, stake.unlock_at).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert!(stake.is_active);
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferChatOwnership<'info> {
+    #[account(mut)]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), owner.key().as_ref()],
+        bump = owner_participant.bump
+    )]
+    pub owner_participant: Account<'info, ChatParticipant>,
+
+    /// Must already exist: ownership can only pass to a current member
+    #[account(
+        mut,
+        seeds = [b"chat_participant", chat_room.key().as_ref(), new_owner.as_ref()],
+        bump = new_owner_participant.bump
+    )]
+    pub new_owner_participant: Account<'info, ChatParticipant>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn transfer_chat_ownership(ctx: Context<TransferChatOwnership>, new_owner: Pubkey) -> Result<()> {
+    let room_key = ctx.accounts.chat_room.key();
+    let previous_owner = ctx.accounts.owner.key();
+
+    hand_over(
+        &ctx.accounts.global_state,
+        &mut ctx.accounts.chat_room,
+        room_key,
+        previous_owner,
+        &mut ctx.accounts.owner_participant,
+        &mut ctx.accounts.new_owner_participant,
+    )?;
+
+    emit!(ChatOwnershipTransferred {
+        chat_room: room_key,
+        previous_owner,
+        new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn hand_over(
+    global_state: &GlobalState,
+    chat_room: &mut ChatRoom,
+    room: Pubkey,
+    signer: Pubkey,
+    owner: &mut ChatParticipant,
+    successor: &mut ChatParticipant,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    chat_room.transfer_ownership(room, signer, owner, successor)
+}
+
+#[event]
+pub struct ChatOwnershipTransferred {
+    pub chat_room: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ownership_transfer_blocked_while_paused() {
+        let global_state = GlobalState { emergency_paused: true, ..Default::default() };
+        let (room, owner_key, successor_key) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut chat_room = ChatRoom { authority: owner_key, ..Default::default() };
+        let mut owner = ChatParticipant { user: owner_key, chat_room: room, ..Default::default() };
+        let mut successor = ChatParticipant { user: successor_key, chat_room: room, ..Default::default() };
+
+        assert_eq!(
+            hand_over(&global_state, &mut chat_room, room, owner_key, &mut owner, &mut successor).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(chat_room.authority, owner_key);
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Gifts keys to another wallet without going through the curve: no price, no fees,
+/// and supply stays the same.
+#[derive(Accounts)]
+#[instruction(to: Pubkey)]
+pub struct TransferKeys<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_keys", user_keys.owner.as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    /// Subject's profile; a frozen creator's keys cannot change hands
+    #[account(
+        seeds = [b"user_profile", user_keys.owner.as_ref()],
+        bump = subject_account.bump,
+    )]
+    pub subject_account: Account<'info, UserProfile>,
+    
+    /// Sender's holding, the same account `buy_keys` and `sell_keys` trade against
+    #[account(
+        mut,
+        seeds = [b"keys", user_keys.owner.as_ref(), sender.key().as_ref()],
+        bump = sender_holding.bump,
+        constraint = sender_holding.owner == sender.key() @ SolSocialError::UnauthorizedAccess,
+    )]
+    pub sender_holding: Account<'info, KeyHolding>,
+    
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = KeyHolding::LEN,
+        seeds = [b"keys", user_keys.owner.as_ref(), to.as_ref()],
+        bump
+    )]
+    pub recipient_holding: Account<'info, KeyHolding>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn transfer_keys(ctx: Context<TransferKeys>, to: Pubkey, amount: u64) -> Result<()> {
+    check_transfer(
+        &ctx.accounts.global_state,
+        &ctx.accounts.subject_account,
+        ctx.accounts.sender.key(),
+        to,
+    )?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    let user_keys = &mut ctx.accounts.user_keys;
+    let sender_holding = &mut ctx.accounts.sender_holding;
+    let recipient_holding = &mut ctx.accounts.recipient_holding;
+    
+    recipient_holding.ensure_initialized(to, user_keys.owner, ctx.bumps.recipient_holding);
+    sender_holding.send_to(recipient_holding, amount, now)?;
+    user_keys.record_transfer(amount, sender_holding.amount, recipient_holding.amount)?;
+    
+    emit!(KeysTransferred {
+        subject: user_keys.owner,
+        from: ctx.accounts.sender.key(),
+        to,
+        amount,
+        holders_count: user_keys.holders_count,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+fn check_transfer(
+    global_state: &GlobalState,
+    subject: &UserProfile,
+    sender: Pubkey,
+    to: Pubkey,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    subject.require_not_frozen()?;
+    // Both balances resolve to the same account on a self-transfer, so stop before either is written
+    require_keys_neq!(to, sender, SolSocialError::CannotTransferKeysToSelf);
+    Ok(())
+}
+
+#[event]
+pub struct KeysTransferred {
+    pub subject: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub holders_count: u32,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_blocked_while_paused() {
+        let (sender, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let subject = UserProfile::default();
+        let mut global_state = GlobalState::default();
+        assert!(check_transfer(&global_state, &subject, sender, to).is_ok());
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            check_transfer(&global_state, &subject, sender, to).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+    }
+
+    #[test]
+    fn test_frozen_creator_keys_cannot_be_transferred() {
+        let (sender, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut subject = UserProfile { token_frozen: true, ..Default::default() };
+        assert_eq!(
+            check_transfer(&GlobalState::default(), &subject, sender, to).unwrap_err(),
+            SolSocialError::TokenAccountFrozen.into()
+        );
+
+        subject.token_frozen = false;
+        assert!(check_transfer(&GlobalState::default(), &subject, sender, to).is_ok());
+    }
+}
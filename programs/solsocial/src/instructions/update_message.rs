@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DeleteMessage<'info> {
+    #[account(
+        mut,
+        constraint = !message.is_deleted @ SolSocialError::MessageNotFound
+    )]
+    pub message: Account<'info, Message>,
+
+    #[account(mut, address = message.chat_room)]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    /// Required when an admin or moderator deletes someone else's message
+    #[account(
+        constraint = participant.user == actor.key() @ SolSocialError::UnauthorizedAccess,
+        constraint = participant.chat_room == message.chat_room @ SolSocialError::UnauthorizedAccess
+    )]
+    pub participant: Option<Account<'info, ChatParticipant>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub actor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_content: String)]
+pub struct EditMessage<'info> {
+    #[account(mut)]
+    pub message: Account<'info, Message>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn delete_message(ctx: Context<DeleteMessage>) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let clock = Clock::get()?;
+    let is_moderator = ctx.accounts.participant
+        .as_ref()
+        .map(|participant| participant.can_moderate(&ctx.accounts.chat_room))
+        .unwrap_or(false);
+
+    let message = &mut ctx.accounts.message;
+    require!(
+        message.can_delete(ctx.accounts.actor.key(), is_moderator),
+        SolSocialError::UnauthorizedAccess
+    );
+
+    // Keep the account so ordering by message_id has no gaps
+    ctx.accounts.chat_room.unpin_message(message)?;
+    message.content = String::new();
+    message.reactions.clear();
+    message.is_deleted = true;
+
+    emit!(MessageDeleted {
+        message: message.key(),
+        chat_room: message.chat_room,
+        deleted_by: ctx.accounts.actor.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn edit_message(ctx: Context<EditMessage>, new_content: String) -> Result<()> {
+    require_not_emergency_paused(&ctx.accounts.global_state)?;
+    let clock = Clock::get()?;
+
+    require!(
+        !new_content.trim().is_empty(),
+        SolSocialError::EmptyMessage
+    );
+    require!(
+        new_content.len() <= Message::MAX_CONTENT_LENGTH,
+        SolSocialError::MessageTooLong
+    );
+
+    let message = &mut ctx.accounts.message;
+    require!(
+        message.can_edit(ctx.accounts.author.key(), clock.unix_timestamp),
+        SolSocialError::EditWindowExpired
+    );
+
+    message.content = new_content;
+    message.edited_at = Some(clock.unix_timestamp);
+
+    emit!(MessageEdited {
+        message: message.key(),
+        chat_room: message.chat_room,
+        author: ctx.accounts.author.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MessageDeleted {
+    pub message: Pubkey,
+    pub chat_room: Pubkey,
+    pub deleted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MessageEdited {
+    pub message: Pubkey,
+    pub chat_room: Pubkey,
+    pub author: Pubkey,
+    pub timestamp: i64,
+}
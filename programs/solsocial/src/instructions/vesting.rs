@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_token_vault", user.key().as_ref()],
+        bump
+    )]
+    pub user_token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = VestingSchedule::LEN,
+        seeds = [b"vesting", user.key().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user.key() @ SolSocialError::UnauthorizedAccess
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", user.key().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"user_token_vault", user.key().as_ref()],
+        bump
+    )]
+    pub user_token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination.owner == user.key() @ SolSocialError::InvalidTokenAccountOwner,
+        constraint = destination.mint == user_profile.token_mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Locks whatever the vault holds now; from then on it only leaves through `claim_vested`.
+pub fn create_vesting_schedule(ctx: Context<CreateVestingSchedule>, cliff: i64, duration: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+
+    open_schedule(
+        &ctx.accounts.global_state,
+        vesting_schedule,
+        ctx.accounts.user_token_vault.amount,
+        clock.unix_timestamp,
+        cliff,
+        duration,
+    )?;
+    vesting_schedule.creator = ctx.accounts.user.key();
+    vesting_schedule.bump = ctx.bumps.vesting_schedule;
+
+    emit!(VestingScheduleCreated {
+        creator: vesting_schedule.creator,
+        total_amount: vesting_schedule.total_amount,
+        cliff,
+        duration,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let clock = Clock::get()?;
+    let amount = release_vested(
+        &ctx.accounts.global_state,
+        &mut ctx.accounts.vesting_schedule,
+        clock.unix_timestamp,
+    )?;
+
+    let user_key = ctx.accounts.user.key();
+    let seeds = &[
+        b"user_profile",
+        user_key.as_ref(),
+        &[ctx.accounts.user_profile.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.user_profile.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(VestedTokensClaimed {
+        creator: user_key,
+        amount,
+        total_released: ctx.accounts.vesting_schedule.released,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn open_schedule(
+    global_state: &GlobalState,
+    vesting_schedule: &mut VestingSchedule,
+    total_amount: u64,
+    start: i64,
+    cliff: i64,
+    duration: i64,
+) -> Result<()> {
+    require_not_emergency_paused(global_state)?;
+    vesting_schedule.open(total_amount, start, cliff, duration)
+}
+
+fn release_vested(global_state: &GlobalState, vesting_schedule: &mut VestingSchedule, now: i64) -> Result<u64> {
+    require_not_emergency_paused(global_state)?;
+    vesting_schedule.release(now)
+}
+
+#[event]
+pub struct VestingScheduleCreated {
+    pub creator: Pubkey,
+    pub total_amount: u64,
+    pub cliff: i64,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_released: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vesting_blocked_while_paused() {
+        let mut global_state = GlobalState::default();
+        let mut schedule = VestingSchedule::default();
+        open_schedule(&global_state, &mut schedule, 1_000, 0, 0, 100).unwrap();
+
+        global_state.emergency_paused = true;
+        assert_eq!(
+            open_schedule(&global_state, &mut VestingSchedule::default(), 1_000, 0, 0, 100).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(
+            release_vested(&global_state, &mut schedule, 100).unwrap_err(),
+            SolSocialError::EmergencyPauseActivated.into()
+        );
+        assert_eq!(schedule.released, 0);
+    }
+}
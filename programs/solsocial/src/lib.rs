@@ -1,9 +1,17 @@
-```rust
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use crate::state::require_not_emergency_paused;
+use crate::utils::bonding_curve::{
+    calculate_price_impact_with_mode, calculate_price_with_mode, calculate_reserve_buy_cost_with_mode,
+    calculate_reserve_sell_proceeds_with_mode, PRICE_PRECISION,
+};
 
 declare_id!("SoLSociaL1111111111111111111111111111111111");
 
+const FOLLOW_REQUEST_TTL: i64 = 7 * 24 * 60 * 60; // 7 days
+const LEGACY_POST_CONTENT_LENGTH: usize = 500; // content budgeted by PostAccount::LEN
+
 #[program]
 pub mod solsocial {
     use super::*;
@@ -13,6 +21,7 @@ pub mod solsocial {
         username: String,
         bio: String,
     ) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
         require!(username.len() <= 32, SolSocialError::UsernameTooLong);
         require!(bio.len() <= 280, SolSocialError::BioTooLong);
 
@@ -24,10 +33,19 @@ pub mod solsocial {
         user_account.following_count = 0;
         user_account.posts_count = 0;
         user_account.token_price = 1_000_000; // 0.001 SOL in lamports
-        user_account.total_supply = 1_000_000;
-        user_account.circulating_supply = 0;
+        user_account.max_supply = 1_000_000;
+        user_account.token_supply = 0;
+        user_account.is_private = false;
         user_account.created_at = Clock::get()?.unix_timestamp;
         user_account.bump = ctx.bumps.user_account;
+        ctx.accounts.global_state.record_user()?;
+
+        emit!(UserInitialized {
+            user: user_account.key(),
+            authority: user_account.authority,
+            username: user_account.username.clone(),
+            timestamp: user_account.created_at,
+        });
 
         Ok(())
     }
@@ -37,7 +55,8 @@ pub mod solsocial {
         content: String,
         media_url: Option<String>,
     ) -> Result<()> {
-        require!(content.len() <= 500, SolSocialError::ContentTooLong);
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        ctx.accounts.global_state.check_post_length(&content)?;
         
         if let Some(ref url) = media_url {
             require!(url.len() <= 200, SolSocialError::MediaUrlTooLong);
@@ -56,33 +75,110 @@ pub mod solsocial {
         post_account.created_at = Clock::get()?.unix_timestamp;
         post_account.bump = ctx.bumps.post_account;
 
-        user_account.posts_count = user_account.posts_count.checked_add(1).unwrap();
+        user_account.posts_count = user_account.posts_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        ctx.accounts.global_state.record_post()?;
+
+        emit!(PostCreated {
+            post: post_account.key(),
+            author: post_account.authority,
+            user: user_account.key(),
+            content_preview: post_account.content.chars().take(100).collect(),
+            posts_count: user_account.posts_count,
+            timestamp: post_account.created_at,
+        });
+
+        Ok(())
+    }
 
+    /// Returns `posts_count` so clients know how far to page with `derive_post_pda`.
+    pub fn get_post_count(ctx: Context<GetPostCount>) -> Result<()> {
+        // Clients read this via simulateTransaction
+        set_return_data(&ctx.accounts.user_account.posts_count.to_le_bytes());
         Ok(())
     }
 
     pub fn like_post(ctx: Context<LikePost>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
         let like_account = &mut ctx.accounts.like_account;
         let post_account = &mut ctx.accounts.post_account;
 
+        // The PDA is init_if_needed so a repeat like reaches this check instead of failing on init
+        require!(
+            like_account.user == Pubkey::default(),
+            SolSocialError::AlreadyLiked
+        );
+
         like_account.user = ctx.accounts.authority.key();
         like_account.post = post_account.key();
         like_account.created_at = Clock::get()?.unix_timestamp;
         like_account.bump = ctx.bumps.like_account;
 
-        post_account.likes_count = post_account.likes_count.checked_add(1).unwrap();
+        post_account.likes_count = post_account.likes_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        emit!(PostLiked {
+            post: post_account.key(),
+            user: ctx.accounts.authority.key(),
+            like_count: post_account.likes_count,
+            timestamp: like_account.created_at,
+        });
 
         Ok(())
     }
 
     pub fn unlike_post(ctx: Context<UnlikePost>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
         let post_account = &mut ctx.accounts.post_account;
-        post_account.likes_count = post_account.likes_count.checked_sub(1).unwrap();
+        post_account.likes_count = post_account.likes_count
+            .checked_sub(1)
+            .ok_or(SolSocialError::MathUnderflow)?;
+
+        emit!(PostUnliked {
+            post: post_account.key(),
+            user: ctx.accounts.authority.key(),
+            like_count: post_account.likes_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_profile_privacy(ctx: Context<SetProfilePrivacy>, is_private: bool) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        ctx.accounts.user_account.is_private = is_private;
+
+        emit!(ProfilePrivacySet {
+            user: ctx.accounts.user_account.key(),
+            is_private,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
     pub fn follow_user(ctx: Context<FollowUser>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        require!(
+            ctx.accounts.block_record.data_is_empty(),
+            SolSocialError::UserBlocked
+        );
+        require!(
+            ctx.accounts.follower_account.key() != ctx.accounts.following_account.key(),
+            SolSocialError::CannotFollowSelf
+        );
+        // init_if_needed lets a repeat follow reach this check instead of failing on init
+        require!(
+            ctx.accounts.follow_account.follower == Pubkey::default(),
+            SolSocialError::AlreadyFollowing
+        );
+        require!(
+            !ctx.accounts.following_account.is_private,
+            SolSocialError::FollowRequestRequired
+        );
+
         let follow_account = &mut ctx.accounts.follow_account;
         let follower_account = &mut ctx.accounts.follower_account;
         let following_account = &mut ctx.accounts.following_account;
@@ -92,18 +188,185 @@ pub mod solsocial {
         follow_account.created_at = Clock::get()?.unix_timestamp;
         follow_account.bump = ctx.bumps.follow_account;
 
-        follower_account.following_count = follower_account.following_count.checked_add(1).unwrap();
-        following_account.followers_count = following_account.followers_count.checked_add(1).unwrap();
+        UserProfile::record_follow(follower_account, following_account, true)?;
+
+        emit!(UserFollowed {
+            follower: follower_account.key(),
+            following: following_account.key(),
+            following_count: follower_account.following_count,
+            followers_count: following_account.followers_count,
+            timestamp: follow_account.created_at,
+        });
 
         Ok(())
     }
 
     pub fn unfollow_user(ctx: Context<UnfollowUser>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        require!(
+            !ctx.accounts.follow_account.data_is_empty(),
+            SolSocialError::NotFollowing
+        );
+        let follow_info = ctx.accounts.follow_account.to_account_info();
+        let follow_account = Account::<FollowAccount>::try_from(&follow_info)?;
+        follow_account.close(ctx.accounts.follower.to_account_info())?;
+
         let follower_account = &mut ctx.accounts.follower_account;
         let following_account = &mut ctx.accounts.following_account;
 
-        follower_account.following_count = follower_account.following_count.checked_sub(1).unwrap();
-        following_account.followers_count = following_account.followers_count.checked_sub(1).unwrap();
+        UserProfile::record_follow(follower_account, following_account, false)?;
+
+        emit!(UserUnfollowed {
+            follower: follower_account.key(),
+            following: following_account.key(),
+            following_count: follower_account.following_count,
+            followers_count: following_account.followers_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn block_user(ctx: Context<BlockUser>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        let blocker = ctx.accounts.blocker.key();
+        let blocked = ctx.accounts.blocked.key();
+        require_keys_neq!(blocker, blocked, SolSocialError::CannotBlockSelf);
+
+        let block_record = &mut ctx.accounts.block_record;
+        block_record.blocker = blocker;
+        block_record.blocked = blocked;
+        block_record.created_at = Clock::get()?.unix_timestamp;
+        block_record.bump = ctx.bumps.block_record;
+
+        // The blocked user's follow is closed by the account constraint; settle the counts here
+        let removed_follow = ctx.accounts.existing_follow.is_some();
+        if removed_follow {
+            let blocker_account = &mut ctx.accounts.blocker_account;
+            let blocked_account = &mut ctx.accounts.blocked_account;
+            UserProfile::record_follow(blocked_account, blocker_account, false)?;
+        }
+
+        emit!(UserBlocked {
+            blocker,
+            blocked,
+            removed_follow,
+            timestamp: block_record.created_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn unblock_user(ctx: Context<UnblockUser>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        emit!(UserUnblocked {
+            blocker: ctx.accounts.block_record.blocker,
+            blocked: ctx.accounts.block_record.blocked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Records that `muter` wants `muted`'s posts hidden. Purely a client hint:
+    /// follows and DMs are untouched. Muting twice is a no-op.
+    pub fn mute_feed(ctx: Context<MuteFeed>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        let muter = ctx.accounts.muter.key();
+        let muted = ctx.accounts.muted.key();
+        require_keys_neq!(muter, muted, SolSocialError::CannotMuteSelf);
+
+        let feed_mute = &mut ctx.accounts.feed_mute;
+        feed_mute.muter = muter;
+        feed_mute.muted = muted;
+        feed_mute.bump = ctx.bumps.feed_mute;
+
+        Ok(())
+    }
+
+    pub fn unmute_feed(ctx: Context<UnmuteFeed>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)
+    }
+
+    pub fn request_follow(ctx: Context<RequestFollow>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        let follow_request = &mut ctx.accounts.follow_request;
+        let following_account = &ctx.accounts.following_account;
+
+        require!(
+            ctx.accounts.follower_account.key() != following_account.key(),
+            SolSocialError::CannotFollowSelf
+        );
+        require!(following_account.is_private, SolSocialError::ProfileNotPrivate);
+        require!(
+            ctx.accounts.block_record.data_is_empty(),
+            SolSocialError::UserBlocked
+        );
+
+        follow_request.requester = ctx.accounts.follower.key();
+        follow_request.target = following_account.key();
+        follow_request.created_at = Clock::get()?.unix_timestamp;
+        follow_request.bump = ctx.bumps.follow_request;
+
+        emit!(FollowRequested {
+            requester: follow_request.requester,
+            target: follow_request.target,
+            timestamp: follow_request.created_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn approve_follow(ctx: Context<ApproveFollow>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now - ctx.accounts.follow_request.created_at <= FOLLOW_REQUEST_TTL,
+            SolSocialError::FollowRequestExpired
+        );
+
+        let follow_account = &mut ctx.accounts.follow_account;
+        let follower_account = &mut ctx.accounts.follower_account;
+        let following_account = &mut ctx.accounts.following_account;
+
+        follow_account.follower = follower_account.key();
+        follow_account.following = following_account.key();
+        follow_account.created_at = now;
+        follow_account.bump = ctx.bumps.follow_account;
+
+        UserProfile::record_follow(follower_account, following_account, true)?;
+
+        // An approved request is indexed as an ordinary follow
+        emit!(UserFollowed {
+            follower: follower_account.key(),
+            following: following_account.key(),
+            following_count: follower_account.following_count,
+            followers_count: following_account.followers_count,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn reject_follow(ctx: Context<RejectFollow>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        // Closing the request refunds rent to the requester; a later approve finds no account
+        emit!(FollowRequestRejected {
+            requester: ctx.accounts.follow_request.requester,
+            target: ctx.accounts.follow_request.target,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_follow_request(ctx: Context<CancelFollowRequest>) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        emit!(FollowRequestCancelled {
+            requester: ctx.accounts.follow_request.requester,
+            target: ctx.accounts.follow_request.target,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
@@ -111,17 +374,45 @@ pub mod solsocial {
     pub fn buy_user_tokens(
         ctx: Context<BuyUserTokens>,
         amount: u64,
+        max_cost: u64,
+        deadline: i64,
+        max_price_impact_bps: Option<u64>,
     ) -> Result<()> {
+        check_deadline(Clock::get()?.unix_timestamp, deadline)?;
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        require!(!ctx.accounts.global_state.is_paused, SolSocialError::TradingPaused);
+
         let user_account = &mut ctx.accounts.user_account;
+        user_account.require_not_frozen()?;
         let buyer_token_account = &mut ctx.accounts.buyer_token_account;
+        let creator_token = &mut ctx.accounts.creator_token;
+        creator_token.ensure_initialized(
+            user_account.authority,
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.creator_token,
+        );
+        check_price_impact(user_account.token_supply, amount, true, max_price_impact_bps, creator_token)?;
         
-        // Calculate price based on bonding curve
-        let price = calculate_buy_price(user_account.circulating_supply, amount);
+        // Calculate price along the creator's bonding curve
+        let price = calculate_buy_price(user_account.token_supply, amount, creator_token)?;
+        let fee = token_trade_fee(
+            &ctx.accounts.global_state,
+            ctx.accounts.trader_profile.as_deref(),
+            user_account.token_supply,
+            amount,
+            true,
+            price,
+            creator_token,
+        )?;
+        let total_cost = price
+            .checked_add(fee)
+            .ok_or(SolSocialError::MathOverflow)?;
+        require!(total_cost <= max_cost, SolSocialError::SlippageToleranceExceeded);
         
-        // Transfer SOL from buyer to user
+        // Lock the SOL in the curve escrow so sellers can always be paid out
         let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
             &ctx.accounts.buyer.key(),
-            &ctx.accounts.user_authority.key(),
+            &ctx.accounts.token_escrow.key(),
             price,
         );
         
@@ -129,19 +420,76 @@ pub mod solsocial {
             &transfer_instruction,
             &[
                 ctx.accounts.buyer.to_account_info(),
-                ctx.accounts.user_authority.to_account_info(),
+                ctx.accounts.token_escrow.to_account_info(),
             ],
         )?;
 
+        let token_escrow = &mut ctx.accounts.token_escrow;
+        token_escrow.user_account = user_account.key();
+        token_escrow.bump = ctx.bumps.token_escrow;
+        token_escrow.deposit(price)?;
+
+        // The trading fee goes to the treasury, outside the escrow
+        if fee > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &ctx.accounts.buyer.key(),
+                    &ctx.accounts.treasury.key(),
+                    fee,
+                ),
+                &[
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.treasury.to_account_info(),
+                ],
+            )?;
+        }
+        if let Some(trader_profile) = ctx.accounts.trader_profile.as_mut() {
+            trader_profile.total_volume = trader_profile.total_volume
+                .checked_add(price)
+                .ok_or(SolSocialError::MathOverflow)?;
+        }
+
         // Update token account
         buyer_token_account.user = user_account.key();
         buyer_token_account.owner = ctx.accounts.buyer.key();
-        buyer_token_account.amount = buyer_token_account.amount.checked_add(amount).unwrap();
+        buyer_token_account.amount = buyer_token_account.amount
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
         buyer_token_account.bump = ctx.bumps.buyer_token_account;
 
+        // Settle holder rewards at the pre-trade supply before the balance grows
+        let now = Clock::get()?.unix_timestamp;
+        let reward_cursor = &mut ctx.accounts.reward_cursor;
+        reward_cursor.profile_owner = user_account.authority;
+        reward_cursor.bump = ctx.bumps.reward_cursor;
+        let token_holder = &mut ctx.accounts.token_holder;
+        token_holder.ensure_initialized(
+            ctx.accounts.buyer.key(),
+            user_account.authority,
+            user_account.token_mint,
+            reward_cursor.rewards_per_token,
+            now,
+            ctx.bumps.token_holder,
+        );
+        token_holder.record_buy(amount, price, reward_cursor.rewards_per_token, user_account.token_supply, now)?;
+
         // Update user account
-        user_account.circulating_supply = user_account.circulating_supply.checked_add(amount).unwrap();
-        user_account.token_price = calculate_current_price(user_account.circulating_supply);
+        user_account.token_supply = user_account.token_supply
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        creator_token.record_trade(amount, true)?;
+        user_account.token_price = calculate_current_price(user_account.token_supply, creator_token)?;
+
+        emit!(UserTokensBought {
+            buyer: ctx.accounts.buyer.key(),
+            user: user_account.key(),
+            amount,
+            price,
+            fee,
+            token_supply: user_account.token_supply,
+            token_price: user_account.token_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
@@ -149,28 +497,81 @@ pub mod solsocial {
     pub fn sell_user_tokens(
         ctx: Context<SellUserTokens>,
         amount: u64,
+        min_proceeds: u64,
+        deadline: i64,
+        max_price_impact_bps: Option<u64>,
     ) -> Result<()> {
+        check_deadline(Clock::get()?.unix_timestamp, deadline)?;
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
+        require!(!ctx.accounts.global_state.is_paused, SolSocialError::TradingPaused);
+
         let user_account = &mut ctx.accounts.user_account;
+        user_account.require_not_frozen()?;
         let seller_token_account = &mut ctx.accounts.seller_token_account;
         
         require!(
             seller_token_account.amount >= amount,
             SolSocialError::InsufficientTokens
         );
+        let creator_token = &mut ctx.accounts.creator_token;
+        check_price_impact(user_account.token_supply, amount, false, max_price_impact_bps, creator_token)?;
 
-        // Calculate sell price based on bonding curve
-        let price = calculate_sell_price(user_account.circulating_supply, amount);
+        // Calculate sell price along the creator's bonding curve
+        let price = calculate_sell_price(user_account.token_supply, amount, creator_token)?;
+        let fee = token_trade_fee(
+            &ctx.accounts.global_state,
+            ctx.accounts.trader_profile.as_deref(),
+            user_account.token_supply,
+            amount,
+            false,
+            price,
+            creator_token,
+        )?;
+        let proceeds = price
+            .checked_sub(fee)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        require!(proceeds >= min_proceeds, SolSocialError::SlippageToleranceExceeded);
         
-        // Transfer SOL from user to seller
-        **ctx.accounts.user_authority.to_account_info().try_borrow_mut_lamports()? -= price;
-        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += price;
+        // Pay the seller out of the curve escrow; the trading fee goes to the treasury
+        ctx.accounts.token_escrow.withdraw(price)?;
+        **ctx.accounts.token_escrow.to_account_info().try_borrow_mut_lamports()? -= price;
+        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += proceeds;
+        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
+        if let Some(trader_profile) = ctx.accounts.trader_profile.as_mut() {
+            trader_profile.total_volume = trader_profile.total_volume
+                .checked_add(price)
+                .ok_or(SolSocialError::MathOverflow)?;
+        }
 
         // Update token account
-        seller_token_account.amount = seller_token_account.amount.checked_sub(amount).unwrap();
+        seller_token_account.amount = seller_token_account.amount
+            .checked_sub(amount)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        ctx.accounts.token_holder.record_sell(
+            amount,
+            price,
+            ctx.accounts.reward_cursor.rewards_per_token,
+            user_account.token_supply,
+            Clock::get()?.unix_timestamp,
+        )?;
 
         // Update user account
-        user_account.circulating_supply = user_account.circulating_supply.checked_sub(amount).unwrap();
-        user_account.token_price = calculate_current_price(user_account.circulating_supply);
+        user_account.token_supply = user_account.token_supply
+            .checked_sub(amount)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        creator_token.record_trade(amount, false)?;
+        user_account.token_price = calculate_current_price(user_account.token_supply, creator_token)?;
+
+        emit!(UserTokensSold {
+            seller: ctx.accounts.seller.key(),
+            user: user_account.key(),
+            amount,
+            price,
+            fee,
+            token_supply: user_account.token_supply,
+            token_price: user_account.token_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
@@ -179,6 +580,7 @@ pub mod solsocial {
         ctx: Context<TipPost>,
         amount: u64,
     ) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
         let post_account = &mut ctx.accounts.post_account;
         
         // Transfer SOL from tipper to post author
@@ -196,7 +598,19 @@ pub mod solsocial {
             ],
         )?;
 
-        post_account.tips_amount = post_account.tips_amount.checked_add(amount).unwrap();
+        post_account.tips_amount = post_account.tips_amount
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        ctx.accounts.global_state.record_tips(1, amount)?;
+
+        emit!(PostTipped {
+            post: post_account.key(),
+            tipper: ctx.accounts.tipper.key(),
+            recipient: ctx.accounts.post_author.key(),
+            amount,
+            total_tips: post_account.tips_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
@@ -205,6 +619,7 @@ pub mod solsocial {
         ctx: Context<CreateComment>,
         content: String,
     ) -> Result<()> {
+        require_not_emergency_paused(&ctx.accounts.global_state)?;
         require!(content.len() <= 280, SolSocialError::ContentTooLong);
 
         let comment_account = &mut ctx.accounts.comment_account;
@@ -217,44 +632,294 @@ pub mod solsocial {
         comment_account.created_at = Clock::get()?.unix_timestamp;
         comment_account.bump = ctx.bumps.comment_account;
 
-        post_account.comments_count = post_account.comments_count.checked_add(1).unwrap();
+        post_account.comments_count = post_account.comments_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        emit!(PostCommented {
+            post: post_account.key(),
+            comment: comment_account.key(),
+            author: comment_account.authority,
+            content: comment_account.content.clone(),
+            comment_count: post_account.comments_count,
+            timestamp: comment_account.created_at,
+        });
 
         Ok(())
     }
 }
 
+/// Address of an author's post at `index`. Indices are assigned in order from
+/// `posts_count` at creation, so an author's posts live at 0..posts_count.
+pub fn derive_post_pda(author: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"post", author.as_ref(), &index.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+// Priced off the cumulative reserve so the escrow always covers every outstanding token
+fn calculate_buy_price(supply: u64, amount: u64, curve: &CreatorToken) -> Result<u64> {
+    calculate_reserve_buy_cost_with_mode(supply, amount, curve.price_curve_type, &curve.curve_params())
+}
+
+fn calculate_sell_price(supply: u64, amount: u64, curve: &CreatorToken) -> Result<u64> {
+    calculate_reserve_sell_proceeds_with_mode(supply, amount, curve.price_curve_type, &curve.curve_params())
+}
+
+fn calculate_current_price(supply: u64, curve: &CreatorToken) -> Result<u64> {
+    calculate_price_with_mode(supply, curve.price_curve_type, &curve.curve_params())
+}
+
+/// Rejects a trade that would move the spot price by more than the trader's cap.
+/// No cap means any impact is accepted.
+fn check_price_impact(
+    supply: u64,
+    amount: u64,
+    is_buy: bool,
+    max_price_impact_bps: Option<u64>,
+    curve: &CreatorToken,
+) -> Result<()> {
+    let Some(max_bps) = max_price_impact_bps else {
+        return Ok(());
+    };
+    let impact = calculate_price_impact_with_mode(
+        supply,
+        amount,
+        is_buy,
+        curve.price_curve_type,
+        &curve.curve_params(),
+    )?;
+    // impact is scaled so PRICE_PRECISION is 100%; compare without truncating to bps
+    require!(
+        (impact as u128) * 10_000 <= (max_bps as u128) * (PRICE_PRECISION as u128),
+        SolSocialError::PriceDeviationTooHigh
+    );
+    Ok(())
+}
+
+/// Platform fee on a creator token trade; traders without a profile pay the first tier.
+fn token_trade_fee(
+    global_state: &GlobalState,
+    trader_profile: Option<&UserProfile>,
+    supply: u64,
+    amount: u64,
+    is_buy: bool,
+    price: u64,
+    curve: &CreatorToken,
+) -> Result<u64> {
+    let impact = calculate_price_impact_with_mode(
+        supply,
+        amount,
+        is_buy,
+        curve.price_curve_type,
+        &curve.curve_params(),
+    )?;
+    let trader_volume = trader_profile.map_or(0, |profile| profile.total_volume);
+    global_state.token_trade_fee(price, impact, trader_volume)
+}
+
+/// Trades signed against a quote are only valid until the deadline the client chose.
+fn check_deadline(now: i64, deadline: i64) -> Result<()> {
+    require!(now <= deadline, SolSocialError::TransactionExpired);
+    Ok(())
+}
+
+#[event]
+pub struct UserInitialized {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub username: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostCreated {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub user: Pubkey,
+    pub content_preview: String,
+    pub posts_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostLiked {
+    pub post: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostUnliked {
+    pub post: Pubkey,
+    pub user: Pubkey,
+    pub like_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProfilePrivacySet {
+    pub user: Pubkey,
+    pub is_private: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserFollowed {
+    pub follower: Pubkey,
+    pub following: Pubkey,
+    pub following_count: u64,
+    pub followers_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserUnfollowed {
+    pub follower: Pubkey,
+    pub following: Pubkey,
+    pub following_count: u64,
+    pub followers_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserBlocked {
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub removed_follow: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserUnblocked {
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FollowRequested {
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FollowRequestRejected {
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FollowRequestCancelled {
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserTokensBought {
+    pub buyer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub fee: u64,
+    pub token_supply: u64,
+    pub token_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserTokensSold {
+    pub seller: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub fee: u64,
+    pub token_supply: u64,
+    pub token_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostTipped {
+    pub post: Pubkey,
+    pub tipper: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_tips: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PostCommented {
+    pub post: Pubkey,
+    pub comment: Pubkey,
+    pub author: Pubkey,
+    pub content: String,
+    pub comment_count: u64,
+    pub timestamp: i64,
+}
+
 #[derive(Accounts)]
 #[instruction(username: String)]
 pub struct InitializeUser<'info> {
     #[account(
         init,
         payer = authority,
-        space = UserAccount::LEN,
-        seeds = [b"user", authority.key().as_ref()],
+        space = UserProfile::LEN,
+        seeds = [b"user_profile", authority.key().as_ref()],
         bump
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+pub struct GetPostCount<'info> {
+    #[account(
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserProfile>,
+    /// CHECK: Only used to derive the author's profile
+    pub author: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(content: String)]
 pub struct CreatePost<'info> {
     #[account(
         init,
         payer = authority,
-        space = PostAccount::LEN,
+        space = PostAccount::LEN + content.len().saturating_sub(LEGACY_POST_CONTENT_LENGTH),
         seeds = [b"post", authority.key().as_ref(), &user_account.posts_count.to_le_bytes()],
         bump
     )]
     pub post_account: Account<'info, PostAccount>,
     #[account(
         mut,
-        seeds = [b"user", authority.key().as_ref()],
+        seeds = [b"user_profile", authority.key().as_ref()],
         bump = user_account.bump
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -263,7 +928,7 @@ pub struct CreatePost<'info> {
 #[derive(Accounts)]
 pub struct LikePost<'info> {
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = LikeAccount::LEN,
         seeds = [b"like", authority.key().as_ref(), post_account.key().as_ref()],
@@ -272,6 +937,11 @@ pub struct LikePost<'info> {
     pub like_account: Account<'info, LikeAccount>,
     #[account(mut)]
     pub post_account: Account<'info, PostAccount>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -288,6 +958,11 @@ pub struct UnlikePost<'info> {
     pub like_account: Account<'info, LikeAccount>,
     #[account(mut)]
     pub post_account: Account<'info, PostAccount>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub authority: Signer<'info>,
 }
@@ -295,7 +970,7 @@ pub struct UnlikePost<'info> {
 #[derive(Accounts)]
 pub struct FollowUser<'info> {
     #[account(
-        init,
+        init_if_needed,
         payer = follower,
         space = FollowAccount::LEN,
         seeds = [b"follow", follower.key().as_ref(), following_account.key().as_ref()],
@@ -304,12 +979,23 @@ pub struct FollowUser<'info> {
     pub follow_account: Account<'info, FollowAccount>,
     #[account(
         mut,
-        seeds = [b"user", follower.key().as_ref()],
+        seeds = [b"user_profile", follower.key().as_ref()],
         bump = follower_account.bump
     )]
-    pub follower_account: Account<'info, UserAccount>,
+    pub follower_account: Account<'info, UserProfile>,
     #[account(mut)]
-    pub following_account: Account<'info, UserAccount>,
+    pub following_account: Account<'info, UserProfile>,
+    /// CHECK: Only exists if the followed user blocked the follower; must be empty
+    #[account(
+        seeds = [b"block", following_account.authority.as_ref(), follower.key().as_ref()],
+        bump
+    )]
+    pub block_record: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub follower: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -317,29 +1003,320 @@ pub struct FollowUser<'info> {
 
 #[derive(Accounts)]
 pub struct UnfollowUser<'info> {
+    /// CHECK: Empty when not following; otherwise deserialized and closed in the handler
     #[account(
         mut,
-        close = follower,
         seeds = [b"follow", follower.key().as_ref(), following_account.key().as_ref()],
-        bump = follow_account.bump
+        bump
+    )]
+    pub follow_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user_profile", follower.key().as_ref()],
+        bump = follower_account.bump
+    )]
+    pub follower_account: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub following_account: Account<'info, UserProfile>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProfilePrivacy<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserProfile>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestFollow<'info> {
+    #[account(
+        init,
+        payer = follower,
+        space = FollowRequest::LEN,
+        seeds = [b"follow_request", follower.key().as_ref(), following_account.key().as_ref()],
+        bump
+    )]
+    pub follow_request: Account<'info, FollowRequest>,
+    #[account(
+        seeds = [b"user_profile", follower.key().as_ref()],
+        bump = follower_account.bump
+    )]
+    pub follower_account: Account<'info, UserProfile>,
+    pub following_account: Account<'info, UserProfile>,
+    /// CHECK: Only exists if the followed user blocked the follower; must be empty
+    #[account(
+        seeds = [b"block", following_account.authority.as_ref(), follower.key().as_ref()],
+        bump
+    )]
+    pub block_record: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub follower: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BlockUser<'info> {
+    #[account(
+        init,
+        payer = blocker,
+        space = BlockRecord::LEN,
+        seeds = [b"block", blocker.key().as_ref(), blocked.key().as_ref()],
+        bump
+    )]
+    pub block_record: Account<'info, BlockRecord>,
+    #[account(
+        mut,
+        seeds = [b"user_profile", blocker.key().as_ref()],
+        bump = blocker_account.bump
+    )]
+    pub blocker_account: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"user_profile", blocked.key().as_ref()],
+        bump = blocked_account.bump
+    )]
+    pub blocked_account: Account<'info, UserProfile>,
+    /// The blocked user's follow of the blocker, if any; closed with rent returned to them
+    #[account(
+        mut,
+        close = blocked,
+        seeds = [b"follow", blocked.key().as_ref(), blocker_account.key().as_ref()],
+        bump = existing_follow.bump
+    )]
+    pub existing_follow: Option<Account<'info, FollowAccount>>,
+    /// CHECK: The user being blocked; only receives the follow rent refund
+    #[account(mut)]
+    pub blocked: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub blocker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockUser<'info> {
+    #[account(
+        mut,
+        close = blocker,
+        seeds = [b"block", blocker.key().as_ref(), blocked.key().as_ref()],
+        bump = block_record.bump
+    )]
+    pub block_record: Account<'info, BlockRecord>,
+    /// CHECK: The previously blocked user; only used for the PDA seeds
+    pub blocked: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub blocker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MuteFeed<'info> {
+    #[account(
+        init_if_needed,
+        payer = muter,
+        space = FeedMute::LEN,
+        seeds = [b"feed_mute", muter.key().as_ref(), muted.key().as_ref()],
+        bump
+    )]
+    pub feed_mute: Account<'info, FeedMute>,
+    /// CHECK: The user whose posts are hidden; only used for the PDA seeds
+    pub muted: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub muter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnmuteFeed<'info> {
+    #[account(
+        mut,
+        close = muter,
+        seeds = [b"feed_mute", muter.key().as_ref(), muted.key().as_ref()],
+        bump = feed_mute.bump
+    )]
+    pub feed_mute: Account<'info, FeedMute>,
+    /// CHECK: The user whose posts were hidden; only used for the PDA seeds
+    pub muted: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub muter: Signer<'info>,
+}
+
+#[account]
+pub struct FeedMute {
+    pub muter: Pubkey,
+    pub muted: Pubkey,
+    pub bump: u8,
+}
+
+impl FeedMute {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // muter
+        32 + // muted
+        1; // bump
+}
+
+/// Stops `blocked` from following or messaging `blocker`, keyed by both wallets
+#[account]
+pub struct BlockRecord {
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl BlockRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // blocker
+        32 + // blocked
+        8 + // created_at
+        1; // bump
+}
+
+#[derive(Accounts)]
+pub struct ApproveFollow<'info> {
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"follow_request", requester.key().as_ref(), following_account.key().as_ref()],
+        bump = follow_request.bump
+    )]
+    pub follow_request: Account<'info, FollowRequest>,
+    #[account(
+        init,
+        payer = authority,
+        space = FollowAccount::LEN,
+        seeds = [b"follow", requester.key().as_ref(), following_account.key().as_ref()],
+        bump
     )]
     pub follow_account: Account<'info, FollowAccount>,
     #[account(
         mut,
-        seeds = [b"user", follower.key().as_ref()],
+        seeds = [b"user_profile", requester.key().as_ref()],
         bump = follower_account.bump
     )]
-    pub follower_account: Account<'info, UserAccount>,
+    pub follower_account: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = following_account.bump
+    )]
+    pub following_account: Account<'info, UserProfile>,
+    /// CHECK: Requester receiving the request rent refund
+    #[account(mut)]
+    pub requester: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectFollow<'info> {
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"follow_request", requester.key().as_ref(), following_account.key().as_ref()],
+        bump = follow_request.bump
+    )]
+    pub follow_request: Account<'info, FollowRequest>,
+    #[account(
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = following_account.bump
+    )]
+    pub following_account: Account<'info, UserProfile>,
+    /// CHECK: Requester receiving the request rent refund
     #[account(mut)]
-    pub following_account: Account<'info, UserAccount>,
+    pub requester: AccountInfo<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFollowRequest<'info> {
+    #[account(
+        mut,
+        close = follower,
+        seeds = [b"follow_request", follower.key().as_ref(), following_account.key().as_ref()],
+        bump = follow_request.bump
+    )]
+    pub follow_request: Account<'info, FollowRequest>,
+    pub following_account: Account<'info, UserProfile>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub follower: Signer<'info>,
 }
 
+#[account]
+pub struct FollowRequest {
+    pub requester: Pubkey,
+    pub target: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl FollowRequest {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // requester
+        32 + // target
+        8 + // created_at
+        1; // bump
+}
+
 #[derive(Accounts)]
 pub struct BuyUserTokens<'info> {
     #[account(mut)]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
     #[account(
         init_if_needed,
         payer = buyer,
@@ -348,9 +1325,57 @@ pub struct BuyUserTokens<'info> {
         bump
     )]
     pub buyer_token_account: Account<'info, UserTokenAccount>,
-    /// CHECK: User authority for receiving SOL
-    #[account(mut)]
-    pub user_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TokenEscrow::LEN,
+        seeds = [b"token_escrow", user_account.key().as_ref()],
+        bump
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    /// Creator's curve; created with the default curve if they never configured one
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = CreatorToken::LEN,
+        seeds = [b"creator_token", user_account.authority.as_ref()],
+        bump
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = RewardDistributionCursor::LEN,
+        seeds = [b"reward_cursor", user_account.authority.as_ref()],
+        bump
+    )]
+    pub reward_cursor: Account<'info, RewardDistributionCursor>,
+    /// Buyer's holder record for this creator; created on their first buy
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = TokenHolder::LEN,
+        seeds = [b"token_holder", user_account.authority.as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub token_holder: Account<'info, TokenHolder>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    /// Buyer's own profile, if they have one; its volume sets their fee tier
+    #[account(
+        mut,
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump = trader_profile.bump
+    )]
+    pub trader_profile: Option<Account<'info, UserProfile>>,
+    #[account(
+        mut,
+        address = global_state.treasury @ SolSocialError::InvalidTreasury
+    )]
+    pub treasury: SystemAccount<'info>,
     #[account(mut)]
     pub buyer: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -359,16 +1384,53 @@ pub struct BuyUserTokens<'info> {
 #[derive(Accounts)]
 pub struct SellUserTokens<'info> {
     #[account(mut)]
-    pub user_account: Account<'info, UserAccount>,
+    pub user_account: Account<'info, UserProfile>,
     #[account(
         mut,
         seeds = [b"user_tokens", seller.key().as_ref(), user_account.key().as_ref()],
         bump = seller_token_account.bump
     )]
     pub seller_token_account: Account<'info, UserTokenAccount>,
-    /// CHECK: User authority for sending SOL
-    #[account(mut)]
-    pub user_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"token_escrow", user_account.key().as_ref()],
+        bump = token_escrow.bump
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    #[account(
+        mut,
+        seeds = [b"creator_token", user_account.authority.as_ref()],
+        bump = creator_token.bump
+    )]
+    pub creator_token: Account<'info, CreatorToken>,
+    #[account(
+        seeds = [b"reward_cursor", user_account.authority.as_ref()],
+        bump = reward_cursor.bump
+    )]
+    pub reward_cursor: Account<'info, RewardDistributionCursor>,
+    #[account(
+        mut,
+        seeds = [b"token_holder", user_account.authority.as_ref(), seller.key().as_ref()],
+        bump = token_holder.bump
+    )]
+    pub token_holder: Account<'info, TokenHolder>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    /// Seller's own profile, if they have one; its volume sets their fee tier
+    #[account(
+        mut,
+        seeds = [b"user_profile", seller.key().as_ref()],
+        bump = trader_profile.bump
+    )]
+    pub trader_profile: Option<Account<'info, UserProfile>>,
+    #[account(
+        mut,
+        address = global_state.treasury @ SolSocialError::InvalidTreasury
+    )]
+    pub treasury: SystemAccount<'info>,
     #[account(mut)]
     pub seller: Signer<'info>,
 }
@@ -380,6 +1442,12 @@ pub struct TipPost<'info> {
     /// CHECK: Post author for receiving tip
     #[account(mut)]
     pub post_author: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(mut)]
     pub tipper: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -387,6 +1455,11 @@ pub struct TipPost<'info> {
 
 #[derive(Accounts)]
 pub struct CreateComment<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     #[account(
         init,
         payer = authority,
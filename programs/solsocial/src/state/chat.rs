@@ -1,7 +1,12 @@
-```rust
 use anchor_lang::prelude::*;
 
+use crate::errors::SolSocialError;
+use crate::utils::bonding_curve::{
+    BondingCurveParams, BASE_PRICE, CURVE_FACTOR, CURVE_TYPE_QUADRATIC, MAX_SUPPLY,
+};
+
 #[account]
+#[derive(Default)]
 pub struct ChatRoom {
     pub authority: Pubkey,
     pub name: String,
@@ -13,6 +18,9 @@ pub struct ChatRoom {
     pub is_active: bool,
     pub max_participants: u32,
     pub current_participants: u32,
+    /// Opts the room into the reputation and spam limits from `ChatSettings`
+    pub is_moderated: bool,
+    pub pinned_messages: u8,
     pub bump: u8,
 }
 
@@ -28,10 +36,134 @@ impl ChatRoom {
         1 + // is_active
         4 + // max_participants
         4 + // current_participants
+        1 + // is_moderated
+        1 + // pinned_messages
         1; // bump
+
+    pub const MAX_PINNED_MESSAGES: u8 = 5;
+}
+
+impl ChatRoom {
+    pub fn is_full(&self) -> bool {
+        self.current_participants >= self.max_participants
+    }
+
+    /// Counts a new message and returns its sequence id in this room. Ids start
+    /// at 1, so a read cursor at `total_messages` means everything was read.
+    pub fn next_message_id(&mut self) -> Result<u64> {
+        self.total_messages = self.total_messages
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(self.total_messages)
+    }
+
+    /// Reserves a seat for a new participant and returns the entry fee they owe.
+    pub fn admit_participant(&mut self) -> Result<u64> {
+        require!(!self.is_full(), SolSocialError::ChatRoomFull);
+
+        self.current_participants = self.current_participants
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(self.entry_fee)
+    }
+
+    /// Frees the seat of a departing member. The authority is the room's only
+    /// admin, so it has to hand the room over before it can leave.
+    pub fn release_participant(&mut self, user: Pubkey) -> Result<()> {
+        require_keys_neq!(user, self.authority, SolSocialError::SoleAdminCannotLeave);
+
+        self.current_participants = self.current_participants
+            .checked_sub(1)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        Ok(())
+    }
+
+    /// Hands the room to another member of `room`. The successor picks up
+    /// moderator rights and the outgoing owner drops back to a regular member.
+    pub fn transfer_ownership(
+        &mut self,
+        room: Pubkey,
+        signer: Pubkey,
+        owner: &mut ChatParticipant,
+        successor: &mut ChatParticipant,
+    ) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        require!(owner.is_member_of(room, signer), SolSocialError::NotChatMember);
+        require!(successor.is_member_of(room, successor.user), SolSocialError::NotChatMember);
+        require_keys_neq!(successor.user, signer, SolSocialError::InvalidPublicKey);
+
+        self.authority = successor.user;
+        owner.is_moderator = false;
+        successor.is_moderator = true;
+        Ok(())
+    }
+
+    /// Pinning an already pinned message is a no-op; returns whether it changed.
+    pub fn pin_message(&mut self, message: &mut Message) -> Result<bool> {
+        require!(!message.is_deleted, SolSocialError::MessageNotFound);
+        if message.is_pinned {
+            return Ok(false);
+        }
+        require!(
+            self.pinned_messages < Self::MAX_PINNED_MESSAGES,
+            SolSocialError::MaxPinnedMessagesExceeded
+        );
+
+        self.pinned_messages += 1;
+        message.is_pinned = true;
+        Ok(true)
+    }
+
+    pub fn unpin_message(&mut self, message: &mut Message) -> Result<bool> {
+        if !message.is_pinned {
+            return Ok(false);
+        }
+
+        self.pinned_messages = self.pinned_messages
+            .checked_sub(1)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        message.is_pinned = false;
+        Ok(true)
+    }
+
+    /// Only the room authority grants or revokes moderator rights.
+    pub fn set_moderator(&self, signer: Pubkey, member: &mut ChatParticipant, is_moderator: bool) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        require_keys_neq!(member.user, self.authority, SolSocialError::InvalidPublicKey);
+
+        member.is_moderator = is_moderator;
+        Ok(())
+    }
+
+    /// Admins and moderators may mute anyone except the admin.
+    pub fn set_muted(&self, actor: &ChatParticipant, member: &mut ChatParticipant, is_muted: bool) -> Result<()> {
+        require!(actor.can_moderate(self), SolSocialError::UnauthorizedAccess);
+        require_keys_neq!(member.user, self.authority, SolSocialError::UnauthorizedAccess);
+
+        member.is_muted = is_muted;
+        Ok(())
+    }
+}
+
+/// Orders two members by their raw bytes so both sides derive the same DM room.
+pub fn canonical_dm_pair(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+    if a.to_bytes() <= b.to_bytes() {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+pub fn dm_room_address(a: Pubkey, b: Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    let (first, second) = canonical_dm_pair(a, b);
+    Pubkey::find_program_address(
+        &[b"chat_room", first.as_ref(), second.as_ref()],
+        program_id,
+    )
 }
 
 #[account]
+#[derive(Default)]
 pub struct Message {
     pub author: Pubkey,
     pub chat_room: Pubkey,
@@ -41,6 +173,8 @@ pub struct Message {
     pub tip_amount: u64,
     pub reply_to: Option<u64>,
     pub is_pinned: bool,
+    pub is_deleted: bool,
+    pub edited_at: Option<i64>,
     pub reactions: Vec<Reaction>,
     pub bump: u8,
 }
@@ -55,8 +189,53 @@ impl Message {
         8 + // tip_amount
         1 + 8 + // reply_to (Option<u64>)
         1 + // is_pinned
+        1 + // is_deleted
+        1 + 8 + // edited_at (Option<i64>)
         4 + (10 * Reaction::LEN) + // reactions (Vec with max 10 reactions)
         1; // bump
+
+    pub const MAX_CONTENT_LENGTH: usize = 500;
+    pub const MAX_REACTIONS: usize = 10;
+    pub const EDIT_WINDOW: i64 = 15 * 60; // 15 minutes
+
+    pub fn can_delete(&self, actor: Pubkey, is_moderator: bool) -> bool {
+        self.author == actor || is_moderator
+    }
+
+    pub fn can_edit(&self, actor: Pubkey, now: i64) -> bool {
+        self.author == actor && !self.is_deleted && now - self.timestamp <= Self::EDIT_WINDOW
+    }
+
+    pub fn add_reaction(&mut self, reactor: Pubkey, emoji: String, timestamp: i64) -> Result<()> {
+        require!(
+            !emoji.is_empty() && emoji.len() <= Reaction::MAX_EMOJI_LENGTH,
+            SolSocialError::InvalidEmoji
+        );
+        require!(
+            !self.reactions.iter().any(|reaction| reaction.reactor == reactor),
+            SolSocialError::AlreadyReacted
+        );
+        require!(
+            self.reactions.len() < Self::MAX_REACTIONS,
+            SolSocialError::ReactionLimitReached
+        );
+
+        self.reactions.push(Reaction {
+            reactor,
+            emoji,
+            timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn remove_reaction(&mut self, reactor: Pubkey) -> Result<()> {
+        let index = self.reactions
+            .iter()
+            .position(|reaction| reaction.reactor == reactor)
+            .ok_or(SolSocialError::ReactionNotFound)?;
+        self.reactions.remove(index);
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -67,12 +246,15 @@ pub struct Reaction {
 }
 
 impl Reaction {
+    pub const MAX_EMOJI_LENGTH: usize = 10;
+
     pub const LEN: usize = 32 + // reactor
-        4 + 10 + // emoji (String with length prefix)
+        4 + Self::MAX_EMOJI_LENGTH + // emoji (String with length prefix)
         8; // timestamp
 }
 
 #[account]
+#[derive(Default)]
 pub struct ChatParticipant {
     pub user: Pubkey,
     pub chat_room: Pubkey,
@@ -84,6 +266,9 @@ pub struct ChatParticipant {
     pub is_moderator: bool,
     pub is_muted: bool,
     pub reputation_score: u32,
+    pub last_read_message: u64,
+    pub window_started_at: i64,
+    pub window_message_count: u32,
     pub bump: u8,
 }
 
@@ -99,7 +284,63 @@ impl ChatParticipant {
         1 + // is_moderator
         1 + // is_muted
         4 + // reputation_score
+        8 + // last_read_message
+        8 + // window_started_at
+        4 + // window_message_count
         1; // bump
+
+    pub fn is_member_of(&self, chat_room: Pubkey, user: Pubkey) -> bool {
+        self.chat_room == chat_room && self.user == user
+    }
+
+    pub fn can_moderate(&self, chat_room: &ChatRoom) -> bool {
+        self.is_moderator || self.user == chat_room.authority
+    }
+
+    /// Advances the read cursor to a message's `message_id`; marking an
+    /// already-read range is a no-op. Returns whether the cursor moved.
+    pub fn mark_read(&mut self, up_to_message_id: u64, total_messages: u64) -> Result<bool> {
+        require!(up_to_message_id <= total_messages, SolSocialError::MessageNotFound);
+
+        if up_to_message_id <= self.last_read_message {
+            return Ok(false);
+        }
+        self.last_read_message = up_to_message_id;
+        Ok(true)
+    }
+
+    pub fn unread_count(&self, total_messages: u64) -> u64 {
+        total_messages.saturating_sub(self.last_read_message)
+    }
+
+    /// Counts a message against the current spam window, starting a fresh
+    /// window once the previous one has elapsed. A zero threshold disables the limit.
+    pub fn record_message(&mut self, now: i64, spam_threshold: u32) -> Result<()> {
+        if now - self.window_started_at >= ChatSettings::SPAM_WINDOW {
+            self.window_started_at = now;
+            self.window_message_count = 0;
+        }
+
+        self.window_message_count = self.window_message_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        require!(
+            spam_threshold == 0 || self.window_message_count <= spam_threshold,
+            SolSocialError::SpamDetectionTriggered
+        );
+        Ok(())
+    }
+}
+
+pub fn require_chat_member(
+    participant: Option<&ChatParticipant>,
+    chat_room: Pubkey,
+    user: Pubkey,
+) -> Result<()> {
+    match participant {
+        Some(participant) if participant.is_member_of(chat_room, user) => Ok(()),
+        _ => err!(SolSocialError::NotChatMember),
+    }
 }
 
 #[account]
@@ -150,53 +391,34 @@ impl ChatSettings {
         32 + // treasury
         1 + // is_paused
         1; // bump
-}
 
-#[account]
-pub struct UserProfile {
-    pub owner: Pubkey,
-    pub username: String,
-    pub bio: String,
-    pub avatar_url: String,
-    pub token_balance: u64,
-    pub total_messages_sent: u64,
-    pub total_tips_sent: u64,
-    pub total_tips_received: u64,
-    pub reputation_score: u32,
-    pub created_at: i64,
-    pub last_active: i64,
-    pub is_verified: bool,
-    pub social_links: Vec<SocialLink>,
-    pub bump: u8,
-}
+    /// Length of the window `spam_threshold` is measured over
+    pub const SPAM_WINDOW: i64 = 60; // 1 minute
 
-impl UserProfile {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // owner
-        4 + 50 + // username (String with length prefix)
-        4 + 200 + // bio (String with length prefix)
-        4 + 200 + // avatar_url (String with length prefix)
-        8 + // token_balance
-        8 + // total_messages_sent
-        8 + // total_tips_sent
-        8 + // total_tips_received
-        4 + // reputation_score
-        8 + // created_at
-        8 + // last_active
-        1 + // is_verified
-        4 + (5 * SocialLink::LEN) + // social_links (Vec with max 5 links)
-        1; // bump
+    pub fn require_reputation(&self, reputation_score: u64) -> Result<()> {
+        require!(
+            reputation_score >= self.reputation_threshold as u64,
+            SolSocialError::SpamDetectionTriggered
+        );
+        Ok(())
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct SocialLink {
-    pub platform: String,
-    pub url: String,
-}
+/// Applies the platform anti-spam rules to a sender in a moderated room;
+/// unmoderated rooms are left alone.
+pub fn enforce_chat_moderation(
+    settings: &ChatSettings,
+    chat_room: &ChatRoom,
+    participant: &mut ChatParticipant,
+    reputation_score: u64,
+    now: i64,
+) -> Result<()> {
+    if !chat_room.is_moderated {
+        return Ok(());
+    }
 
-impl SocialLink {
-    pub const LEN: usize = 4 + 20 + // platform (String with length prefix)
-        4 + 200; // url (String with length prefix)
+    settings.require_reputation(reputation_score)?;
+    participant.record_message(now, settings.spam_threshold)
 }
 
 #[account]
@@ -208,6 +430,7 @@ pub struct TokenPrice {
     pub volume_24h: u64,
     pub price_change_24h: i64,
     pub market_cap: u64,
+    pub price_window: [PriceBucket; 24],
     pub bump: u8,
 }
 
@@ -220,7 +443,76 @@ impl TokenPrice {
         8 + // volume_24h
         8 + // price_change_24h
         8 + // market_cap
+        24 * PriceBucket::LEN + // price_window (one bucket per hour)
         1; // bump
+
+    /// Default freshness bound for consumers that don't need a tighter one
+    pub const MAX_PRICE_AGE: i64 = 5 * 60; // 5 minutes
+    pub const BUCKET_SECONDS: i64 = 60 * 60;
+    pub const WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+    /// Records a trade in the hourly ring buffer and recomputes the 24h volume
+    /// and price change. Buckets that fell out of the window are ignored and
+    /// overwritten as their slot comes around again.
+    pub fn roll_price_window(&mut self, price: u64, volume: u64, now: i64) -> Result<()> {
+        let hour = now / Self::BUCKET_SECONDS;
+        let slot = (hour.rem_euclid(self.price_window.len() as i64)) as usize;
+
+        let bucket = &mut self.price_window[slot];
+        if bucket.hour != hour {
+            *bucket = PriceBucket {
+                hour,
+                open_price: price,
+                volume: 0,
+            };
+        }
+        bucket.volume = bucket.volume
+            .checked_add(volume)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        let oldest_hour = hour - (Self::WINDOW_SECONDS / Self::BUCKET_SECONDS) + 1;
+        let mut volume_24h: u64 = 0;
+        let mut reference: Option<&PriceBucket> = None;
+        for bucket in self.price_window.iter().filter(|b| b.volume > 0 && b.hour >= oldest_hour) {
+            volume_24h = volume_24h
+                .checked_add(bucket.volume)
+                .ok_or(SolSocialError::MathOverflow)?;
+            if reference.map_or(true, |r| bucket.hour < r.hour) {
+                reference = Some(bucket);
+            }
+        }
+        let price_24h_ago = reference.map_or(price, |r| r.open_price);
+
+        self.volume_24h = volume_24h;
+        self.price_change_24h = (price as i128 - price_24h_ago as i128)
+            .try_into()
+            .map_err(|_| error!(SolSocialError::MathOverflow))?;
+        self.price_in_sol = price;
+        self.last_updated = now;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceBucket {
+    pub hour: i64,
+    pub open_price: u64,
+    pub volume: u64,
+}
+
+impl PriceBucket {
+    pub const LEN: usize = 8 + // hour
+        8 + // open_price
+        8; // volume
+}
+
+pub fn require_fresh_price(token_price: &TokenPrice, max_age: i64, now: i64) -> Result<u64> {
+    require!(token_price.last_updated > 0, SolSocialError::PriceFeedNotFound);
+    require!(
+        now.saturating_sub(token_price.last_updated) <= max_age,
+        SolSocialError::OracleDataTooOld
+    );
+    Ok(token_price.price_in_usdc)
 }
 
 #[account]
@@ -252,6 +544,7 @@ impl Subscription {
 }
 
 #[account]
+#[derive(Default)]
 pub struct CreatorToken {
     pub creator: Pubkey,
     pub mint: Pubkey,
@@ -285,6 +578,46 @@ impl CreatorToken {
         8 + // created_at
         1 + // is_tradeable
         1; // bump
+
+    pub fn curve_params(&self) -> BondingCurveParams {
+        BondingCurveParams {
+            base_price: self.base_price,
+            curve_factor: self.price_multiplier,
+            max_supply: self.total_supply,
+        }
+    }
+
+    /// Gives a creator who never configured a curve the default quadratic one.
+    /// A no-op once set, so buy paths can call it on every `init_if_needed`.
+    pub fn ensure_initialized(&mut self, creator: Pubkey, now: i64, bump: u8) {
+        if self.creator != Pubkey::default() {
+            return;
+        }
+        self.creator = creator;
+        self.total_supply = MAX_SUPPLY;
+        self.circulating_supply = 0;
+        self.price_curve_type = CURVE_TYPE_QUADRATIC;
+        self.base_price = BASE_PRICE;
+        self.price_multiplier = CURVE_FACTOR;
+        self.created_at = now;
+        self.is_tradeable = true;
+        self.bump = bump;
+    }
+
+    /// Keeps `circulating_supply` in step with trades so `set_curve_params` stays
+    /// locked once anyone holds a key.
+    pub fn record_trade(&mut self, amount: u64, is_buy: bool) -> Result<()> {
+        self.circulating_supply = if is_buy {
+            self.circulating_supply
+                .checked_add(amount)
+                .ok_or(SolSocialError::MathOverflow)?
+        } else {
+            self.circulating_supply
+                .checked_sub(amount)
+                .ok_or(SolSocialError::MathUnderflow)?
+        };
+        Ok(())
+    }
 }
 
 #[account]
@@ -312,4 +645,452 @@ impl Trade {
         8 + // timestamp
         1; // bump
 }
-```
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(author: Pubkey) -> Message {
+        Message {
+            author,
+            chat_room: Pubkey::default(),
+            content: "gm".to_string(),
+            timestamp: 1_000,
+            message_id: 0,
+            tip_amount: 0,
+            reply_to: None,
+            is_pinned: false,
+            is_deleted: false,
+            edited_at: None,
+            reactions: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    fn chat_room(entry_fee: u64, max_participants: u32) -> ChatRoom {
+        ChatRoom {
+            authority: Pubkey::new_unique(),
+            name: "room".to_string(),
+            description: String::new(),
+            token_mint: Pubkey::new_unique(),
+            entry_fee,
+            total_messages: 0,
+            created_at: 0,
+            is_active: true,
+            max_participants,
+            current_participants: 1,
+            is_moderated: false,
+            pinned_messages: 0,
+            bump: 0,
+        }
+    }
+
+    fn participant(chat_room: Pubkey, user: Pubkey) -> ChatParticipant {
+        ChatParticipant {
+            user,
+            chat_room,
+            joined_at: 0,
+            last_active: 0,
+            message_count: 0,
+            total_tips_sent: 0,
+            total_tips_received: 0,
+            is_moderator: false,
+            is_muted: false,
+            reputation_score: 0,
+            last_read_message: 0,
+            window_started_at: 0,
+            window_message_count: 0,
+            bump: 0,
+        }
+    }
+
+    fn chat_settings(spam_threshold: u32, reputation_threshold: u32) -> ChatSettings {
+        ChatSettings {
+            authority: Pubkey::new_unique(),
+            global_message_fee: 0,
+            global_tip_fee_percentage: 0,
+            max_message_length: 500,
+            spam_threshold,
+            reputation_threshold,
+            treasury: Pubkey::new_unique(),
+            is_paused: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_join_charges_entry_fee_and_takes_a_seat() {
+        let mut room = chat_room(250, 3);
+        assert_eq!(room.admit_participant().unwrap(), 250);
+        assert_eq!(room.current_participants, 2);
+    }
+
+    #[test]
+    fn test_join_rejected_when_room_full() {
+        let mut room = chat_room(0, 2);
+        room.admit_participant().unwrap();
+        assert!(room.is_full());
+        assert!(room.admit_participant().is_err());
+        assert_eq!(room.current_participants, 2);
+    }
+
+    #[test]
+    fn test_join_then_leave_frees_the_seat() {
+        let mut room = chat_room(0, 3);
+        let member = Pubkey::new_unique();
+
+        room.admit_participant().unwrap();
+        assert_eq!(room.current_participants, 2);
+        room.release_participant(member).unwrap();
+        assert_eq!(room.current_participants, 1);
+    }
+
+    #[test]
+    fn test_sole_admin_cannot_leave() {
+        let mut room = chat_room(0, 3);
+        let authority = room.authority;
+
+        assert!(room.release_participant(authority).is_err());
+        assert_eq!(room.current_participants, 1);
+    }
+
+    #[test]
+    fn test_owner_transfers_room_to_member() {
+        let room_key = Pubkey::new_unique();
+        let mut room = chat_room(0, 3);
+        let owner_key = room.authority;
+        let mut owner = participant(room_key, owner_key);
+        owner.is_moderator = true;
+        let mut successor = participant(room_key, Pubkey::new_unique());
+
+        room.transfer_ownership(room_key, owner_key, &mut owner, &mut successor).unwrap();
+        assert_eq!(room.authority, successor.user);
+        assert!(successor.is_moderator);
+        assert!(!owner.is_moderator);
+
+        // The previous owner can now leave
+        assert!(room.release_participant(owner_key).is_ok());
+    }
+
+    #[test]
+    fn test_transfer_to_non_member_rejected() {
+        let room_key = Pubkey::new_unique();
+        let mut room = chat_room(0, 3);
+        let owner_key = room.authority;
+        let mut owner = participant(room_key, owner_key);
+        let mut outsider = participant(Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(room.transfer_ownership(room_key, owner_key, &mut owner, &mut outsider).is_err());
+        assert_eq!(room.authority, owner_key);
+
+        let mut member = participant(room_key, Pubkey::new_unique());
+        assert!(room.transfer_ownership(room_key, member.user, &mut owner, &mut member).is_err());
+    }
+
+    #[test]
+    fn test_non_member_cannot_send() {
+        let room = Pubkey::new_unique();
+        let sender = Pubkey::new_unique();
+        let member = participant(room, sender);
+
+        assert!(require_chat_member(Some(&member), room, sender).is_ok());
+        assert!(require_chat_member(None, room, sender).is_err());
+        assert!(require_chat_member(Some(&member), Pubkey::new_unique(), sender).is_err());
+        assert!(require_chat_member(Some(&member), room, Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_mark_subset_read() {
+        let mut reader = participant(Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(reader.unread_count(10), 10);
+
+        assert!(reader.mark_read(4, 10).unwrap());
+        assert_eq!(reader.last_read_message, 4);
+        assert_eq!(reader.unread_count(10), 6);
+
+        assert!(reader.mark_read(11, 10).is_err());
+    }
+
+    #[test]
+    fn test_mark_read_up_to_sent_message() {
+        let mut room = ChatRoom::default();
+        let first = room.next_message_id().unwrap();
+        let second = room.next_message_id().unwrap();
+        let third = room.next_message_id().unwrap();
+        assert_eq!((first, second, third), (1, 2, 3));
+        assert_eq!(room.total_messages, 3);
+
+        let mut reader = participant(Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(reader.unread_count(room.total_messages), 3);
+        assert!(reader.mark_read(second, room.total_messages).unwrap());
+        assert_eq!(reader.unread_count(room.total_messages), 1);
+        assert!(reader.mark_read(third, room.total_messages).unwrap());
+        assert_eq!(reader.unread_count(room.total_messages), 0);
+    }
+
+    #[test]
+    fn test_mark_read_is_idempotent() {
+        let mut reader = participant(Pubkey::new_unique(), Pubkey::new_unique());
+        reader.mark_read(7, 10).unwrap();
+
+        assert!(!reader.mark_read(7, 10).unwrap());
+        assert!(!reader.mark_read(3, 10).unwrap());
+        assert_eq!(reader.last_read_message, 7);
+        assert_eq!(reader.unread_count(10), 3);
+    }
+
+    fn token_price(last_updated: i64) -> TokenPrice {
+        TokenPrice {
+            mint: Pubkey::new_unique(),
+            price_in_sol: 1_000,
+            price_in_usdc: 2_500_000,
+            last_updated,
+            volume_24h: 0,
+            price_change_24h: 0,
+            market_cap: 0,
+            price_window: [PriceBucket::default(); 24],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_fresh_price_accepted() {
+        let feed = token_price(10_000);
+        assert_eq!(require_fresh_price(&feed, TokenPrice::MAX_PRICE_AGE, 10_000 + TokenPrice::MAX_PRICE_AGE).unwrap(), 2_500_000);
+    }
+
+    #[test]
+    fn test_stale_price_rejected() {
+        let feed = token_price(10);
+        assert!(require_fresh_price(&feed, TokenPrice::MAX_PRICE_AGE, 1_000_000).is_err());
+        // A feed that was never written is missing, not stale
+        assert!(require_fresh_price(&token_price(0), TokenPrice::MAX_PRICE_AGE, 0).is_err());
+    }
+
+    #[test]
+    fn test_price_window_tracks_24h_change() {
+        let mut feed = token_price(0);
+        let hour = TokenPrice::BUCKET_SECONDS;
+
+        feed.roll_price_window(100, 1_000, 0).unwrap();
+        feed.roll_price_window(120, 500, 5 * hour).unwrap();
+        assert_eq!(feed.volume_24h, 1_500);
+        assert_eq!(feed.price_change_24h, 20);
+
+        feed.roll_price_window(90, 250, 5 * hour + 60).unwrap();
+        assert_eq!(feed.volume_24h, 1_750);
+        assert_eq!(feed.price_change_24h, -10);
+    }
+
+    #[test]
+    fn test_price_window_drops_stale_volume() {
+        let mut feed = token_price(0);
+        let hour = TokenPrice::BUCKET_SECONDS;
+
+        // Trades at hours 0, 6, 12, 18, 24 and 30 of a 30-hour span
+        for (i, price) in [100u64, 110, 120, 130, 140, 150].iter().enumerate() {
+            feed.roll_price_window(*price, 1_000, i as i64 * 6 * hour).unwrap();
+        }
+
+        // Only hours 12 through 30 are inside the window at hour 30
+        assert_eq!(feed.volume_24h, 4_000);
+        assert_eq!(feed.price_change_24h, 150 - 120);
+        assert_eq!(feed.price_in_sol, 150);
+
+        // A day of silence empties the window apart from the new trade
+        feed.roll_price_window(160, 10, 60 * hour).unwrap();
+        assert_eq!(feed.volume_24h, 10);
+        assert_eq!(feed.price_change_24h, 0);
+    }
+
+    #[test]
+    fn test_dm_room_is_order_independent() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+
+        assert_eq!(canonical_dm_pair(alice, bob), canonical_dm_pair(bob, alice));
+        assert_eq!(
+            dm_room_address(alice, bob, &crate::ID),
+            dm_room_address(bob, alice, &crate::ID)
+        );
+        assert_ne!(
+            dm_room_address(alice, bob, &crate::ID).0,
+            dm_room_address(alice, Pubkey::new_unique(), &crate::ID).0
+        );
+    }
+
+    #[test]
+    fn test_moderator_can_delete_others_message() {
+        let author = Pubkey::new_unique();
+        let moderator = Pubkey::new_unique();
+        assert!(message(author).can_delete(moderator, true));
+        assert!(message(author).can_delete(author, false));
+    }
+
+    #[test]
+    fn test_non_moderator_cannot_delete_others_message() {
+        let author = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        assert!(!message(author).can_delete(other, false));
+    }
+
+    #[test]
+    fn test_admin_promotes_member_to_moderator() {
+        let room_key = Pubkey::new_unique();
+        let room = chat_room(0, 3);
+        let mut member = participant(room_key, Pubkey::new_unique());
+
+        room.set_moderator(room.authority, &mut member, true).unwrap();
+        assert!(member.is_moderator);
+        assert!(member.can_moderate(&room));
+    }
+
+    #[test]
+    fn test_non_admin_cannot_promote() {
+        let room_key = Pubkey::new_unique();
+        let room = chat_room(0, 3);
+        let mut moderator = participant(room_key, Pubkey::new_unique());
+        moderator.is_moderator = true;
+        let mut member = participant(room_key, Pubkey::new_unique());
+
+        assert!(room.set_moderator(moderator.user, &mut member, true).is_err());
+        assert!(room.set_moderator(member.user, &mut member.clone(), true).is_err());
+        assert!(!member.is_moderator);
+
+        // Moderators can still mute, plain members cannot
+        let mut target = participant(room_key, Pubkey::new_unique());
+        room.set_muted(&moderator, &mut target, true).unwrap();
+        assert!(target.is_muted);
+        assert!(room.set_muted(&member, &mut target, false).is_err());
+    }
+
+    #[test]
+    fn test_demoted_moderator_loses_delete_rights() {
+        let room_key = Pubkey::new_unique();
+        let room = chat_room(0, 3);
+        let mut moderator = participant(room_key, Pubkey::new_unique());
+        let msg = message(Pubkey::new_unique());
+
+        room.set_moderator(room.authority, &mut moderator, true).unwrap();
+        assert!(msg.can_delete(moderator.user, moderator.can_moderate(&room)));
+
+        room.set_moderator(room.authority, &mut moderator, false).unwrap();
+        assert!(!msg.can_delete(moderator.user, moderator.can_moderate(&room)));
+    }
+
+    #[test]
+    fn test_pin_up_to_limit_then_reject() {
+        let mut room = chat_room(0, 3);
+        let mut pinned: Vec<Message> = (0..ChatRoom::MAX_PINNED_MESSAGES)
+            .map(|_| message(Pubkey::new_unique()))
+            .collect();
+
+        for msg in pinned.iter_mut() {
+            assert!(room.pin_message(msg).unwrap());
+        }
+        assert_eq!(room.pinned_messages, ChatRoom::MAX_PINNED_MESSAGES);
+        assert!(!room.pin_message(&mut pinned[0]).unwrap());
+
+        let mut sixth = message(Pubkey::new_unique());
+        assert!(room.pin_message(&mut sixth).is_err());
+        assert!(!sixth.is_pinned);
+    }
+
+    #[test]
+    fn test_unpin_frees_a_slot() {
+        let mut room = chat_room(0, 3);
+        let mut msg = message(Pubkey::new_unique());
+
+        room.pin_message(&mut msg).unwrap();
+        assert!(room.unpin_message(&mut msg).unwrap());
+        assert!(!msg.is_pinned);
+        assert_eq!(room.pinned_messages, 0);
+        assert!(!room.unpin_message(&mut msg).unwrap());
+    }
+
+    #[test]
+    fn test_add_and_remove_reaction() {
+        let reactor = Pubkey::new_unique();
+        let mut msg = message(Pubkey::new_unique());
+
+        msg.add_reaction(reactor, "🔥".to_string(), 5).unwrap();
+        assert_eq!(msg.reactions.len(), 1);
+        assert_eq!(msg.reactions[0].reactor, reactor);
+
+        msg.remove_reaction(reactor).unwrap();
+        assert!(msg.reactions.is_empty());
+        assert!(msg.remove_reaction(reactor).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_reaction_rejected() {
+        let reactor = Pubkey::new_unique();
+        let mut msg = message(Pubkey::new_unique());
+
+        msg.add_reaction(reactor, "👍".to_string(), 5).unwrap();
+        assert!(msg.add_reaction(reactor, "🎉".to_string(), 6).is_err());
+    }
+
+    #[test]
+    fn test_reaction_cap_and_emoji_length() {
+        let mut msg = message(Pubkey::new_unique());
+        for _ in 0..Message::MAX_REACTIONS {
+            msg.add_reaction(Pubkey::new_unique(), "👍".to_string(), 5).unwrap();
+        }
+        assert!(msg.add_reaction(Pubkey::new_unique(), "👍".to_string(), 5).is_err());
+
+        let mut fresh = message(Pubkey::new_unique());
+        assert!(fresh.add_reaction(Pubkey::new_unique(), "x".repeat(11), 5).is_err());
+    }
+
+    #[test]
+    fn test_edit_limited_to_author_within_window() {
+        let author = Pubkey::new_unique();
+        let msg = message(author);
+        assert!(msg.can_edit(author, 1_000 + Message::EDIT_WINDOW));
+        assert!(!msg.can_edit(author, 1_001 + Message::EDIT_WINDOW));
+        assert!(!msg.can_edit(Pubkey::new_unique(), 1_000));
+    }
+
+    #[test]
+    fn test_low_reputation_sender_blocked_in_moderated_room() {
+        let settings = chat_settings(0, 50);
+        let mut room = chat_room(0, 2);
+        room.is_moderated = true;
+        let mut sender = participant(Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 49, 1_000).is_err());
+
+        room.is_moderated = false;
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 49, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_high_reputation_sender_allowed() {
+        let settings = chat_settings(0, 50);
+        let mut room = chat_room(0, 2);
+        room.is_moderated = true;
+        let mut sender = participant(Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 50, 1_000).is_ok());
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 500, 1_001).is_ok());
+        assert_eq!(sender.window_message_count, 2);
+    }
+
+    #[test]
+    fn test_spam_threshold_trips_within_window() {
+        let settings = chat_settings(3, 0);
+        let mut room = chat_room(0, 2);
+        room.is_moderated = true;
+        let mut sender = participant(Pubkey::new_unique(), Pubkey::new_unique());
+
+        for i in 0..3 {
+            enforce_chat_moderation(&settings, &room, &mut sender, 100, 1_000 + i).unwrap();
+        }
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 100, 1_010).is_err());
+
+        let next_window = 1_000 + ChatSettings::SPAM_WINDOW;
+        assert!(enforce_chat_moderation(&settings, &room, &mut sender, 100, next_window).is_ok());
+        assert_eq!(sender.window_message_count, 1);
+    }
+}
@@ -1,10 +1,13 @@
-```rust
 use anchor_lang::prelude::*;
 
+use crate::utils::bonding_curve::{self, BondingCurveParams, BASE_PRICE, MAX_SUPPLY};
+
 #[account]
+#[derive(Default)]
 pub struct UserKeys {
     pub owner: Pubkey,
     pub total_supply: u64,
+    pub max_supply: u64,
     pub current_price: u64,
     pub holders_count: u32,
     pub trading_fee_collected: u64,
@@ -12,6 +15,7 @@ pub struct UserKeys {
     pub is_active: bool,
     pub created_at: i64,
     pub last_trade_at: i64,
+    pub snapshot_count: u64,
     pub bump: u8,
 }
 
@@ -19,6 +23,7 @@ impl UserKeys {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
         8 + // total_supply
+        8 + // max_supply
         8 + // current_price
         4 + // holders_count
         8 + // trading_fee_collected
@@ -26,67 +31,99 @@ impl UserKeys {
         1 + // is_active
         8 + // created_at
         8 + // last_trade_at
+        8 + // snapshot_count
         1; // bump
 
     pub fn initialize(&mut self, owner: Pubkey, bump: u8) -> Result<()> {
         self.owner = owner;
         self.total_supply = 0;
-        self.current_price = 1_000_000; // 0.001 SOL in lamports
+        self.max_supply = MAX_SUPPLY;
+        self.current_price = BASE_PRICE;
         self.holders_count = 0;
         self.trading_fee_collected = 0;
         self.creator_fee_collected = 0;
         self.is_active = true;
         self.created_at = Clock::get()?.unix_timestamp;
         self.last_trade_at = Clock::get()?.unix_timestamp;
+        self.snapshot_count = 0;
         self.bump = bump;
         Ok(())
     }
 
-    pub fn calculate_price(&self, supply: u64, amount: u64, is_buy: bool) -> Result<u64> {
+    /// Hands out sequential snapshot ids, starting at zero.
+    pub fn next_snapshot_id(&mut self) -> Result<u64> {
+        let id = self.snapshot_count;
+        self.snapshot_count = self.snapshot_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(id)
+    }
+
+    /// Prices a trade on the creator's own curve (`CreatorToken::curve_params`).
+    pub fn calculate_price(
+        &self,
+        supply: u64,
+        amount: u64,
+        is_buy: bool,
+        curve_type: u8,
+        params: &BondingCurveParams,
+    ) -> Result<u64> {
         if amount == 0 {
             return Ok(0);
         }
 
-        let base_price = 1_000_000; // 0.001 SOL
-        let price_increment = 100_000; // 0.0001 SOL per key
-
         if is_buy {
-            let mut total_cost = 0u64;
-            for i in 0..amount {
-                let current_supply = supply + i;
-                let price = base_price + (current_supply * price_increment);
-                total_cost = total_cost.checked_add(price).ok_or(ErrorCode::MathOverflow)?;
-            }
-            Ok(total_cost)
+            bonding_curve::calculate_buy_cost_with_mode(supply, amount, curve_type, params)
         } else {
-            let mut total_return = 0u64;
-            for i in 0..amount {
-                let current_supply = supply - i - 1;
-                let price = base_price + (current_supply * price_increment);
-                total_return = total_return.checked_add(price).ok_or(ErrorCode::MathOverflow)?;
-            }
-            Ok(total_return)
+            bonding_curve::calculate_sell_proceeds_with_mode(supply, amount, curve_type, params)
         }
     }
 
-    pub fn get_current_price(&self) -> u64 {
-        let base_price = 1_000_000; // 0.001 SOL
-        let price_increment = 100_000; // 0.0001 SOL per key
-        base_price + (self.total_supply * price_increment)
+    pub fn get_current_price(&self, curve_type: u8, params: &BondingCurveParams) -> Result<u64> {
+        bonding_curve::calculate_price_with_mode(self.total_supply, curve_type, params)
     }
 
-    pub fn update_after_trade(&mut self, supply_change: i64, is_buy: bool) -> Result<()> {
+    /// Call after the trader's holding has been updated for the trade;
+    /// `holder_balance` is what they hold afterwards, which tells a first buy
+    /// and a full exit apart from trades by existing holders.
+    pub fn update_after_trade(
+        &mut self,
+        supply_change: i64,
+        is_buy: bool,
+        holder_balance: u64,
+        curve_type: u8,
+        params: &BondingCurveParams,
+        now: i64,
+    ) -> Result<()> {
         if is_buy {
             self.total_supply = self.total_supply.checked_add(supply_change as u64).ok_or(ErrorCode::MathOverflow)?;
-            if supply_change > 0 {
+            if supply_change > 0 && holder_balance == supply_change as u64 {
                 self.holders_count = self.holders_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
             }
         } else {
             self.total_supply = self.total_supply.checked_sub(supply_change as u64).ok_or(ErrorCode::MathUnderflow)?;
+            if supply_change > 0 && holder_balance == 0 {
+                self.holders_count = self.holders_count.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?;
+            }
         }
         
-        self.current_price = self.get_current_price();
-        self.last_trade_at = Clock::get()?.unix_timestamp;
+        self.current_price = self.get_current_price(curve_type, params)?;
+        self.last_trade_at = now;
+        Ok(())
+    }
+
+    /// Call with both wallets' balances after `KeyHolding::send_to`.
+    /// Supply is untouched; only who holds the keys changes.
+    pub fn record_transfer(
+        &mut self,
+        amount: u64,
+        sender_balance: u64,
+        recipient_balance: u64,
+    ) -> Result<()> {
+        if amount > 0 && recipient_balance == amount {
+            self.holders_count = self.holders_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+        if amount > 0 && sender_balance == 0 {
+            self.holders_count = self.holders_count.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?;
+        }
         Ok(())
     }
 
@@ -101,6 +138,50 @@ impl UserKeys {
     }
 }
 
+/// Frozen copy of a creator's key supply and holder count, so airdrops and
+/// reward rounds can all be computed against the same basis.
+#[account]
+pub struct HolderSnapshot {
+    pub key_owner: Pubkey,
+    pub snapshot_id: u64,
+    pub total_supply: u64,
+    pub holders_count: u32,
+    pub taken_at: i64,
+    pub bump: u8,
+}
+
+impl HolderSnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // key_owner
+        8 + // snapshot_id
+        8 + // total_supply
+        4 + // holders_count
+        8 + // taken_at
+        1; // bump
+
+    /// `total_supply` is the creator profile's `token_supply`, the count key trades
+    /// move; `UserKeys` contributes the holder count.
+    pub fn capture(&mut self, keys: &UserKeys, total_supply: u64, snapshot_id: u64, now: i64, bump: u8) {
+        self.key_owner = keys.owner;
+        self.snapshot_id = snapshot_id;
+        self.total_supply = total_supply;
+        self.holders_count = keys.holders_count;
+        self.taken_at = now;
+        self.bump = bump;
+    }
+
+    /// `rewards` spread over the snapshotted supply, at the same scale the reward
+    /// accumulator uses.
+    pub fn rewards_per_token(&self, rewards: u64) -> Result<u64> {
+        require!(self.total_supply > 0, ErrorCode::InvalidAmount);
+        let per_token = (rewards as u128)
+            .checked_mul(1_000_000)
+            .ok_or(ErrorCode::MathOverflow)?
+            / self.total_supply as u128;
+        u64::try_from(per_token).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
 #[account]
 pub struct UserKeyBalance {
     pub owner: Pubkey,
@@ -109,9 +190,14 @@ pub struct UserKeyBalance {
     pub last_purchase_price: u64,
     pub total_spent: u64,
     pub total_earned: u64,
+    /// What the keys still held cost, at their average purchase price
+    pub cost_basis: u64,
     pub purchase_count: u32,
     pub sale_count: u32,
+    /// Start of the current holding; resets after a full exit
     pub first_purchase_at: i64,
+    /// First time this wallet ever held the creator's keys; never overwritten
+    pub original_first_purchase_at: i64,
     pub last_trade_at: i64,
     pub bump: u8,
 }
@@ -124,9 +210,11 @@ impl UserKeyBalance {
         8 + // last_purchase_price
         8 + // total_spent
         8 + // total_earned
+        8 + // cost_basis
         4 + // purchase_count
         4 + // sale_count
         8 + // first_purchase_at
+        8 + // original_first_purchase_at
         8 + // last_trade_at
         1; // bump
 
@@ -137,100 +225,66 @@ impl UserKeyBalance {
         self.last_purchase_price = 0;
         self.total_spent = 0;
         self.total_earned = 0;
+        self.cost_basis = 0;
         self.purchase_count = 0;
         self.sale_count = 0;
         self.first_purchase_at = 0;
+        self.original_first_purchase_at = 0;
         self.last_trade_at = Clock::get()?.unix_timestamp;
         self.bump = bump;
         Ok(())
     }
 
-    pub fn add_keys(&mut self, amount: u64, price_paid: u64) -> Result<()> {
+    pub fn add_keys(&mut self, amount: u64, price_paid: u64, now: i64) -> Result<()> {
         if self.balance == 0 {
-            self.first_purchase_at = Clock::get()?.unix_timestamp;
+            self.first_purchase_at = now;
+        }
+        if self.original_first_purchase_at == 0 {
+            self.original_first_purchase_at = now;
         }
         
         self.balance = self.balance.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
         self.last_purchase_price = price_paid.checked_div(amount).unwrap_or(0);
         self.total_spent = self.total_spent.checked_add(price_paid).ok_or(ErrorCode::MathOverflow)?;
+        self.cost_basis = self.cost_basis.checked_add(price_paid).ok_or(ErrorCode::MathOverflow)?;
         self.purchase_count = self.purchase_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
-        self.last_trade_at = Clock::get()?.unix_timestamp;
+        self.last_trade_at = now;
         Ok(())
     }
 
-    pub fn remove_keys(&mut self, amount: u64, price_received: u64) -> Result<()> {
+    pub fn remove_keys(&mut self, amount: u64, price_received: u64, now: i64) -> Result<()> {
         require!(self.balance >= amount, ErrorCode::InsufficientBalance);
-        
+
+        // Sold keys take their share of the cost basis with them
+        let cost_sold = (self.cost_basis as u128)
+            .checked_mul(amount as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.balance as u128)
+            .unwrap_or(0) as u64;
+
         self.balance = self.balance.checked_sub(amount).ok_or(ErrorCode::MathUnderflow)?;
+        self.cost_basis = self.cost_basis.checked_sub(cost_sold).ok_or(ErrorCode::MathUnderflow)?;
         self.total_earned = self.total_earned.checked_add(price_received).ok_or(ErrorCode::MathOverflow)?;
         self.sale_count = self.sale_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
-        self.last_trade_at = Clock::get()?.unix_timestamp;
+        self.last_trade_at = now;
         Ok(())
     }
 
     pub fn get_profit_loss(&self) -> i64 {
         (self.total_earned as i64) - (self.total_spent as i64)
     }
-}
-
-#[account]
-pub struct GlobalState {
-    pub authority: Pubkey,
-    pub trading_fee_bps: u16, // basis points (100 = 1%)
-    pub creator_fee_bps: u16, // basis points (100 = 1%)
-    pub protocol_fee_bps: u16, // basis points (100 = 1%)
-    pub total_keys_created: u64,
-    pub total_volume: u64,
-    pub total_fees_collected: u64,
-    pub is_paused: bool,
-    pub bump: u8,
-}
-
-impl GlobalState {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // authority
-        2 + // trading_fee_bps
-        2 + // creator_fee_bps
-        2 + // protocol_fee_bps
-        8 + // total_keys_created
-        8 + // total_volume
-        8 + // total_fees_collected
-        1 + // is_paused
-        1; // bump
-
-    pub fn initialize(&mut self, authority: Pubkey, bump: u8) -> Result<()> {
-        self.authority = authority;
-        self.trading_fee_bps = 500; // 5%
-        self.creator_fee_bps = 500; // 5%
-        self.protocol_fee_bps = 100; // 1%
-        self.total_keys_created = 0;
-        self.total_volume = 0;
-        self.total_fees_collected = 0;
-        self.is_paused = false;
-        self.bump = bump;
-        Ok(())
-    }
 
-    pub fn calculate_fees(&self, amount: u64) -> (u64, u64, u64) {
-        let trading_fee = amount.checked_mul(self.trading_fee_bps as u64).unwrap_or(0) / 10000;
-        let creator_fee = amount.checked_mul(self.creator_fee_bps as u64).unwrap_or(0) / 10000;
-        let protocol_fee = amount.checked_mul(self.protocol_fee_bps as u64).unwrap_or(0) / 10000;
-        (trading_fee, creator_fee, protocol_fee)
+    /// Proceeds from sales minus what the sold keys cost.
+    pub fn realized_pnl(&self) -> i64 {
+        self.get_profit_loss() + self.cost_basis as i64
     }
 
-    pub fn add_volume(&mut self, volume: u64) -> Result<()> {
-        self.total_volume = self.total_volume.checked_add(volume).ok_or(ErrorCode::MathOverflow)?;
-        Ok(())
-    }
-
-    pub fn add_fees(&mut self, fees: u64) -> Result<()> {
-        self.total_fees_collected = self.total_fees_collected.checked_add(fees).ok_or(ErrorCode::MathOverflow)?;
-        Ok(())
-    }
-
-    pub fn increment_keys_created(&mut self) -> Result<()> {
-        self.total_keys_created = self.total_keys_created.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
-        Ok(())
+    /// Value of the keys still held at `current_price` minus their cost basis.
+    pub fn unrealized_pnl(&self, current_price: u64) -> Result<i64> {
+        let value = current_price
+            .checked_mul(self.balance)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(value as i64 - self.cost_basis as i64)
     }
 }
 
@@ -250,5 +304,189 @@ pub enum ErrorCode {
     Unauthorized,
     #[msg("Keys not active")]
     KeysNotActive,
+    #[msg("Invalid fee")]
+    InvalidFee,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bonding_curve::CURVE_TYPE_QUADRATIC;
+
+    fn key_balance() -> UserKeyBalance {
+        UserKeyBalance {
+            owner: Pubkey::new_unique(),
+            key_owner: Pubkey::new_unique(),
+            balance: 0,
+            last_purchase_price: 0,
+            total_spent: 0,
+            total_earned: 0,
+            cost_basis: 0,
+            purchase_count: 0,
+            sale_count: 0,
+            first_purchase_at: 0,
+            original_first_purchase_at: 0,
+            last_trade_at: 0,
+            bump: 0,
+        }
+    }
+
+    fn user_keys() -> UserKeys {
+        UserKeys {
+            owner: Pubkey::new_unique(),
+            total_supply: 1,
+            max_supply: MAX_SUPPLY,
+            current_price: BASE_PRICE,
+            holders_count: 1,
+            trading_fee_collected: 0,
+            creator_fee_collected: 0,
+            is_active: true,
+            created_at: 0,
+            last_trade_at: 0,
+            snapshot_count: 0,
+            bump: 0,
+        }
+    }
+
+    fn empty_snapshot() -> HolderSnapshot {
+        HolderSnapshot {
+            key_owner: Pubkey::default(),
+            snapshot_id: 0,
+            total_supply: 0,
+            holders_count: 0,
+            taken_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_ignores_later_trades() {
+        let mut keys = user_keys();
+        let mut holding = key_balance();
+        holding.add_keys(4, 4_000, 10).unwrap();
+        keys.update_after_trade(4, true, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 10).unwrap();
+
+        let mut profile_supply = 5;
+        let mut snapshot = empty_snapshot();
+        let id = keys.next_snapshot_id().unwrap();
+        snapshot.capture(&keys, profile_supply, id, 100, 254);
+        assert_eq!(snapshot.key_owner, keys.owner);
+        assert_eq!((snapshot.total_supply, snapshot.holders_count), (5, 2));
+
+        let mut late_buyer = key_balance();
+        late_buyer.add_keys(5, 9_000, 110).unwrap();
+        keys.update_after_trade(5, true, late_buyer.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 110).unwrap();
+        profile_supply += 5;
+        holding.remove_keys(4, 4_000, 120).unwrap();
+        keys.update_after_trade(4, false, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 120).unwrap();
+        profile_supply -= 4;
+        assert_eq!((profile_supply, keys.holders_count), (6, 2));
+
+        assert_eq!((snapshot.total_supply, snapshot.holders_count), (5, 2));
+        assert_eq!(snapshot.taken_at, 100);
+        assert_eq!(snapshot.rewards_per_token(1_000).unwrap(), 200_000_000);
+    }
+
+    #[test]
+    fn test_snapshot_ids_are_sequential() {
+        let mut keys = user_keys();
+        assert_eq!(keys.next_snapshot_id().unwrap(), 0);
+        assert_eq!(keys.next_snapshot_id().unwrap(), 1);
+        assert_eq!(keys.snapshot_count, 2);
+
+        assert!(empty_snapshot().rewards_per_token(1_000).is_err());
+    }
+
+    #[test]
+    fn test_full_exit_decrements_holders() {
+        let mut keys = user_keys();
+        let mut holding = key_balance();
+
+        holding.add_keys(3, 3_000, 10).unwrap();
+        keys.update_after_trade(3, true, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 10).unwrap();
+        assert_eq!(keys.holders_count, 2);
+
+        holding.remove_keys(3, 3_000, 20).unwrap();
+        keys.update_after_trade(3, false, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 20).unwrap();
+        assert_eq!(keys.holders_count, 1);
+        assert_eq!(keys.total_supply, 1);
+    }
+
+    #[test]
+    fn test_partial_sell_and_repeat_buy_keep_holders() {
+        let mut keys = user_keys();
+        let mut holding = key_balance();
+
+        holding.add_keys(3, 3_000, 10).unwrap();
+        keys.update_after_trade(3, true, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 10).unwrap();
+        holding.add_keys(2, 2_500, 11).unwrap();
+        keys.update_after_trade(2, true, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 11).unwrap();
+        assert_eq!(keys.holders_count, 2);
+
+        holding.remove_keys(4, 4_000, 20).unwrap();
+        keys.update_after_trade(4, false, holding.balance, CURVE_TYPE_QUADRATIC, &BondingCurveParams::default(), 20).unwrap();
+        assert_eq!(keys.holders_count, 2);
+        assert_eq!(keys.total_supply, 2);
+    }
+
+    #[test]
+    fn test_rebuy_after_full_exit_keeps_original_purchase_time() {
+        let mut holding = key_balance();
+        holding.add_keys(2, 2_000, 10).unwrap();
+        assert_eq!(holding.first_purchase_at, 10);
+        assert_eq!(holding.original_first_purchase_at, 10);
+
+        holding.add_keys(1, 1_100, 15).unwrap();
+        holding.remove_keys(3, 3_300, 20).unwrap();
+        holding.add_keys(1, 1_000, 30).unwrap();
+
+        assert_eq!(holding.first_purchase_at, 30);
+        assert_eq!(holding.original_first_purchase_at, 10);
+    }
+
+    #[test]
+    fn test_buy_high_sell_low_realizes_a_loss() {
+        let mut holding = key_balance();
+        holding.add_keys(2, 2_000, 10).unwrap();
+        holding.remove_keys(1, 600, 20).unwrap();
+
+        assert_eq!(holding.realized_pnl(), -400);
+        assert_eq!(holding.cost_basis, 1_000);
+        assert_eq!(holding.unrealized_pnl(300).unwrap(), -700);
+
+        holding.remove_keys(1, 500, 30).unwrap();
+        assert_eq!(holding.realized_pnl(), -900);
+        assert_eq!(holding.realized_pnl(), holding.get_profit_loss());
+        assert_eq!(holding.unrealized_pnl(300).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_buy_low_sell_high_realizes_a_gain() {
+        let mut holding = key_balance();
+        holding.add_keys(1, 500, 10).unwrap();
+        holding.add_keys(1, 700, 20).unwrap();
+        holding.remove_keys(1, 1_000, 30).unwrap();
+
+        assert!(holding.realized_pnl() > 0);
+        assert_eq!(holding.realized_pnl(), 400);
+    }
+
+    #[test]
+    fn test_transfer_counts_new_holders_and_full_exits() {
+        let mut keys = user_keys();
+        keys.holders_count = 2;
+
+        // Partial transfer to someone who already holds: nobody joins or leaves
+        keys.record_transfer(1, 3, 2).unwrap();
+        assert_eq!(keys.holders_count, 2);
+
+        // First keys for the recipient
+        keys.record_transfer(1, 2, 1).unwrap();
+        assert_eq!(keys.holders_count, 3);
+
+        // Sender hands over everything they had left
+        keys.record_transfer(2, 0, 3).unwrap();
+        assert_eq!(keys.holders_count, 2);
+        assert_eq!(keys.total_supply, 1);
+    }
 }
-```
\ No newline at end of file
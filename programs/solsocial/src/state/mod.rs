@@ -1,21 +1,66 @@
-```rust
 use anchor_lang::prelude::*;
 
+use crate::errors::SolSocialError;
+use crate::utils::bonding_curve::calculate_trading_fee;
+
+/// The one profile account every instruction reads and writes, keyed by
+/// `[b"user_profile", authority]`.
 #[account]
+#[derive(Default)]
 pub struct UserProfile {
     pub authority: Pubkey,
     pub username: String,
     pub display_name: String,
     pub bio: String,
     pub avatar_url: String,
+    pub banner_image_url: String,
     pub token_mint: Pubkey,
+    pub token_vault: Pubkey,
     pub token_supply: u64,
+    pub max_supply: u64,
     pub token_price: u64,
+    pub holder_count: u32,
+    pub total_volume: u64,
+    pub total_keys_held: u64,
     pub followers_count: u64,
     pub following_count: u64,
     pub posts_count: u64,
+    pub pinned_count: u8,
     pub total_earned: u64,
+    pub total_spent: u64,
+    pub tips_given: u64,
+    pub tips_received: u64,
+    /// Share of each tip kept by the creator; the rest after the platform fee goes to holders
+    pub creator_tip_percentage: u8,
+    /// Share of distributed revenue kept by the creator; the rest after the platform fee goes to holders
+    pub revenue_share_percentage: u8,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    /// Seeds the next message PDA so same-second sends never collide
+    pub message_nonce: u64,
+    /// Seeds the next tip PDA, for the same reason
+    pub tip_nonce: u64,
+    pub chats_created: u64,
+    pub total_chats_joined: u64,
+    pub engagement_score: u64,
+    pub reputation_score: u64,
+    pub reputation_last_decayed: i64,
+    pub price_boost_day: i64,
+    pub price_boost_today: u64,
+    pub referral_count: u32,
+    pub is_verified: bool,
+    pub is_private: bool,
+    /// Keeps NSFW-labelled posts off this profile
+    pub blocks_nsfw: bool,
+    pub is_active: bool,
+    /// Set by the platform to halt trading in a sanctioned creator's token
+    pub token_frozen: bool,
+    pub encryption_pubkey: Option<[u8; 32]>,
+    pub social_links: Vec<SocialLink>,
     pub created_at: i64,
+    pub updated_at: i64,
+    pub last_post_timestamp: i64,
+    pub last_active: i64,
     pub bump: u8,
 }
 
@@ -24,60 +69,680 @@ impl UserProfile {
         32 + // authority
         4 + 32 + // username (max 32 chars)
         4 + 64 + // display_name (max 64 chars)
-        4 + 256 + // bio (max 256 chars)
-        4 + 128 + // avatar_url (max 128 chars)
+        4 + 280 + // bio (max 280 chars)
+        4 + 200 + // avatar_url (max 200 chars)
+        4 + 128 + // banner_image_url (max 128 chars)
         32 + // token_mint
+        32 + // token_vault
         8 + // token_supply
+        8 + // max_supply
         8 + // token_price
+        4 + // holder_count
+        8 + // total_volume
+        8 + // total_keys_held
         8 + // followers_count
         8 + // following_count
         8 + // posts_count
+        1 + // pinned_count
         8 + // total_earned
+        8 + // total_spent
+        8 + // tips_given
+        8 + // tips_received
+        1 + // creator_tip_percentage
+        1 + // revenue_share_percentage
+        8 + // messages_sent
+        8 + // messages_received
+        8 + // message_nonce
+        8 + // tip_nonce
+        8 + // chats_created
+        8 + // total_chats_joined
+        8 + // engagement_score
+        8 + // reputation_score
+        8 + // reputation_last_decayed
+        8 + // price_boost_day
+        8 + // price_boost_today
+        4 + // referral_count
+        1 + // is_verified
+        1 + // is_private
+        1 + // blocks_nsfw
+        1 + // is_active
+        1 + // token_frozen
+        1 + 32 + // encryption_pubkey (Option<[u8; 32]>)
+        4 + (UserProfile::MAX_SOCIAL_LINKS * SocialLink::LEN) + // social_links
         8 + // created_at
+        8 + // updated_at
+        8 + // last_post_timestamp
+        8 + // last_active
+        1; // bump
+
+    pub const INITIAL_REPUTATION: u64 = 100;
+    pub const DEFAULT_CREATOR_TIP_PERCENTAGE: u8 = 90;
+    pub const DEFAULT_REVENUE_SHARE_PERCENTAGE: u8 = 50;
+    pub const REPUTATION_FLOOR: u64 = 10;
+    pub const REPUTATION_DECAY_PER_DAY: u64 = 1;
+    const SECONDS_PER_DAY: i64 = 86_400;
+    /// Most that holder messages can raise a creator's price within one day
+    pub const MAX_DAILY_PRICE_BOOST: u64 = 50_000;
+    pub const MAX_SOCIAL_LINKS: usize = 5;
+
+    /// Raises `token_price` by up to `increase`, clamped so the total for the
+    /// current day never exceeds `MAX_DAILY_PRICE_BOOST`. Returns the amount applied.
+    pub fn apply_price_boost(&mut self, increase: u64, now: i64) -> Result<u64> {
+        let day = now / Self::SECONDS_PER_DAY;
+        if day != self.price_boost_day {
+            self.price_boost_day = day;
+            self.price_boost_today = 0;
+        }
+
+        let applied = increase.min(Self::MAX_DAILY_PRICE_BOOST.saturating_sub(self.price_boost_today));
+        self.price_boost_today += applied;
+        self.token_price = self.token_price
+            .checked_add(applied)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(applied)
+    }
+
+    /// Charges one point per whole inactive day since the later of `last_active`
+    /// and the previous decay. Call before `last_active` is refreshed.
+    pub fn decay_reputation(&mut self, now: i64) -> Result<u64> {
+        let since = self.last_active.max(self.reputation_last_decayed);
+        let inactive_days = now.saturating_sub(since) / Self::SECONDS_PER_DAY;
+        if inactive_days <= 0 {
+            return Ok(0);
+        }
+
+        let decay = (inactive_days as u64)
+            .checked_mul(Self::REPUTATION_DECAY_PER_DAY)
+            .ok_or(SolSocialError::MathOverflow)?;
+        // Decay never lifts a score that already sits below the floor
+        let floor = Self::REPUTATION_FLOOR.min(self.reputation_score);
+        let decayed = self.reputation_score.saturating_sub(decay).max(floor);
+        let applied = self.reputation_score - decayed;
+
+        self.reputation_score = decayed;
+        // Keep the partial day so it still counts toward the next decay
+        self.reputation_last_decayed = since + inactive_days * Self::SECONDS_PER_DAY;
+        Ok(applied)
+    }
+
+    pub fn update_reputation(&mut self, score_change: i64) -> Result<()> {
+        if score_change >= 0 {
+            self.reputation_score = self.reputation_score
+                .checked_add(score_change as u64)
+                .ok_or(SolSocialError::MathOverflow)?;
+        } else {
+            self.reputation_score = self.reputation_score
+                .checked_sub(score_change.unsigned_abs())
+                .ok_or(SolSocialError::MathUnderflow)?;
+        }
+        Ok(())
+    }
+
+    pub fn require_not_frozen(&self) -> Result<()> {
+        require!(!self.token_frozen, SolSocialError::TokenAccountFrozen);
+        Ok(())
+    }
+
+    /// Counts a follow on both profiles, or undoes one. Counters that have drifted
+    /// out of sync fail with `MathUnderflow` rather than wrapping or panicking.
+    pub fn record_follow(follower: &mut UserProfile, following: &mut UserProfile, follow: bool) -> Result<()> {
+        if follow {
+            follower.following_count = follower.following_count
+                .checked_add(1)
+                .ok_or(SolSocialError::MathOverflow)?;
+            following.followers_count = following.followers_count
+                .checked_add(1)
+                .ok_or(SolSocialError::MathOverflow)?;
+        } else {
+            follower.following_count = follower.following_count
+                .checked_sub(1)
+                .ok_or(SolSocialError::MathUnderflow)?;
+            following.followers_count = following.followers_count
+                .checked_sub(1)
+                .ok_or(SolSocialError::MathUnderflow)?;
+        }
+        Ok(())
+    }
+
+    /// Changes the creator's revenue share; together with the pool's platform fee it
+    /// may not exceed 100%.
+    pub fn set_revenue_share(&mut self, percentage: u8, platform_fee_percentage: u8) -> Result<()> {
+        crate::utils::revenue_share::validate_revenue_distribution_params(
+            percentage,
+            platform_fee_percentage,
+        )?;
+        self.revenue_share_percentage = percentage;
+        Ok(())
+    }
+
+    /// Publishes an x25519 public key so others can send this user encrypted DMs.
+    pub fn register_encryption_key(&mut self, key: [u8; 32]) -> Result<()> {
+        require!(key != [0u8; 32], SolSocialError::InvalidEncryptionKey);
+        self.encryption_pubkey = Some(key);
+        Ok(())
+    }
+
+    pub fn require_can_receive(&self, is_encrypted: bool) -> Result<()> {
+        require!(
+            !is_encrypted || self.encryption_pubkey.is_some(),
+            SolSocialError::RecipientEncryptionKeyMissing
+        );
+        Ok(())
+    }
+
+    /// Returns the nonce the current message PDA was seeded with and moves to the next.
+    pub fn advance_message_nonce(&mut self) -> Result<u64> {
+        let used = self.message_nonce;
+        self.message_nonce = used.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
+        Ok(used)
+    }
+
+    pub fn advance_tip_nonce(&mut self) -> Result<u64> {
+        let used = self.tip_nonce;
+        self.tip_nonce = used.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
+        Ok(used)
+    }
+
+    /// Points the profile at a new username. The caller swaps the registry PDAs.
+    pub fn rename(&mut self, new_username: String, now: i64) -> Result<()> {
+        validate_username(&new_username)?;
+        require!(new_username != self.username, SolSocialError::UsernameAlreadyTaken);
+
+        self.username = new_username;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Adds a link for `platform`, which may only appear once per profile.
+    pub fn add_social_link(&mut self, platform: String, url: String) -> Result<()> {
+        require!(
+            !platform.is_empty() && platform.len() <= SocialLink::MAX_PLATFORM_LENGTH,
+            SolSocialError::InvalidSocialPlatformLength
+        );
+        require!(
+            !url.is_empty() && url.len() <= SocialLink::MAX_URL_LENGTH,
+            SolSocialError::InvalidSocialUrlLength
+        );
+        require!(
+            !self.social_links.iter().any(|link| link.platform == platform),
+            SolSocialError::SocialLinkAlreadyExists
+        );
+        require!(
+            self.social_links.len() < Self::MAX_SOCIAL_LINKS,
+            SolSocialError::MaxSocialLinksExceeded
+        );
+
+        self.social_links.push(SocialLink { platform, url });
+        Ok(())
+    }
+
+    pub fn remove_social_link(&mut self, platform: &str) -> Result<()> {
+        let index = self.social_links
+            .iter()
+            .position(|link| link.platform == platform)
+            .ok_or(SolSocialError::SocialLinkNotFound)?;
+        self.social_links.remove(index);
+        Ok(())
+    }
+}
+
+/// Schemes clients know how to fetch media from
+pub const ALLOWED_URL_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+
+/// Checks an avatar or banner URL. An empty string means no image is set.
+pub fn validate_url(url: &str) -> Result<()> {
+    require!(
+        url.is_empty() || ALLOWED_URL_SCHEMES.iter().any(|scheme| url.starts_with(scheme)),
+        SolSocialError::InvalidMediaUrl
+    );
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SocialLink {
+    pub platform: String,
+    pub url: String,
+}
+
+impl SocialLink {
+    pub const MAX_PLATFORM_LENGTH: usize = 20;
+    pub const MAX_URL_LENGTH: usize = 200;
+    pub const LEN: usize = 4 + Self::MAX_PLATFORM_LENGTH + // platform
+        4 + Self::MAX_URL_LENGTH; // url
+}
+
+#[account]
+#[derive(Default)]
+pub struct SocialStats {
+    pub user: Pubkey,
+    pub followers_count: u64,
+    pub following_count: u64,
+    pub posts_count: u64,
+    pub likes_received: u64,
+    pub likes_given: u64,
+    pub comments_count: u64,
+    pub shares_count: u64,
+    pub tips_received: u64,
+    pub tips_given: u64,
+    pub total_earnings: u64,
+    pub reputation_score: u64,
+    pub engagement_rate: u64,
+    pub last_active: i64,
+    pub bump: u8,
+}
+
+impl SocialStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        8 + // followers_count
+        8 + // following_count
+        8 + // posts_count
+        8 + // likes_received
+        8 + // likes_given
+        8 + // comments_count
+        8 + // shares_count
+        8 + // tips_received
+        8 + // tips_given
+        8 + // total_earnings
+        8 + // reputation_score
+        8 + // engagement_rate
+        8 + // last_active
         1; // bump
+
+    pub const ENGAGEMENT_SCALE: u128 = 10_000;
+
+    /// Interactions per post per follower, scaled by 10000 so ranking stays in integers.
+    pub fn engagement_rate_scaled(interactions: u64, posts: u64, followers: u64) -> u64 {
+        let audience = (posts as u128) * (followers as u128);
+        if audience == 0 {
+            return 0;
+        }
+        let rate = (interactions as u128) * Self::ENGAGEMENT_SCALE / audience;
+        u64::try_from(rate).unwrap_or(u64::MAX)
+    }
+
+    pub fn recompute_engagement(&mut self) -> Result<()> {
+        let interactions = self.likes_received
+            .checked_add(self.comments_count)
+            .and_then(|total| total.checked_add(self.shares_count))
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.engagement_rate =
+            Self::engagement_rate_scaled(interactions, self.posts_count, self.followers_count);
+        Ok(())
+    }
+
+    /// Seeds a newly created stats account from the counters the profile already
+    /// tracks. Returns false and leaves the account alone if it was already set up.
+    pub fn backfill(&mut self, profile: &UserProfile, bump: u8) -> bool {
+        if self.user != Pubkey::default() {
+            return false;
+        }
+
+        self.user = profile.authority;
+        self.followers_count = profile.followers_count;
+        self.following_count = profile.following_count;
+        self.posts_count = profile.posts_count;
+        self.total_earnings = profile.total_earned;
+        self.reputation_score = profile.reputation_score;
+        self.last_active = profile.last_active;
+        self.bump = bump;
+        true
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct Post {
     pub id: u64,
     pub author: Pubkey,
     pub content: String,
     pub image_url: String,
+    /// Attachments, each checked by `validate_media_urls`
+    pub media_urls: Vec<String>,
     pub likes_count: u64,
     pub comments_count: u64,
     pub tips_count: u64,
     pub total_tips_amount: u64,
+    /// Only the total is public; who bookmarked lives in each reader's interaction PDA
+    pub bookmarks_count: u64,
+    /// Shares plus reposts and quote reposts
+    pub share_count: u64,
     pub created_at: i64,
     pub updated_at: i64,
+    pub edit_count: u8,
     pub is_premium: bool,
     pub premium_price: u64,
+    pub premium_key_ciphertext: Vec<u8>,
+    /// Set by a moderator; hidden posts stay on-chain for audit but take no new interactions
+    pub is_hidden: bool,
+    /// Like `is_hidden`, but final
+    pub is_removed: bool,
+    /// Author-applied content rating; clients filter on it, profiles can refuse it
+    pub is_nsfw: bool,
+    /// The post this one reposts; `content` holds the quote, empty for a plain repost
+    pub repost_of: Option<Pubkey>,
     pub bump: u8,
 }
 
 impl Post {
-    pub const LEN: usize = 8 + // discriminator
+    /// Everything except the bytes of `content` and `media_urls`; size an account
+    /// with `space_for`.
+    pub const BASE_LEN: usize = 8 + // discriminator
         8 + // id
         32 + // author
-        4 + 512 + // content (max 512 chars)
+        4 + // content length prefix
         4 + 128 + // image_url (max 128 chars)
         8 + // likes_count
         8 + // comments_count
         8 + // tips_count
         8 + // total_tips_amount
+        8 + // bookmarks_count
+        8 + // share_count
         8 + // created_at
         8 + // updated_at
+        1 + // edit_count
         1 + // is_premium
         8 + // premium_price
+        4 + 64 + // premium_key_ciphertext (max 64 bytes)
+        1 + // is_hidden
+        1 + // is_removed
+        1 + // is_nsfw
+        1 + 32 + // repost_of
+        1; // bump
+
+    pub const MAX_MEDIA_URLS: usize = 10;
+    pub const MAX_MEDIA_URL_LENGTH: usize = 200;
+    pub const MAX_PREMIUM_KEY_LENGTH: usize = 64;
+    pub const PREMIUM_EDIT_WINDOW: i64 = 24 * 60 * 60; // 24 hours
+
+    pub fn moderate(&mut self, action: ModerationAction) -> Result<()> {
+        require!(!self.is_removed, SolSocialError::ContentModerationFailed);
+        self.is_hidden = true;
+        if action == ModerationAction::Remove {
+            self.is_removed = true;
+        }
+        Ok(())
+    }
+
+    /// An NSFW post may only go where NSFW is not blocked.
+    pub fn check_nsfw_allowed(is_nsfw: bool, blocks_nsfw: bool) -> Result<()> {
+        require!(!(is_nsfw && blocks_nsfw), SolSocialError::NSFWContentNotAllowed);
+        Ok(())
+    }
+
+    /// Likes, shares, comments, tips, edits and deletion are refused once a moderator
+    /// has acted on the post.
+    pub fn require_not_moderated(&self) -> Result<()> {
+        require!(!self.is_hidden && !self.is_removed, SolSocialError::ContentModerationFailed);
+        Ok(())
+    }
+
+    /// Reposts point at live originals only, so a chain never grows past one hop.
+    pub fn validate_repost_target(&self) -> Result<()> {
+        self.require_not_moderated()?;
+        require!(self.repost_of.is_none(), SolSocialError::RepostDepthExceeded);
+        Ok(())
+    }
+
+    /// A repost is a post of its own; `comment` makes it a quote repost. The
+    /// comment's length is checked against `GlobalState` by the caller.
+    pub fn initialize_repost(
+        &mut self,
+        author: Pubkey,
+        original: Pubkey,
+        comment: Option<String>,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.author = author;
+        self.content = comment.unwrap_or_default();
+        self.repost_of = Some(original);
+        self.created_at = now;
+        self.updated_at = now;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn share(&mut self) -> Result<()> {
+        self.share_count = self.share_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Borsh size of `media_urls`: the vec length prefix plus each string's prefix and bytes.
+    pub fn media_space(media_urls: &[String]) -> usize {
+        4 + media_urls.iter().map(|url| 4 + url.len()).sum::<usize>()
+    }
+
+    /// Account size for a post created with `content_len` bytes of content and
+    /// `media_urls`; only meaningful once the URLs have passed `validate_media_urls`.
+    pub fn space_for(content_len: usize, media_urls: &[String]) -> usize {
+        Self::BASE_LEN + content_len + Self::media_space(media_urls)
+    }
+
+    /// Every attached URL must be short enough to store and point at https or IPFS.
+    pub fn validate_media_urls(media_urls: &[String]) -> Result<()> {
+        require!(media_urls.len() <= Self::MAX_MEDIA_URLS, SolSocialError::TooManyMediaFiles);
+        for url in media_urls {
+            require!(url.len() <= Self::MAX_MEDIA_URL_LENGTH, SolSocialError::MediaUrlTooLong);
+            require!(
+                ALLOWED_URL_SCHEMES.iter().any(|scheme| url.starts_with(scheme)),
+                SolSocialError::InvalidFileFormat
+            );
+        }
+        Ok(())
+    }
+
+    pub fn add_bookmark(&mut self) -> Result<()> {
+        self.bookmarks_count = self.bookmarks_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_bookmark(&mut self) -> Result<()> {
+        self.bookmarks_count = self.bookmarks_count
+            .checked_sub(1)
+            .ok_or(SolSocialError::MathUnderflow)?;
+        Ok(())
+    }
+
+    /// The upper length bound is `GlobalState::check_post_length`, shared with new posts.
+    pub fn validate_edit(&self, new_content: &str, now: i64) -> Result<()> {
+        require!(!new_content.is_empty(), SolSocialError::InvalidPostContentLength);
+        // Paying readers bought a specific post; only allow fixes shortly after publishing
+        if self.is_premium {
+            require!(
+                now - self.created_at <= Self::PREMIUM_EDIT_WINDOW,
+                SolSocialError::EditWindowExpired
+            );
+        }
+        require!(self.edit_count < u8::MAX, SolSocialError::MathOverflow);
+        Ok(())
+    }
+
+    /// Premium content is stored encrypted; the ciphertext holds the content key
+    /// wrapped for release to buyers.
+    pub fn configure_premium(&mut self, premium_price: u64, premium_key_ciphertext: Vec<u8>) -> Result<()> {
+        require!(premium_price > 0, SolSocialError::InvalidTipAmount);
+        require!(
+            !premium_key_ciphertext.is_empty()
+                && premium_key_ciphertext.len() <= Self::MAX_PREMIUM_KEY_LENGTH,
+            SolSocialError::InvalidEncryptionKey
+        );
+
+        self.is_premium = true;
+        self.premium_price = premium_price;
+        self.premium_key_ciphertext = premium_key_ciphertext;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PostInteraction {
+    pub user: Pubkey,
+    pub post: Pubkey,
+    pub interactions: u8, // bitmask of InteractionType flags
+    pub timestamp: i64,
+    pub reward_charged: u64,
+    pub bump: u8,
+}
+
+impl PostInteraction {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        32 + // post
+        1 + // interactions
+        8 + // timestamp
+        8 + // reward_charged
+        1; // bump
+
+    pub fn initialize(
+        &mut self,
+        user: Pubkey,
+        post: Pubkey,
+        timestamp: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.user = user;
+        self.post = post;
+        self.interactions = 0;
+        self.timestamp = timestamp;
+        self.reward_charged = 0;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn has(&self, interaction_type: InteractionType) -> bool {
+        self.interactions & interaction_type.flag() != 0
+    }
+
+    /// Each kind is its own bit, so a like, share and bookmark toggle independently.
+    pub fn insert(&mut self, interaction_type: InteractionType, timestamp: i64) {
+        self.interactions |= interaction_type.flag();
+        self.timestamp = timestamp;
+    }
+
+    pub fn remove(&mut self, interaction_type: InteractionType, timestamp: i64) {
+        self.interactions &= !interaction_type.flag();
+        self.timestamp = timestamp;
+    }
+
+    pub fn record_reward(&mut self, amount: u64) {
+        self.reward_charged = amount;
+    }
+
+    /// Returns the reward this interaction still holds in the post escrow and clears it.
+    /// Unlike refunds it to the user; release pays it to the creator. Either way it is
+    /// taken once, so one user can never draw down another user's deposit.
+    pub fn take_unreleased_reward(&mut self) -> u64 {
+        std::mem::take(&mut self.reward_charged)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionType {
+    Like,
+    Share,
+    Tip,
+    Bookmark,
+}
+
+impl InteractionType {
+    pub fn flag(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[account]
+pub struct TopTipper {
+    pub post: Pubkey,
+    pub tipper: Pubkey,
+    pub total_tipped: u64,
+    pub tip_count: u64,
+    pub last_tipped_at: i64,
+    pub bump: u8,
+}
+
+impl TopTipper {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // post
+        32 + // tipper
+        8 + // total_tipped
+        8 + // tip_count
+        8 + // last_tipped_at
+        1; // bump
+
+    pub fn record_tip(&mut self, amount: u64, timestamp: i64) -> Result<()> {
+        self.total_tipped = self.total_tipped
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.tip_count = self.tip_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.last_tipped_at = timestamp;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct RecentPostHash {
+    pub author: Pubkey,
+    pub content_hash: [u8; 32],
+    pub last_posted_at: i64,
+    pub bump: u8,
+}
+
+impl RecentPostHash {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // author
+        32 + // content_hash
+        8 + // last_posted_at
+        1; // bump
+
+    /// Identical content from the same author is rejected inside this window
+    pub const DUPLICATE_WINDOW: i64 = 10 * 60; // 10 minutes
+
+    pub fn record_post(&mut self, author: Pubkey, content_hash: [u8; 32], timestamp: i64) -> Result<()> {
+        require!(
+            self.last_posted_at == 0 || timestamp - self.last_posted_at >= Self::DUPLICATE_WINDOW,
+            SolSocialError::SpamDetectionTriggered
+        );
+
+        self.author = author;
+        self.content_hash = content_hash;
+        self.last_posted_at = timestamp;
+        Ok(())
+    }
+}
+
+/// One wallet's like on a comment; unliking closes it.
+#[account]
+pub struct CommentInteraction {
+    pub user: Pubkey,
+    pub comment: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl CommentInteraction {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        32 + // comment
+        8 + // timestamp
         1; // bump
 }
 
 #[account]
+#[derive(Default)]
 pub struct Comment {
     pub id: u64,
     pub post_id: u64,
     pub author: Pubkey,
     pub content: String,
     pub likes_count: u64,
+    pub reply_to: Option<Pubkey>,
     pub created_at: i64,
     pub bump: u8,
 }
@@ -89,8 +754,14 @@ impl Comment {
         32 + // author
         4 + 256 + // content (max 256 chars)
         8 + // likes_count
+        1 + 32 + // reply_to (Option<Pubkey>)
         8 + // created_at
         1; // bump
+
+    /// Threads are one level deep: only top-level comments accept replies.
+    pub fn accepts_replies(&self) -> bool {
+        self.reply_to.is_none()
+    }
 }
 
 #[account]
@@ -195,18 +866,59 @@ impl PremiumAccess {
         8 + // expires_at
         8 + // created_at
         1; // bump
+
+    pub const ACCESS_DURATION: i64 = 30 * 24 * 60 * 60; // 30 days
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at > now
+    }
+
+    /// Grants access if it has lapsed. Returns whether the caller owes payment;
+    /// unlocking again while access is active is a no-op.
+    pub fn unlock(&mut self, now: i64) -> Result<bool> {
+        if self.is_active(now) {
+            return Ok(false);
+        }
+
+        self.expires_at = now
+            .checked_add(Self::ACCESS_DURATION)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(true)
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct GlobalState {
     pub authority: Pubkey,
     pub total_users: u64,
     pub total_posts: u64,
     pub total_tips: u64,
     pub total_volume: u64,
-    pub platform_fee_bps: u16,
-    pub creator_fee_bps: u16,
-    pub paused: bool,
+    pub trading_fee_bps: u16, // basis points (100 = 1%)
+    pub creator_fee_bps: u16, // basis points (100 = 1%)
+    pub protocol_fee_bps: u16, // basis points (100 = 1%)
+    pub total_keys_created: u64,
+    /// The protocol's unwithdrawn share of key trading fees
+    pub total_fees_collected: u64,
+    /// Halts key trading only
+    pub is_paused: bool,
+    pub pending_authority: Option<Pubkey>,
+    pub min_post_interval_secs: i64,
+    pub oracle_authority: Pubkey,
+    /// Lets holder messages nudge a creator's token price
+    pub message_price_boost_enabled: bool,
+    /// Smallest tip accepted; zero still rejects empty tips
+    pub min_tip: u64,
+    /// Largest tip accepted; zero falls back to the program's built-in cap
+    pub max_tip: u64,
+    /// Platform cut of every post tip, sent to `treasury`
+    pub platform_tip_fee_bps: u16,
+    pub treasury: Pubkey,
+    /// Longest post body accepted; zero falls back to `DEFAULT_MAX_POST_LENGTH`
+    pub max_post_length: u16,
+    /// Halts every mutating instruction, not just trading
+    pub emergency_paused: bool,
     pub bump: u8,
 }
 
@@ -217,10 +929,201 @@ impl GlobalState {
         8 + // total_posts
         8 + // total_tips
         8 + // total_volume
-        2 + // platform_fee_bps
+        2 + // trading_fee_bps
         2 + // creator_fee_bps
-        1 + // paused
+        2 + // protocol_fee_bps
+        8 + // total_keys_created
+        8 + // total_fees_collected
+        1 + // is_paused
+        1 + 32 + // pending_authority
+        8 + // min_post_interval_secs
+        32 + // oracle_authority
+        1 + // message_price_boost_enabled
+        8 + // min_tip
+        8 + // max_tip
+        2 + // platform_tip_fee_bps
+        32 + // treasury
+        2 + // max_post_length
+        1 + // emergency_paused
         1; // bump
+
+    pub const MAX_FEE_BPS: u16 = 1000; // 10%
+    pub const BPS_DENOMINATOR: u16 = 10000;
+    pub const MAX_PLATFORM_TIP_FEE_BPS: u16 = 1_000; // 10%
+    /// The most permissive of the limits posts were created under before this was configurable
+    pub const DEFAULT_MAX_POST_LENGTH: u16 = 2_000;
+
+    pub fn initialize(&mut self, authority: Pubkey, bump: u8) -> Result<()> {
+        self.authority = authority;
+        self.trading_fee_bps = 500; // 5%
+        self.creator_fee_bps = 500; // 5%
+        self.protocol_fee_bps = 100; // 1%
+        self.total_keys_created = 0;
+        self.total_volume = 0;
+        self.total_fees_collected = 0;
+        self.is_paused = false;
+        self.pending_authority = None;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn update_fees(
+        &mut self,
+        signer: Pubkey,
+        trading_fee_bps: u16,
+        creator_fee_bps: u16,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        require!(
+            trading_fee_bps <= Self::MAX_FEE_BPS
+                && creator_fee_bps <= Self::MAX_FEE_BPS
+                && protocol_fee_bps <= Self::MAX_FEE_BPS,
+            SolSocialError::InvalidProtocolFeePercentage
+        );
+        // Each fee is capped, so the sum can't overflow u16 here
+        require!(
+            trading_fee_bps + creator_fee_bps + protocol_fee_bps < Self::BPS_DENOMINATOR,
+            SolSocialError::InvalidProtocolFeePercentage
+        );
+
+        self.trading_fee_bps = trading_fee_bps;
+        self.creator_fee_bps = creator_fee_bps;
+        self.protocol_fee_bps = protocol_fee_bps;
+        Ok(())
+    }
+
+    /// First step of an authority handoff; the new key must accept before it takes effect.
+    pub fn propose_authority(&mut self, signer: Pubkey, new_authority: Pubkey) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        self.pending_authority = Some(new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(&mut self, signer: Pubkey) -> Result<()> {
+        require!(self.pending_authority == Some(signer), SolSocialError::UnauthorizedAccess);
+        self.authority = signer;
+        self.pending_authority = None;
+        Ok(())
+    }
+
+    /// Pauses key trading; see `set_emergency_pause` for halting everything.
+    pub fn set_paused(&mut self, signer: Pubkey, paused: bool) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        self.is_paused = paused;
+        Ok(())
+    }
+
+    pub fn calculate_fees(&self, amount: u64) -> (u64, u64, u64) {
+        let trading_fee = amount.checked_mul(self.trading_fee_bps as u64).unwrap_or(0) / 10000;
+        let creator_fee = amount.checked_mul(self.creator_fee_bps as u64).unwrap_or(0) / 10000;
+        let protocol_fee = amount.checked_mul(self.protocol_fee_bps as u64).unwrap_or(0) / 10000;
+        (trading_fee, creator_fee, protocol_fee)
+    }
+
+    /// (protocol fee, subject fee) on a key trade of `price`, at the rates set
+    /// through `update_fees`. The protocol fee drops with the trader's volume tier;
+    /// the subject fee is flat.
+    pub fn key_trade_fees(&self, price: u64, price_impact: u64, trader_volume: u64) -> Result<(u64, u64)> {
+        let protocol_fee =
+            calculate_trading_fee(price, price_impact, trader_volume, self.protocol_fee_bps as u64)?;
+        let subject_fee = price
+            .checked_mul(self.creator_fee_bps as u64)
+            .ok_or(SolSocialError::MathOverflow)?
+            / Self::BPS_DENOMINATOR as u64;
+        Ok((protocol_fee, subject_fee))
+    }
+
+    /// Platform fee on a creator token trade of `price`, at `trading_fee_bps`
+    /// scaled down by the trader's volume tier.
+    pub fn token_trade_fee(&self, price: u64, price_impact: u64, trader_volume: u64) -> Result<u64> {
+        calculate_trading_fee(price, price_impact, trader_volume, self.trading_fee_bps as u64)
+    }
+
+    /// `total_fees_collected` is the protocol's unwithdrawn balance, so a
+    /// withdrawal draws it down and can never exceed it.
+    pub fn withdraw_fees(&mut self, signer: Pubkey, amount: u64) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        require!(amount > 0, SolSocialError::InvalidAmount);
+        self.total_fees_collected = self.total_fees_collected
+            .checked_sub(amount)
+            .ok_or(SolSocialError::InsufficientVaultBalance)?;
+        Ok(())
+    }
+
+    pub fn add_fees(&mut self, fees: u64) -> Result<()> {
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(fees)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn increment_keys_created(&mut self) -> Result<()> {
+        self.total_keys_created = self.total_keys_created
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn max_post_length(&self) -> usize {
+        if self.max_post_length == 0 {
+            Self::DEFAULT_MAX_POST_LENGTH as usize
+        } else {
+            self.max_post_length as usize
+        }
+    }
+
+    pub fn check_post_length(&self, content: &str) -> Result<()> {
+        require!(content.len() <= self.max_post_length(), SolSocialError::ContentTooLong);
+        Ok(())
+    }
+
+    pub fn set_emergency_pause(&mut self, signer: Pubkey, paused: bool) -> Result<()> {
+        require_keys_eq!(signer, self.authority, SolSocialError::UnauthorizedAccess);
+        self.emergency_paused = paused;
+        Ok(())
+    }
+
+    pub fn check_tip_amount(&self, amount: u64, default_max_tip: u64) -> Result<()> {
+        let max_tip = if self.max_tip == 0 { default_max_tip } else { self.max_tip };
+        require!(amount > 0 && amount >= self.min_tip, SolSocialError::InvalidTipAmount);
+        require!(amount <= max_tip, SolSocialError::TipAmountTooHigh);
+        Ok(())
+    }
+
+    pub fn record_user(&mut self) -> Result<()> {
+        self.total_users = self.total_users
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_post(&mut self) -> Result<()> {
+        self.total_posts = self.total_posts
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Counts `count` tips worth `amount` in total towards platform-wide tip volume.
+    pub fn record_tips(&mut self, count: u64, amount: u64) -> Result<()> {
+        self.total_tips = self.total_tips
+            .checked_add(count)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn check_post_interval(&self, last_post_timestamp: i64, now: i64) -> Result<()> {
+        // A zero timestamp means the author has never posted
+        require!(
+            last_post_timestamp == 0 || now - last_post_timestamp >= self.min_post_interval_secs,
+            SolSocialError::RateLimitExceeded
+        );
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -230,12 +1133,18 @@ pub enum TradeType {
 }
 
 #[account]
+#[derive(Default)]
 pub struct TokenHolder {
     pub holder: Pubkey,
     pub profile_owner: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
     pub average_price: u64,
+    /// Sale proceeds minus the average cost of the tokens sold, summed over all sells
+    pub realized_pnl: i64,
+    pub last_claimed_rewards_per_token: u64,
+    /// Rewards settled at an earlier balance and not yet paid out
+    pub pending_rewards: u64,
     pub created_at: i64,
     pub updated_at: i64,
     pub bump: u8,
@@ -248,85 +1157,2318 @@ impl TokenHolder {
         32 + // token_mint
         8 + // amount
         8 + // average_price
+        8 + // realized_pnl
+        8 + // last_claimed_rewards_per_token
+        8 + // pending_rewards
         8 + // created_at
         8 + // updated_at
         1; // bump
+
+    /// Starts the checkpoint at the current accumulator, so a new holder only earns
+    /// from distributions made after they first hold.
+    pub fn initialize(
+        &mut self,
+        holder: Pubkey,
+        profile_owner: Pubkey,
+        token_mint: Pubkey,
+        rewards_per_token: u64,
+        now: i64,
+        bump: u8,
+    ) {
+        self.holder = holder;
+        self.profile_owner = profile_owner;
+        self.token_mint = token_mint;
+        self.amount = 0;
+        self.average_price = 0;
+        self.realized_pnl = 0;
+        self.last_claimed_rewards_per_token = rewards_per_token;
+        self.pending_rewards = 0;
+        self.created_at = now;
+        self.updated_at = now;
+        self.bump = bump;
+    }
+
+    /// Initializes a holder record created on the holder's first buy; an existing
+    /// record keeps its balance and checkpoint.
+    pub fn ensure_initialized(
+        &mut self,
+        holder: Pubkey,
+        profile_owner: Pubkey,
+        token_mint: Pubkey,
+        rewards_per_token: u64,
+        now: i64,
+        bump: u8,
+    ) {
+        if self.holder != Pubkey::default() {
+            return;
+        }
+        self.initialize(holder, profile_owner, token_mint, rewards_per_token, now, bump);
+    }
+
+    /// Banks what the current balance earned since the checkpoint and moves the
+    /// checkpoint up. Runs before every balance change so the new balance is never
+    /// paid for distributions it did not hold through.
+    pub fn settle_rewards(&mut self, rewards_per_token: u64, total_supply: u64) -> Result<()> {
+        if self.amount == 0 {
+            self.last_claimed_rewards_per_token = rewards_per_token;
+            return Ok(());
+        }
+        let earned = crate::utils::revenue_share::settle_holder_reward(
+            rewards_per_token,
+            self.amount,
+            total_supply,
+            &mut self.last_claimed_rewards_per_token,
+        )?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(earned)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Settles, then hands back everything owed and clears it.
+    pub fn take_rewards(&mut self, rewards_per_token: u64, total_supply: u64) -> Result<u64> {
+        self.settle_rewards(rewards_per_token, total_supply)?;
+        Ok(std::mem::take(&mut self.pending_rewards))
+    }
+
+    /// Folds `buy_cost` for `buy_amount` tokens into the weighted average price.
+    /// Rewards are settled at the old balance first.
+    pub fn record_buy(
+        &mut self,
+        buy_amount: u64,
+        buy_cost: u64,
+        rewards_per_token: u64,
+        total_supply: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.settle_rewards(rewards_per_token, total_supply)?;
+        let new_amount = self.amount
+            .checked_add(buy_amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        require!(new_amount > 0, SolSocialError::InvalidAmount);
+
+        let total_cost = (self.average_price as u128)
+            .checked_mul(self.amount as u128)
+            .and_then(|held| held.checked_add(buy_cost as u128))
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.average_price = u64::try_from(total_cost / new_amount as u128)
+            .map_err(|_| SolSocialError::MathOverflow)?;
+        self.amount = new_amount;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Sells leave the average untouched so realized PnL can be measured against it.
+    /// Rewards are settled at the old balance first.
+    pub fn record_sell(
+        &mut self,
+        sell_amount: u64,
+        proceeds: u64,
+        rewards_per_token: u64,
+        total_supply: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.settle_rewards(rewards_per_token, total_supply)?;
+        let cost = self.cost_of(sell_amount)?;
+        self.amount = self.amount
+            .checked_sub(sell_amount)
+            .ok_or(SolSocialError::InsufficientTokenBalance)?;
+        self.realized_pnl = i64::try_from(proceeds as i128 - cost as i128)
+            .ok()
+            .and_then(|pnl| self.realized_pnl.checked_add(pnl))
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// What the current balance would gain or lose if it were valued at `current_price`.
+    pub fn unrealized_pnl(&self, current_price: u64) -> Result<i64> {
+        let value = (current_price as u128)
+            .checked_mul(self.amount as u128)
+            .ok_or(SolSocialError::MathOverflow)?;
+        let cost = self.cost_of(self.amount)?;
+        i64::try_from(value as i128 - cost as i128).map_err(|_| SolSocialError::MathOverflow.into())
+    }
+
+    fn cost_of(&self, amount: u64) -> Result<u128> {
+        (self.average_price as u128)
+            .checked_mul(amount as u128)
+            .ok_or(SolSocialError::MathOverflow.into())
+    }
 }
 
+/// A wallet's keys in one subject, as bought and sold along the subject's curve.
 #[account]
-pub struct Notification {
-    pub id: u64,
-    pub recipient: Pubkey,
-    pub sender: Pubkey,
-    pub notification_type: NotificationType,
-    pub content: String,
-    pub post_id: Option<u64>,
-    pub read: bool,
+#[derive(Default)]
+pub struct KeyHolding {
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub amount: u64,
+    pub purchase_count: u64,
+    pub sale_count: u64,
+    pub total_spent: u64,
+    pub total_earned: u64,
+    /// Start of the current holding period; resets on a buy after a full exit
+    pub first_purchase_at: i64,
+    /// First time this wallet ever held the subject's keys; never overwritten
+    pub original_first_purchase_at: i64,
+    pub last_trade_timestamp: i64,
+    pub bump: u8,
+}
+
+impl KeyHolding {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // subject
+        8 + // amount
+        8 + // purchase_count
+        8 + // sale_count
+        8 + // total_spent
+        8 + // total_earned
+        8 + // first_purchase_at
+        8 + // original_first_purchase_at
+        8 + // last_trade_timestamp
+        1; // bump
+
+    /// Fills in the identity of a holding created on the first buy; an existing
+    /// holding keeps its balance and history.
+    pub fn ensure_initialized(&mut self, owner: Pubkey, subject: Pubkey, bump: u8) {
+        if self.owner != Pubkey::default() {
+            return;
+        }
+        self.owner = owner;
+        self.subject = subject;
+        self.bump = bump;
+    }
+
+    /// Books a curve purchase; buying back in after a full exit starts a new
+    /// holding period but keeps the original cohort time.
+    pub fn record_buy(&mut self, amount: u64, cost: u64, now: i64) -> Result<()> {
+        self.mark_acquired(now);
+        self.amount = self.amount
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.purchase_count = self.purchase_count
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.total_spent = self.total_spent
+            .checked_add(cost)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.last_trade_timestamp = now;
+        Ok(())
+    }
+
+    fn mark_acquired(&mut self, now: i64) {
+        if self.amount == 0 {
+            self.first_purchase_at = now;
+        }
+        if self.original_first_purchase_at == 0 {
+            self.original_first_purchase_at = now;
+        }
+    }
+
+    /// Moves keys to another wallet's holding in the same subject off the curve;
+    /// trade counters and spend/earn totals stay with the original trades.
+    pub fn send_to(&mut self, recipient: &mut KeyHolding, amount: u64, now: i64) -> Result<()> {
+        require!(amount > 0, SolSocialError::InvalidShareAmount);
+        require_keys_neq!(self.owner, recipient.owner, SolSocialError::CannotTransferKeysToSelf);
+        require_keys_eq!(self.subject, recipient.subject, SolSocialError::KeyOwnerMismatch);
+
+        self.amount = self.amount
+            .checked_sub(amount)
+            .ok_or(SolSocialError::InsufficientSharesToSell)?;
+        recipient.mark_acquired(now);
+        recipient.amount = recipient.amount
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.last_trade_timestamp = now;
+        recipient.last_trade_timestamp = now;
+        Ok(())
+    }
+}
+
+/// SOL backing a creator's token curve; buys pay in and sells pay out of it,
+/// so the creator can never spend what sellers are owed.
+#[account]
+pub struct TokenEscrow {
+    pub user_account: Pubkey,
+    pub reserve: u64,
+    pub bump: u8,
+}
+
+impl TokenEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user_account
+        8 + // reserve
+        1; // bump
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.reserve = self.reserve
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.reserve = self.reserve
+            .checked_sub(amount)
+            .ok_or(SolSocialError::InsufficientVaultBalance)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct RewardDistributionCursor {
+    pub profile_owner: Pubkey,
+    pub rewards_per_token: u64,
+    pub last_index: u64,
+    pub holder_count: u64,
+    pub total_distributed: u64,
+    /// Rewards handed to the accumulator but not yet paid out, rounding dust included
+    pub undistributed_rewards: u64,
+    pub bump: u8,
+}
+
+impl RewardDistributionCursor {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // profile_owner
+        8 + // rewards_per_token
+        8 + // last_index
+        8 + // holder_count
+        8 + // total_distributed
+        8 + // undistributed_rewards
+        1; // bump
+
+    pub fn is_complete(&self) -> bool {
+        self.last_index >= self.holder_count
+    }
+}
+
+#[account]
+pub struct Referral {
+    pub referrer: Pubkey,
+    pub referee: Pubkey,
     pub created_at: i64,
+    pub bonus_paid: bool,
     pub bump: u8,
 }
 
-impl Notification {
+impl Referral {
     pub const LEN: usize = 8 + // discriminator
-        8 + // id
-        32 + // recipient
-        32 + // sender
-        1 + // notification_type
-        4 + 256 + // content (max 256 chars)
-        1 + 8 + // post_id (Option<u64>)
-        1 + // read
+        32 + // referrer
+        32 + // referee
         8 + // created_at
+        1 + // bonus_paid
         1; // bump
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum NotificationType {
-    Like,
-    Comment,
-    Follow,
-    Tip,
-    TokenPurchase,
-    Mention,
+    pub const MAX_REFERRALS_PER_USER: u32 = 100;
+    pub const BONUS_BPS: u64 = 2500; // 25% of the referee's first protocol fee
+
+    pub fn validate(referrer: Pubkey, referee: Pubkey, referrer_count: u32) -> Result<()> {
+        require_keys_neq!(referrer, referee, SolSocialError::SelfReferralNotAllowed);
+        require!(
+            referrer_count < Self::MAX_REFERRALS_PER_USER,
+            SolSocialError::MaximumReferralsExceeded
+        );
+        Ok(())
+    }
+
+    /// Returns the bonus owed on the referee's first purchase, and zero afterwards.
+    pub fn take_bonus(&mut self, protocol_fee: u64) -> Result<u64> {
+        if self.bonus_paid {
+            return Ok(0);
+        }
+        self.bonus_paid = true;
+
+        let bonus = protocol_fee
+            .checked_mul(Self::BONUS_BPS)
+            .ok_or(SolSocialError::MathOverflow)?
+            / 10000;
+        Ok(bonus)
+    }
 }
 
+/// Claims a username for one profile, keyed by `[b"username", username]`.
+/// Because the PDA can only be initialized once, a taken name fails at `init`.
 #[account]
-pub struct ReportedContent {
-    pub id: u64,
-    pub reporter: Pubkey,
-    pub content_type: ContentType,
-    pub content_id: u64,
-    pub reason: String,
-    pub status: ReportStatus,
+pub struct UsernameRegistry {
+    pub owner: Pubkey,
+    pub username: String,
     pub created_at: i64,
-    pub resolved_at: Option<i64>,
     pub bump: u8,
 }
 
-impl ReportedContent {
+impl UsernameRegistry {
+    pub const MIN_USERNAME_LENGTH: usize = 3;
+    pub const MAX_USERNAME_LENGTH: usize = 32;
     pub const LEN: usize = 8 + // discriminator
-        8 + // id
-        32 + // reporter
-        1 + // content_type
-        8 + // content_id
-        4 + 256 + // reason (max 256 chars)
-        1 + // status
+        32 + // owner
+        4 + Self::MAX_USERNAME_LENGTH + // username
         8 + // created_at
-        1 + 8 + // resolved_at (Option<i64>)
         1; // bump
+
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ContentType {
-    Post,
-    Comment,
-    Profile,
+/// Mutating instructions call this before anything else so one flag freezes the platform.
+pub fn require_not_emergency_paused(global_state: &GlobalState) -> Result<()> {
+    require!(!global_state.emergency_paused, SolSocialError::EmergencyPauseActivated);
+    Ok(())
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ReportStatus {
-    Pending,
-    Reviewed,
-    Resolved,
-    Dismissed,
+/// Usernames are 3-32 ASCII letters, digits and underscores, and may not
+/// start or end with an underscore.
+pub fn validate_username(username: &str) -> Result<()> {
+    require!(
+        username.len() <= UsernameRegistry::MAX_USERNAME_LENGTH,
+        SolSocialError::UsernameTooLong
+    );
+    require!(
+        username.len() >= UsernameRegistry::MIN_USERNAME_LENGTH
+            && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !username.starts_with('_')
+            && !username.ends_with('_'),
+        SolSocialError::InvalidUsernameFormat
+    );
+    Ok(())
+}
+
+#[account]
+#[derive(Default)]
+pub struct Stake {
+    pub staker: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub lock_period: i64,
+    pub staked_at: i64,
+    pub unlock_at: i64,
+    pub reputation_boost: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl Stake {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // staker
+        32 + // token_mint
+        8 + // amount
+        8 + // lock_period
+        8 + // staked_at
+        8 + // unlock_at
+        8 + // reputation_boost
+        1 + // is_active
+        1; // bump
+
+    pub const MIN_LOCK_PERIOD: i64 = 7 * 24 * 60 * 60; // 7 days
+    pub const MAX_LOCK_PERIOD: i64 = 365 * 24 * 60 * 60; // 365 days
+    /// One reputation point per 10 whole tokens (6 decimals) locked for a day
+    pub const BOOST_DIVISOR: u128 = 10_000_000;
+
+    pub fn calculate_boost(amount: u64, lock_period: i64) -> Result<u64> {
+        let days = (lock_period / (24 * 60 * 60)) as u128;
+        let boost = (amount as u128)
+            .checked_mul(days)
+            .ok_or(SolSocialError::MathOverflow)?
+            / Self::BOOST_DIVISOR;
+        u64::try_from(boost).map_err(|_| error!(SolSocialError::MathOverflow))
+    }
+
+    pub fn open(&mut self, amount: u64, lock_period: i64, now: i64) -> Result<()> {
+        require!(amount > 0, SolSocialError::InvalidShareAmount);
+        require!(
+            (Self::MIN_LOCK_PERIOD..=Self::MAX_LOCK_PERIOD).contains(&lock_period),
+            SolSocialError::InvalidStakingPeriod
+        );
+
+        self.amount = amount;
+        self.lock_period = lock_period;
+        self.staked_at = now;
+        self.unlock_at = now.checked_add(lock_period).ok_or(SolSocialError::MathOverflow)?;
+        self.reputation_boost = Self::calculate_boost(amount, lock_period)?;
+        self.is_active = true;
+        Ok(())
+    }
+
+    /// Ends the stake once the lock has passed and returns the principal owed back.
+    pub fn release(&mut self, now: i64) -> Result<u64> {
+        require!(self.is_active, SolSocialError::StakingNotActive);
+        require!(now >= self.unlock_at, SolSocialError::UnstakingTooEarly);
+
+        self.is_active = false;
+        Ok(self.amount)
+    }
+}
+
+/// Locks a creator's initial allocation in their token vault and releases it
+/// linearly from `start` over `duration`, with nothing claimable before the cliff.
+#[account]
+#[derive(Default)]
+pub struct VestingSchedule {
+    pub creator: Pubkey,
+    pub total_amount: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub duration: i64,
+    pub released: u64,
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // creator
+        8 + // total_amount
+        8 + // start
+        8 + // cliff
+        8 + // duration
+        8 + // released
+        1; // bump
+
+    pub fn open(&mut self, total_amount: u64, start: i64, cliff: i64, duration: i64) -> Result<()> {
+        require!(total_amount > 0, SolSocialError::InvalidAmount);
+        require!(
+            duration > 0 && (0..=duration).contains(&cliff),
+            SolSocialError::InvalidVestingSchedule
+        );
+
+        self.total_amount = total_amount;
+        self.start = start;
+        self.cliff = cliff;
+        self.duration = duration;
+        self.released = 0;
+        Ok(())
+    }
+
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff {
+            return 0;
+        }
+        if elapsed >= self.duration {
+            return self.total_amount;
+        }
+        // Widened so total_amount * elapsed cannot overflow
+        ((self.total_amount as u128) * (elapsed as u128) / (self.duration as u128)) as u64
+    }
+
+    /// Marks everything vested but not yet released as claimed and returns it.
+    pub fn release(&mut self, now: i64) -> Result<u64> {
+        let claimable = self.vested_amount(now).saturating_sub(self.released);
+        require!(claimable > 0, SolSocialError::NoRewardsToClaim);
+
+        self.released = self.released
+            .checked_add(claimable)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(claimable)
+    }
+}
+
+/// Revenue earned by a creator (premium unlocks, subscriptions, tips) waiting to
+/// be split between the creator, the platform and token holders.
+#[account]
+#[derive(Default)]
+pub struct RevenuePool {
+    pub creator: Pubkey,
+    pub pending_revenue: u64,
+    pub holder_rewards_pool: u64,
+    pub total_distributed: u64,
+    pub platform_fee_percentage: u8,
+    pub last_distribution_timestamp: i64,
+    pub bump: u8,
+}
+
+impl RevenuePool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // creator
+        8 + // pending_revenue
+        8 + // holder_rewards_pool
+        8 + // total_distributed
+        1 + // platform_fee_percentage
+        8 + // last_distribution_timestamp
+        1; // bump
+
+    pub const DEFAULT_PLATFORM_FEE_PERCENTAGE: u8 = 5;
+
+    pub fn initialize(&mut self, creator: Pubkey, bump: u8) {
+        self.creator = creator;
+        self.pending_revenue = 0;
+        self.holder_rewards_pool = 0;
+        self.total_distributed = 0;
+        self.platform_fee_percentage = Self::DEFAULT_PLATFORM_FEE_PERCENTAGE;
+        self.last_distribution_timestamp = 0;
+        self.bump = bump;
+    }
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, SolSocialError::InvalidAmount);
+        self.pending_revenue = self.pending_revenue
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Books a distribution of everything pending, with `holder_rewards` of it kept
+    /// back for token holders.
+    pub fn settle_distribution(&mut self, holder_rewards: u64, now: i64) -> Result<()> {
+        self.total_distributed = self.total_distributed
+            .checked_add(self.pending_revenue)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.pending_revenue = 0;
+        self.holder_rewards_pool = self.holder_rewards_pool
+            .checked_add(holder_rewards)
+            .ok_or(SolSocialError::MathOverflow)?;
+        self.last_distribution_timestamp = now;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Proposal {
+    pub id: u64,
+    pub creator: Pubkey,
+    pub proposer: Pubkey,
+    pub title: String,
+    pub description: String,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // id
+        32 + // creator
+        32 + // proposer
+        4 + 64 + // title (max 64 chars)
+        4 + 256 + // description (max 256 chars)
+        8 + // yes_votes
+        8 + // no_votes
+        8 + // created_at
+        8 + // voting_ends_at
+        1 + // executed
+        1; // bump
+
+    pub const MAX_TITLE_LENGTH: usize = 64;
+    pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+    pub const MIN_VOTING_PERIOD: i64 = 24 * 60 * 60; // 1 day
+    pub const MAX_VOTING_PERIOD: i64 = 14 * 24 * 60 * 60; // 14 days
+
+    pub fn record_vote(&mut self, voting_power: u64, approve: bool, now: i64) -> Result<()> {
+        require!(now < self.voting_ends_at, SolSocialError::VotingPeriodEnded);
+        require!(voting_power > 0, SolSocialError::InsufficientVotingPower);
+
+        let tally = if approve { &mut self.yes_votes } else { &mut self.no_votes };
+        *tally = tally.checked_add(voting_power).ok_or(SolSocialError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn execute(&mut self, now: i64) -> Result<()> {
+        require!(now >= self.voting_ends_at, SolSocialError::InvalidGovernanceProposal);
+        require!(!self.executed, SolSocialError::InvalidGovernanceProposal);
+        require!(self.yes_votes > self.no_votes, SolSocialError::ProposalExecutionFailed);
+
+        self.executed = true;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Vote {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub voting_power: u64,
+    pub approve: bool,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl Vote {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // voter
+        8 + // voting_power
+        1 + // approve
+        8 + // voted_at
+        1; // bump
+}
+
+#[account]
+pub struct Notification {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub sender: Pubkey,
+    pub notification_type: NotificationType,
+    pub content: String,
+    pub post_id: Option<u64>,
+    pub read: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Notification {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // id
+        32 + // recipient
+        32 + // sender
+        1 + // notification_type
+        4 + 256 + // content (max 256 chars)
+        1 + 8 + // post_id (Option<u64>)
+        1 + // read
+        8 + // created_at
+        1; // bump
+
+    pub const MAX_CONTENT_LENGTH: usize = 256;
+    /// Each mention creates an account, so the count is capped to bound the post's cost.
+    pub const MAX_MENTIONS: usize = 10;
+
+    pub fn validate_mentions(mentions: &[Pubkey]) -> Result<()> {
+        require!(mentions.len() <= Self::MAX_MENTIONS, SolSocialError::TooManyMentions);
+        for (index, mention) in mentions.iter().enumerate() {
+            require!(
+                !mentions[..index].contains(mention),
+                SolSocialError::DuplicateMention
+            );
+        }
+        Ok(())
+    }
+
+    pub fn mention(id: u64, recipient: Pubkey, sender: Pubkey, content: &str, now: i64, bump: u8) -> Self {
+        let end = content
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .take_while(|&end| end <= Self::MAX_CONTENT_LENGTH)
+            .last()
+            .unwrap_or(0);
+
+        Self {
+            id,
+            recipient,
+            sender,
+            notification_type: NotificationType::Mention,
+            content: content[..end].to_string(),
+            post_id: None,
+            read: false,
+            created_at: now,
+            bump,
+        }
+    }
+
+    pub fn mark_read(&mut self, signer: Pubkey) -> Result<()> {
+        require_keys_eq!(signer, self.recipient, SolSocialError::UnauthorizedAccess);
+        self.read = true;
+        Ok(())
+    }
+
+    /// Only the recipient may clear, and only notifications they have already read.
+    pub fn is_clearable_by(&self, signer: Pubkey) -> Result<bool> {
+        require_keys_eq!(signer, self.recipient, SolSocialError::UnauthorizedAccess);
+        Ok(self.read)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum NotificationType {
+    Like,
+    Comment,
+    Follow,
+    Tip,
+    TokenPurchase,
+    Mention,
+}
+
+#[account]
+pub struct ReportedContent {
+    pub id: u64,
+    pub reporter: Pubkey,
+    pub content_type: ContentType,
+    pub content_id: u64,
+    /// Address of the reported account
+    pub content: Pubkey,
+    pub reason: String,
+    pub status: ReportStatus,
+    pub created_at: i64,
+    pub resolved_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl ReportedContent {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // id
+        32 + // reporter
+        1 + // content_type
+        8 + // content_id
+        32 + // content
+        4 + 256 + // reason (max 256 chars)
+        1 + // status
+        8 + // created_at
+        1 + 8 + // resolved_at (Option<i64>)
+        1; // bump
+
+    pub const MAX_REASON_LENGTH: usize = 256;
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self.status, ReportStatus::Resolved | ReportStatus::Dismissed)
+    }
+
+    /// Moves the report forward; closed reports are final and nothing returns to Pending.
+    pub fn transition(&mut self, new_status: ReportStatus, now: i64) -> Result<()> {
+        require!(
+            !self.is_closed() && new_status != ReportStatus::Pending,
+            SolSocialError::InvalidReportTransition
+        );
+
+        self.status = new_status;
+        if self.is_closed() {
+            self.resolved_at = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Moderation only follows a report that was upheld against this exact account.
+    pub fn require_upheld_against(&self, content_type: ContentType, content: Pubkey) -> Result<()> {
+        require!(
+            self.status == ReportStatus::Resolved
+                && self.content_type == content_type
+                && self.content == content,
+            SolSocialError::ContentModerationFailed
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ContentType {
+    Post,
+    Comment,
+    Profile,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModerationAction {
+    Hide,
+    Remove,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ReportStatus {
+    Pending,
+    Reviewed,
+    Resolved,
+    Dismissed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(is_premium: bool) -> Post {
+        Post {
+            id: 0,
+            author: Pubkey::default(),
+            content: "hello".to_string(),
+            image_url: String::new(),
+            media_urls: Vec::new(),
+            likes_count: 0,
+            comments_count: 0,
+            tips_count: 0,
+            total_tips_amount: 0,
+            bookmarks_count: 0,
+            share_count: 0,
+            created_at: 1_000,
+            updated_at: 1_000,
+            edit_count: 0,
+            is_premium,
+            premium_price: 0,
+            premium_key_ciphertext: Vec::new(),
+            is_hidden: false,
+            is_removed: false,
+            is_nsfw: false,
+            repost_of: None,
+            bump: 0,
+        }
+    }
+
+    fn profile() -> UserProfile {
+        UserProfile {
+            authority: Pubkey::new_unique(),
+            username: "alice".to_string(),
+            display_name: String::new(),
+            bio: String::new(),
+            avatar_url: String::new(),
+            banner_image_url: String::new(),
+            token_mint: Pubkey::default(),
+            token_vault: Pubkey::default(),
+            token_supply: 0,
+            max_supply: 0,
+            token_price: 0,
+            holder_count: 0,
+            total_volume: 0,
+            total_keys_held: 0,
+            followers_count: 0,
+            following_count: 0,
+            posts_count: 0,
+            pinned_count: 0,
+            total_earned: 0,
+            total_spent: 0,
+            tips_given: 0,
+            tips_received: 0,
+            creator_tip_percentage: UserProfile::DEFAULT_CREATOR_TIP_PERCENTAGE,
+            revenue_share_percentage: UserProfile::DEFAULT_REVENUE_SHARE_PERCENTAGE,
+            messages_sent: 0,
+            messages_received: 0,
+            message_nonce: 0,
+            tip_nonce: 0,
+            chats_created: 0,
+            total_chats_joined: 0,
+            engagement_score: 0,
+            reputation_score: UserProfile::INITIAL_REPUTATION,
+            reputation_last_decayed: 0,
+            price_boost_day: 0,
+            price_boost_today: 0,
+            referral_count: 0,
+            is_verified: false,
+            is_private: false,
+            blocks_nsfw: false,
+            is_active: true,
+            token_frozen: false,
+            encryption_pubkey: None,
+            social_links: Vec::new(),
+            created_at: 0,
+            updated_at: 0,
+            last_post_timestamp: 0,
+            last_active: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_register_encryption_key() {
+        let mut recipient = profile();
+        assert!(recipient.register_encryption_key([0u8; 32]).is_err());
+
+        recipient.register_encryption_key([7u8; 32]).unwrap();
+        assert_eq!(recipient.encryption_pubkey, Some([7u8; 32]));
+        assert!(recipient.require_can_receive(true).is_ok());
+    }
+
+    #[test]
+    fn test_add_social_link_rejects_duplicate_platform() {
+        let mut user = profile();
+        user.add_social_link("x".to_string(), "https://x.com/alice".to_string()).unwrap();
+        assert_eq!(user.social_links.len(), 1);
+
+        assert!(user.add_social_link("x".to_string(), "https://x.com/bob".to_string()).is_err());
+        assert!(user.add_social_link("github".to_string(), "a".repeat(SocialLink::MAX_URL_LENGTH + 1)).is_err());
+        assert_eq!(user.social_links[0].url, "https://x.com/alice");
+    }
+
+    #[test]
+    fn test_social_links_capped_and_removable() {
+        let mut user = profile();
+        for i in 0..UserProfile::MAX_SOCIAL_LINKS {
+            user.add_social_link(format!("site{}", i), "https://example.com".to_string()).unwrap();
+        }
+        assert!(user.add_social_link("extra".to_string(), "https://example.com".to_string()).is_err());
+
+        user.remove_social_link("site2").unwrap();
+        assert!(user.social_links.iter().all(|link| link.platform != "site2"));
+        assert!(user.remove_social_link("site2").is_err());
+        user.add_social_link("extra".to_string(), "https://example.com".to_string()).unwrap();
+    }
+
+    fn empty_stats() -> SocialStats {
+        SocialStats::default()
+    }
+
+    #[test]
+    fn test_backfill_copies_legacy_profile_counts() {
+        let mut legacy = profile();
+        legacy.followers_count = 12;
+        legacy.posts_count = 4;
+        legacy.last_active = 900;
+
+        let mut stats = empty_stats();
+        assert!(stats.backfill(&legacy, 254));
+        assert_eq!(stats.user, legacy.authority);
+        assert_eq!(stats.followers_count, 12);
+        assert_eq!(stats.posts_count, 4);
+        assert_eq!(stats.reputation_score, UserProfile::INITIAL_REPUTATION);
+        assert_eq!(stats.bump, 254);
+    }
+
+    #[test]
+    fn test_backfill_leaves_existing_stats_alone() {
+        let mut legacy = profile();
+        let mut stats = empty_stats();
+        stats.backfill(&legacy, 254);
+        stats.likes_received = 7;
+
+        legacy.followers_count = 99;
+        assert!(!stats.backfill(&legacy, 254));
+        assert_eq!(stats.followers_count, 0);
+        assert_eq!(stats.likes_received, 7);
+    }
+
+    #[test]
+    fn test_frozen_token_blocks_trading_until_thawed() {
+        let mut creator = profile();
+        assert!(creator.require_not_frozen().is_ok());
+
+        creator.token_frozen = true;
+        assert!(creator.require_not_frozen().is_err());
+
+        creator.token_frozen = false;
+        assert!(creator.require_not_frozen().is_ok());
+    }
+
+    #[test]
+    fn test_set_revenue_share_within_platform_fee() {
+        let mut creator = profile();
+        creator.set_revenue_share(70, 20).unwrap();
+        assert_eq!(creator.revenue_share_percentage, 70);
+
+        // Exactly 100% combined is still allowed
+        creator.set_revenue_share(80, 20).unwrap();
+        assert_eq!(creator.revenue_share_percentage, 80);
+    }
+
+    #[test]
+    fn test_set_revenue_share_over_100_with_fee_rejected() {
+        let mut creator = profile();
+        assert!(creator.set_revenue_share(81, 20).is_err());
+        assert!(creator.set_revenue_share(200, 200).is_err());
+        assert_eq!(
+            creator.revenue_share_percentage,
+            UserProfile::DEFAULT_REVENUE_SHARE_PERCENTAGE
+        );
+    }
+
+    #[test]
+    fn test_revenue_share_persists_across_distributions() {
+        let mut creator = profile();
+        creator.set_revenue_share(60, 10).unwrap();
+
+        for _ in 0..2 {
+            let (creator_share, platform_share, holder_share) =
+                crate::utils::revenue_share::split_revenue(1_000, creator.revenue_share_percentage, 10)
+                    .unwrap();
+            assert_eq!((creator_share, platform_share, holder_share), (600, 100, 300));
+        }
+        assert_eq!(creator.revenue_share_percentage, 60);
+    }
+
+    #[test]
+    fn test_deposit_then_distribute_resets_pending_revenue() {
+        let mut pool = revenue_pool();
+        pool.initialize(Pubkey::new_unique(), 254);
+
+        pool.deposit(600).unwrap();
+        pool.deposit(400).unwrap();
+        assert_eq!(pool.pending_revenue, 1_000);
+
+        let (_, _, holder_rewards) = crate::utils::revenue_share::split_revenue(
+            pool.pending_revenue,
+            UserProfile::DEFAULT_REVENUE_SHARE_PERCENTAGE,
+            pool.platform_fee_percentage,
+        )
+        .unwrap();
+        pool.settle_distribution(holder_rewards, 1_700_000_000).unwrap();
+
+        assert_eq!(pool.pending_revenue, 0);
+        assert_eq!(pool.total_distributed, 1_000);
+        assert_eq!(pool.holder_rewards_pool, 450);
+        assert_eq!(pool.last_distribution_timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_deposit_rejects_zero() {
+        let mut pool = revenue_pool();
+        pool.initialize(Pubkey::new_unique(), 254);
+        assert!(pool.deposit(0).is_err());
+        assert_eq!(pool.pending_revenue, 0);
+    }
+
+    #[test]
+    fn test_nonces_give_each_send_its_own_seed() {
+        let mut sender = profile();
+        // Two sends in the same second still derive different PDAs
+        assert_eq!(sender.advance_message_nonce().unwrap(), 0);
+        assert_eq!(sender.advance_message_nonce().unwrap(), 1);
+        assert_eq!(sender.message_nonce, 2);
+
+        assert_eq!(sender.advance_tip_nonce().unwrap(), 0);
+        assert_eq!(sender.tip_nonce, 1);
+    }
+
+    #[test]
+    fn test_rename_updates_username() {
+        let mut user = profile();
+        user.rename("alice_2".to_string(), 50).unwrap();
+
+        assert_eq!(user.username, "alice_2");
+        assert_eq!(user.updated_at, 50);
+    }
+
+    #[test]
+    fn test_rename_rejects_invalid_or_unchanged_name() {
+        let mut user = profile();
+        assert!(user.rename(String::new(), 50).is_err());
+        assert!(user.rename("a".repeat(UsernameRegistry::MAX_USERNAME_LENGTH + 1), 50).is_err());
+        assert!(user.rename("alice".to_string(), 50).is_err());
+        assert_eq!(user.updated_at, 0);
+    }
+
+    #[test]
+    fn test_validate_username_accepts_alphanumeric_and_underscore() {
+        assert!(validate_username("bob").is_ok());
+        assert!(validate_username("alice_42").is_ok());
+        assert!(validate_username(&"a".repeat(UsernameRegistry::MAX_USERNAME_LENGTH)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_username_rejects_bad_characters() {
+        assert!(validate_username("alice smith").is_err());
+        assert!(validate_username("alice🚀").is_err());
+        assert!(validate_username("alice-smith").is_err());
+        assert!(validate_username("_alice").is_err());
+        assert!(validate_username("alice_").is_err());
+    }
+
+    #[test]
+    fn test_validate_username_rejects_short_names() {
+        assert!(validate_username("").is_err());
+        assert!(validate_username("ab").is_err());
+    }
+
+    #[test]
+    fn test_validate_url_accepts_https_and_ipfs() {
+        assert!(validate_url("https://example.com/avatar.png").is_ok());
+        assert!(validate_url("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").is_ok());
+        assert!(validate_url("").is_ok());
+    }
+
+    #[test]
+    fn test_validate_url_rejects_other_schemes() {
+        assert!(validate_url("javascript:alert(1)").is_err());
+        assert!(validate_url("http://example.com/avatar.png").is_err());
+        assert!(validate_url("my avatar").is_err());
+    }
+
+    fn token_holder() -> TokenHolder {
+        TokenHolder {
+            holder: Pubkey::new_unique(),
+            profile_owner: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            ..TokenHolder::default()
+        }
+    }
+
+    #[test]
+    fn test_two_buys_weight_the_average_price() {
+        let mut holding = token_holder();
+        holding.record_buy(10, 10 * 1_000, 0, 10, 5).unwrap();
+        holding.record_buy(30, 30 * 2_000, 0, 40, 6).unwrap();
+
+        // (1_000 * 10 + 2_000 * 30) / 40
+        assert_eq!(holding.average_price, 1_750);
+        assert_eq!(holding.amount, 40);
+        assert_eq!(holding.updated_at, 6);
+    }
+
+    #[test]
+    fn test_sell_keeps_average_price() {
+        let mut holding = token_holder();
+        holding.record_buy(4, 4 * 500, 0, 4, 5).unwrap();
+        holding.record_sell(3, 3 * 500, 0, 4, 7).unwrap();
+
+        assert_eq!(holding.average_price, 500);
+        assert_eq!(holding.amount, 1);
+        assert!(holding.record_sell(2, 2 * 500, 0, 1, 8).is_err());
+    }
+
+    #[test]
+    fn test_buy_high_sell_low_realizes_a_loss() {
+        let mut holding = token_holder();
+        holding.record_buy(2, 2 * 1_000, 0, 2, 5).unwrap();
+        holding.record_sell(1, 600, 0, 2, 6).unwrap();
+
+        assert_eq!(holding.realized_pnl, -400);
+        // The token still held is marked at the lower price too
+        assert_eq!(holding.unrealized_pnl(600).unwrap(), -400);
+        assert_eq!(holding.unrealized_pnl(1_500).unwrap(), 500);
+
+        holding.record_sell(1, 1_100, 0, 1, 7).unwrap();
+        assert_eq!(holding.realized_pnl, -300);
+        assert_eq!(holding.unrealized_pnl(600).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_new_holder_starts_at_current_rewards_per_token() {
+        let mut holding = token_holder();
+        holding.initialize(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 5_000_000, 1, 255);
+        holding.record_buy(10, 10_000, 5_000_000, 10, 2).unwrap();
+
+        // Nothing was distributed since they joined
+        assert_eq!(holding.take_rewards(5_000_000, 10).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_later_buy_keeps_the_holder_record() {
+        let holder = Pubkey::new_unique();
+        // A freshly created account is zeroed
+        let mut holding = TokenHolder { holder: Pubkey::default(), ..token_holder() };
+        holding.ensure_initialized(holder, Pubkey::new_unique(), Pubkey::new_unique(), 1_000_000, 1, 255);
+        assert_eq!(holding.holder, holder);
+        holding.record_buy(10, 10_000, 1_000_000, 10, 1).unwrap();
+
+        // The account already exists on the second buy
+        holding.ensure_initialized(holder, Pubkey::new_unique(), Pubkey::new_unique(), 4_000_000, 2, 255);
+        assert_eq!(holding.amount, 10);
+        assert_eq!(holding.created_at, 1);
+        assert_eq!(holding.take_rewards(4_000_000, 10).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_buy_settles_rewards_at_old_balance() {
+        let mut holding = token_holder();
+        holding.record_buy(10, 10_000, 0, 10, 1).unwrap();
+
+        // 2 tokens of reward per token held accrue while holding 10
+        holding.record_buy(90, 90_000, 2_000_000, 100, 2).unwrap();
+        assert_eq!(holding.pending_rewards, 20);
+        assert_eq!(holding.last_claimed_rewards_per_token, 2_000_000);
+
+        // The 90 new tokens only earn from here on
+        assert_eq!(holding.take_rewards(3_000_000, 100).unwrap(), 20 + 100);
+        assert_eq!(holding.pending_rewards, 0);
+    }
+
+    #[test]
+    fn test_sell_keeps_rewards_earned_before_it() {
+        let mut holding = token_holder();
+        holding.record_buy(10, 10_000, 0, 10, 1).unwrap();
+        holding.record_sell(10, 10_000, 1_000_000, 10, 2).unwrap();
+
+        assert_eq!(holding.amount, 0);
+        assert_eq!(holding.take_rewards(4_000_000, 5).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_reputation_moves_both_ways() {
+        let mut user = profile();
+        user.update_reputation(25).unwrap();
+        assert_eq!(user.reputation_score, 125);
+
+        user.update_reputation(-125).unwrap();
+        assert_eq!(user.reputation_score, 0);
+        assert!(user.update_reputation(-1).is_err());
+    }
+
+    const DAY: i64 = 86_400;
+
+    #[test]
+    fn test_reputation_decays_after_thirty_idle_days() {
+        let mut user = profile();
+        user.last_active = 1_000;
+
+        assert_eq!(user.decay_reputation(1_000 + 30 * DAY).unwrap(), 30);
+        assert_eq!(user.reputation_score, UserProfile::INITIAL_REPUTATION - 30);
+    }
+
+    #[test]
+    fn test_reputation_decay_not_applied_twice() {
+        let mut user = profile();
+        let now = 30 * DAY + DAY / 2;
+
+        user.decay_reputation(now).unwrap();
+        assert_eq!(user.decay_reputation(now).unwrap(), 0);
+        assert_eq!(user.reputation_score, 70);
+
+        // The leftover half day combines with the next half into one more day
+        user.decay_reputation(31 * DAY).unwrap();
+        assert_eq!(user.reputation_score, 69);
+    }
+
+    #[test]
+    fn test_reputation_decay_stops_at_floor() {
+        let mut user = profile();
+        user.decay_reputation(365 * DAY).unwrap();
+        assert_eq!(user.reputation_score, UserProfile::REPUTATION_FLOOR);
+
+        user.reputation_score = 5;
+        user.decay_reputation(400 * DAY).unwrap();
+        assert_eq!(user.reputation_score, 5);
+    }
+
+    #[test]
+    fn test_price_boost_capped_per_day() {
+        let mut creator = profile();
+        creator.token_price = 1_000_000;
+
+        assert_eq!(creator.apply_price_boost(30_000, DAY).unwrap(), 30_000);
+        assert_eq!(creator.apply_price_boost(30_000, DAY + 60).unwrap(), 20_000);
+        assert_eq!(creator.apply_price_boost(30_000, DAY + 120).unwrap(), 0);
+        assert_eq!(creator.token_price, 1_000_000 + UserProfile::MAX_DAILY_PRICE_BOOST);
+
+        // A new day starts a fresh allowance
+        assert_eq!(creator.apply_price_boost(30_000, 2 * DAY).unwrap(), 30_000);
+    }
+
+    #[test]
+    fn test_encrypted_send_requires_recipient_key() {
+        let recipient = profile();
+        assert!(recipient.require_can_receive(false).is_ok());
+        assert!(recipient.require_can_receive(true).is_err());
+    }
+
+    fn report() -> ReportedContent {
+        ReportedContent {
+            id: 0,
+            reporter: Pubkey::new_unique(),
+            content_type: ContentType::Post,
+            content_id: 42,
+            content: Pubkey::new_unique(),
+            reason: "spam".to_string(),
+            status: ReportStatus::Pending,
+            created_at: 0,
+            resolved_at: None,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_report_lifecycle() {
+        let mut report = report();
+
+        report.transition(ReportStatus::Reviewed, 10).unwrap();
+        assert!(report.status == ReportStatus::Reviewed);
+        assert_eq!(report.resolved_at, None);
+
+        report.transition(ReportStatus::Resolved, 20).unwrap();
+        assert!(report.status == ReportStatus::Resolved);
+        assert_eq!(report.resolved_at, Some(20));
+
+        assert!(report.transition(ReportStatus::Reviewed, 30).is_err());
+        assert!(report.transition(ReportStatus::Dismissed, 30).is_err());
+        assert_eq!(report.resolved_at, Some(20));
+    }
+
+    #[test]
+    fn test_report_cannot_return_to_pending() {
+        let mut report = report();
+        assert!(report.transition(ReportStatus::Pending, 10).is_err());
+
+        report.transition(ReportStatus::Dismissed, 10).unwrap();
+        assert_eq!(report.resolved_at, Some(10));
+    }
+
+    #[test]
+    fn test_moderated_post_rejects_interactions() {
+        let mut report = report();
+        let mut post = post(false);
+        let post_key = report.content;
+        assert!(post.require_not_moderated().is_ok());
+
+        // Nothing happens until the report is upheld
+        assert!(report.require_upheld_against(ContentType::Post, post_key).is_err());
+        report.transition(ReportStatus::Resolved, 10).unwrap();
+        report.require_upheld_against(ContentType::Post, post_key).unwrap();
+        assert!(report.require_upheld_against(ContentType::Comment, post_key).is_err());
+
+        // Post ids are per author, so a report must not carry over to another
+        // author's post that happens to share the id
+        post.id = report.content_id;
+        assert!(report.require_upheld_against(ContentType::Post, Pubkey::new_unique()).is_err());
+
+        post.moderate(ModerationAction::Hide).unwrap();
+        assert!(post.is_hidden && !post.is_removed);
+        assert_eq!(
+            post.require_not_moderated().unwrap_err(),
+            error!(SolSocialError::ContentModerationFailed)
+        );
+    }
+
+    #[test]
+    fn test_nsfw_post_rejected_where_blocked() {
+        let mut author = profile();
+        author.blocks_nsfw = true;
+        assert_eq!(
+            Post::check_nsfw_allowed(true, author.blocks_nsfw).unwrap_err(),
+            error!(SolSocialError::NSFWContentNotAllowed)
+        );
+        assert!(Post::check_nsfw_allowed(false, author.blocks_nsfw).is_ok());
+    }
+
+    #[test]
+    fn test_nsfw_post_accepted_where_allowed() {
+        let author = profile();
+        assert!(!author.blocks_nsfw);
+        assert!(Post::check_nsfw_allowed(true, author.blocks_nsfw).is_ok());
+    }
+
+    #[test]
+    fn test_removal_is_final() {
+        let mut post = post(false);
+        post.moderate(ModerationAction::Remove).unwrap();
+        assert!(post.is_hidden && post.is_removed);
+        assert!(post.moderate(ModerationAction::Hide).is_err());
+        assert!(post.require_not_moderated().is_err());
+        // The account, and what was said, is kept for audit
+        assert_eq!(post.content, "hello");
+    }
+
+    fn global_state(min_post_interval_secs: i64) -> GlobalState {
+        GlobalState { min_post_interval_secs, ..GlobalState::default() }
+    }
+
+    fn trading_state(authority: Pubkey) -> GlobalState {
+        let mut state = global_state(0);
+        state.initialize(authority, 255).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_emergency_pause_blocks_mutations() {
+        let authority = Pubkey::new_unique();
+        let mut state = global_state(0);
+        state.authority = authority;
+        assert!(require_not_emergency_paused(&state).is_ok());
+
+        state.set_emergency_pause(authority, true).unwrap();
+        // Posts, follows, tips, chats and trades all open with this guard
+        assert_eq!(
+            require_not_emergency_paused(&state).unwrap_err(),
+            error!(SolSocialError::EmergencyPauseActivated)
+        );
+        // Checks that would otherwise pass don't matter while paused
+        assert!(state.check_post_length("hello").is_ok());
+        assert!(state.check_tip_amount(1_000, 1_000_000).is_ok());
+
+        state.set_emergency_pause(authority, false).unwrap();
+        assert!(require_not_emergency_paused(&state).is_ok());
+    }
+
+    #[test]
+    fn test_emergency_pause_is_authority_only() {
+        let mut state = global_state(0);
+        state.authority = Pubkey::new_unique();
+        assert_eq!(
+            state.set_emergency_pause(Pubkey::new_unique(), true).unwrap_err(),
+            error!(SolSocialError::UnauthorizedAccess)
+        );
+        assert!(!state.emergency_paused);
+    }
+
+    #[test]
+    fn test_emergency_pause_is_separate_from_trading_pause() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        state.set_paused(authority, true).unwrap();
+        assert!(require_not_emergency_paused(&state).is_ok());
+
+        state.set_paused(authority, false).unwrap();
+        state.set_emergency_pause(authority, true).unwrap();
+        assert!(!state.is_paused);
+    }
+
+    #[test]
+    fn test_global_counters_follow_activity() {
+        let mut state = global_state(0);
+        state.record_user().unwrap();
+        state.record_user().unwrap();
+        state.record_post().unwrap();
+        state.record_tips(1, 5_000).unwrap();
+        state.record_post().unwrap();
+        // A batch of three tips counts each tip
+        state.record_tips(3, 7_500).unwrap();
+
+        assert_eq!(state.total_users, 2);
+        assert_eq!(state.total_posts, 2);
+        assert_eq!(state.total_tips, 4);
+        assert_eq!(state.total_volume, 12_500);
+    }
+
+    #[test]
+    fn test_authority_updates_fees() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        state.update_fees(authority, 300, 400, 50).unwrap();
+        assert_eq!(state.trading_fee_bps, 300);
+        assert_eq!(state.creator_fee_bps, 400);
+        assert_eq!(state.protocol_fee_bps, 50);
+
+        // Key trades pick the new rates up straight away
+        assert_eq!(state.key_trade_fees(1_000_000, 0, 0).unwrap(), (5_000, 40_000));
+        assert_eq!(state.token_trade_fee(1_000_000, 0, 0).unwrap(), 30_000);
+    }
+
+    #[test]
+    fn test_trade_fees_drop_with_trader_volume() {
+        let state = trading_state(Pubkey::new_unique());
+        let new_trader = state.key_trade_fees(1_000_000, 0, 0).unwrap();
+        let whale = state.key_trade_fees(1_000_000, 0, 10_000_000_000_000).unwrap();
+
+        assert_eq!(new_trader, (10_000, 50_000));
+        // Only the protocol's cut is tiered; the creator keeps the same share
+        assert_eq!(whale, (2_500, 50_000));
+        assert!(state.token_trade_fee(1_000_000, 0, 10_000_000_000_000).unwrap()
+            < state.token_trade_fee(1_000_000, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_non_authority_cannot_update_fees() {
+        let mut state = trading_state(Pubkey::new_unique());
+
+        assert!(state.update_fees(Pubkey::new_unique(), 300, 400, 50).is_err());
+        assert_eq!(state.trading_fee_bps, 500);
+    }
+
+    #[test]
+    fn test_authority_handoff() {
+        let authority = Pubkey::new_unique();
+        let successor = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        assert!(state.propose_authority(successor, successor).is_err());
+        state.propose_authority(authority, successor).unwrap();
+        assert_eq!(state.pending_authority, Some(successor));
+        assert_eq!(state.authority, authority);
+
+        state.accept_authority(successor).unwrap();
+        assert_eq!(state.authority, successor);
+        assert_eq!(state.pending_authority, None);
+    }
+
+    #[test]
+    fn test_accept_by_wrong_key_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        assert!(state.accept_authority(Pubkey::new_unique()).is_err());
+
+        state.propose_authority(authority, Pubkey::new_unique()).unwrap();
+        assert!(state.accept_authority(Pubkey::new_unique()).is_err());
+        assert!(state.accept_authority(authority).is_err());
+        assert_eq!(state.authority, authority);
+    }
+
+    #[test]
+    fn test_only_authority_can_pause() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        assert!(state.set_paused(Pubkey::new_unique(), true).is_err());
+        assert!(!state.is_paused);
+
+        state.set_paused(authority, true).unwrap();
+        assert!(state.is_paused);
+        state.set_paused(authority, false).unwrap();
+        assert!(!state.is_paused);
+    }
+
+    #[test]
+    fn test_authority_withdraws_collected_fees() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+        state.add_fees(1_000).unwrap();
+
+        state.withdraw_fees(authority, 400).unwrap();
+        assert_eq!(state.total_fees_collected, 600);
+        assert!(state.withdraw_fees(Pubkey::new_unique(), 100).is_err());
+    }
+
+    #[test]
+    fn test_over_withdrawal_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+        state.add_fees(1_000).unwrap();
+
+        assert!(state.withdraw_fees(authority, 1_001).is_err());
+        assert_eq!(state.total_fees_collected, 1_000);
+        state.withdraw_fees(authority, 1_000).unwrap();
+        assert_eq!(state.total_fees_collected, 0);
+    }
+
+    #[test]
+    fn test_fee_over_cap_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut state = trading_state(authority);
+
+        assert!(state.update_fees(authority, GlobalState::MAX_FEE_BPS + 1, 0, 0).is_err());
+        assert!(state.update_fees(authority, GlobalState::MAX_FEE_BPS, GlobalState::MAX_FEE_BPS, GlobalState::MAX_FEE_BPS).is_ok());
+        assert_eq!(state.protocol_fee_bps, GlobalState::MAX_FEE_BPS);
+    }
+
+    #[test]
+    fn test_global_counters_use_checked_math() {
+        let mut state = global_state(0);
+        state.total_users = u64::MAX;
+        state.total_volume = u64::MAX - 1;
+        assert!(state.record_user().is_err());
+        assert!(state.record_tips(1, 2).is_err());
+    }
+
+    #[test]
+    fn test_post_length_boundary() {
+        let mut state = global_state(0);
+        state.max_post_length = 280;
+        assert!(state.check_post_length(&"a".repeat(280)).is_ok());
+        assert_eq!(
+            state.check_post_length(&"a".repeat(281)).unwrap_err(),
+            error!(SolSocialError::ContentTooLong)
+        );
+    }
+
+    #[test]
+    fn test_unset_post_length_uses_default() {
+        let state = global_state(0);
+        let max = GlobalState::DEFAULT_MAX_POST_LENGTH as usize;
+        assert!(state.check_post_length(&"a".repeat(max)).is_ok());
+        assert!(state.check_post_length(&"a".repeat(max + 1)).is_err());
+    }
+
+    #[test]
+    fn test_tip_bounds_reject_out_of_range() {
+        let mut state = global_state(0);
+        state.min_tip = 100;
+        state.max_tip = 10_000;
+
+        assert!(state.check_tip_amount(99, 1_000_000).is_err());
+        assert!(state.check_tip_amount(10_001, 1_000_000).is_err());
+        assert!(state.check_tip_amount(100, 1_000_000).is_ok());
+        assert!(state.check_tip_amount(10_000, 1_000_000).is_ok());
+    }
+
+    #[test]
+    fn test_unset_max_tip_uses_default() {
+        let state = global_state(0);
+        assert!(state.check_tip_amount(0, 1_000).is_err());
+        assert!(state.check_tip_amount(1_000, 1_000).is_ok());
+        assert!(state.check_tip_amount(1_001, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_back_to_back_posts_rate_limited() {
+        let state = global_state(60);
+        assert!(state.check_post_interval(0, 1_000).is_ok());
+        assert!(state.check_post_interval(1_000, 1_000).is_err());
+        assert!(state.check_post_interval(1_000, 1_059).is_err());
+    }
+
+    #[test]
+    fn test_post_allowed_after_interval() {
+        let state = global_state(60);
+        assert!(state.check_post_interval(1_000, 1_060).is_ok());
+    }
+
+    #[test]
+    fn test_premium_unlock_charges_once() {
+        let mut access = PremiumAccess {
+            user: Pubkey::new_unique(),
+            profile_owner: Pubkey::new_unique(),
+            expires_at: 0,
+            created_at: 0,
+            bump: 0,
+        };
+
+        assert!(access.unlock(1_000).unwrap());
+        assert_eq!(access.expires_at, 1_000 + PremiumAccess::ACCESS_DURATION);
+
+        // Second unlock while active neither charges nor extends
+        assert!(!access.unlock(2_000).unwrap());
+        assert_eq!(access.expires_at, 1_000 + PremiumAccess::ACCESS_DURATION);
+
+        let lapsed = 1_000 + PremiumAccess::ACCESS_DURATION;
+        assert!(access.unlock(lapsed).unwrap());
+        assert_eq!(access.expires_at, lapsed + PremiumAccess::ACCESS_DURATION);
+    }
+
+    #[test]
+    fn test_configure_premium_validates_key() {
+        let mut premium = post(false);
+        assert!(premium.configure_premium(100, Vec::new()).is_err());
+        assert!(premium.configure_premium(100, vec![1; Post::MAX_PREMIUM_KEY_LENGTH + 1]).is_err());
+
+        premium.configure_premium(100, vec![1; 48]).unwrap();
+        assert!(premium.is_premium);
+        assert_eq!(premium.premium_price, 100);
+    }
+
+    #[test]
+    fn test_referral_attribution() {
+        let referrer = Pubkey::new_unique();
+        assert!(Referral::validate(referrer, Pubkey::new_unique(), 0).is_ok());
+
+        let mut referral = Referral {
+            referrer,
+            referee: Pubkey::new_unique(),
+            created_at: 0,
+            bonus_paid: false,
+            bump: 0,
+        };
+        assert_eq!(referral.take_bonus(1_000).unwrap(), 250);
+        assert!(referral.bonus_paid);
+        // Only the first purchase pays out
+        assert_eq!(referral.take_bonus(1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_self_referral_rejected() {
+        let user = Pubkey::new_unique();
+        assert!(Referral::validate(user, user, 0).is_err());
+    }
+
+    #[test]
+    fn test_referral_cap_enforced() {
+        let referrer = Pubkey::new_unique();
+        assert!(Referral::validate(referrer, Pubkey::new_unique(), Referral::MAX_REFERRALS_PER_USER - 1).is_ok());
+        assert!(Referral::validate(referrer, Pubkey::new_unique(), Referral::MAX_REFERRALS_PER_USER).is_err());
+    }
+
+    const VESTING_DURATION: i64 = 400 * DAY;
+
+    fn vesting() -> VestingSchedule {
+        let mut schedule = VestingSchedule {
+            creator: Pubkey::new_unique(),
+            total_amount: 0,
+            start: 0,
+            cliff: 0,
+            duration: 0,
+            released: 0,
+            bump: 0,
+        };
+        schedule.open(1_000_000, 1_000, 100 * DAY, VESTING_DURATION).unwrap();
+        schedule
+    }
+
+    #[test]
+    fn test_nothing_vests_before_cliff() {
+        let mut schedule = vesting();
+        assert_eq!(schedule.vested_amount(1_000 + 100 * DAY - 1), 0);
+        assert!(schedule.release(1_000 + 100 * DAY - 1).is_err());
+    }
+
+    #[test]
+    fn test_vesting_is_linear_mid_schedule() {
+        let mut schedule = vesting();
+        assert_eq!(schedule.release(1_000 + 100 * DAY).unwrap(), 250_000);
+        assert_eq!(schedule.release(1_000 + 200 * DAY).unwrap(), 250_000);
+        assert_eq!(schedule.released, 500_000);
+    }
+
+    #[test]
+    fn test_everything_vests_after_duration() {
+        let mut schedule = vesting();
+        schedule.release(1_000 + 100 * DAY).unwrap();
+        assert_eq!(schedule.release(1_000 + VESTING_DURATION + DAY).unwrap(), 750_000);
+        assert_eq!(schedule.released, schedule.total_amount);
+        assert!(schedule.release(1_000 + 2 * VESTING_DURATION).is_err());
+    }
+
+    #[test]
+    fn test_vesting_rejects_cliff_past_duration() {
+        let mut schedule = vesting();
+        assert!(schedule.open(1_000, 0, VESTING_DURATION + 1, VESTING_DURATION).is_err());
+        assert!(schedule.open(1_000, 0, 0, 0).is_err());
+    }
+
+    fn stake() -> Stake {
+        Stake {
+            staker: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            amount: 0,
+            lock_period: 0,
+            staked_at: 0,
+            unlock_at: 0,
+            reputation_boost: 0,
+            is_active: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_stake_boosts_reputation_by_amount_and_period() {
+        let mut short = stake();
+        short.open(100_000_000, Stake::MIN_LOCK_PERIOD, 1_000).unwrap();
+        assert_eq!(short.unlock_at, 1_000 + Stake::MIN_LOCK_PERIOD);
+        assert_eq!(short.reputation_boost, 70);
+
+        let mut long = stake();
+        long.open(100_000_000, 4 * Stake::MIN_LOCK_PERIOD, 1_000).unwrap();
+        assert_eq!(long.reputation_boost, 4 * short.reputation_boost);
+
+        assert!(stake().open(100, Stake::MIN_LOCK_PERIOD - 1, 0).is_err());
+        assert!(stake().open(0, Stake::MIN_LOCK_PERIOD, 0).is_err());
+    }
+
+    #[test]
+    fn test_early_unstake_rejected() {
+        let mut position = stake();
+        position.open(500, Stake::MIN_LOCK_PERIOD, 1_000).unwrap();
+
+        assert!(position.release(1_000 + Stake::MIN_LOCK_PERIOD - 1).is_err());
+        assert!(position.is_active);
+    }
+
+    #[test]
+    fn test_unstake_after_lock_returns_principal() {
+        let mut position = stake();
+        position.open(500, Stake::MIN_LOCK_PERIOD, 1_000).unwrap();
+
+        assert_eq!(position.release(1_000 + Stake::MIN_LOCK_PERIOD).unwrap(), 500);
+        assert!(!position.is_active);
+        assert!(position.release(2_000 + Stake::MIN_LOCK_PERIOD).is_err());
+    }
+
+    fn proposal() -> Proposal {
+        Proposal {
+            id: 0,
+            creator: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            title: "Raise creator fee".to_string(),
+            description: String::new(),
+            yes_votes: 0,
+            no_votes: 0,
+            created_at: 0,
+            voting_ends_at: 1_000,
+            executed: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_passing_proposal_executes() {
+        let mut proposal = proposal();
+        proposal.record_vote(30, true, 10).unwrap();
+        proposal.record_vote(20, false, 20).unwrap();
+        assert_eq!(proposal.yes_votes, 30);
+        assert_eq!(proposal.no_votes, 20);
+
+        assert!(proposal.execute(999).is_err());
+        proposal.execute(1_000).unwrap();
+        assert!(proposal.executed);
+        assert!(proposal.execute(1_001).is_err());
+    }
+
+    #[test]
+    fn test_failing_proposal_not_executed() {
+        let mut proposal = proposal();
+        proposal.record_vote(10, true, 10).unwrap();
+        proposal.record_vote(10, false, 10).unwrap();
+        assert!(proposal.execute(1_000).is_err());
+    }
+
+    #[test]
+    fn test_late_or_powerless_vote_rejected() {
+        let mut proposal = proposal();
+        assert!(proposal.record_vote(10, true, 1_000).is_err());
+        assert!(proposal.record_vote(0, true, 10).is_err());
+        assert_eq!(proposal.yes_votes, 0);
+    }
+
+    #[test]
+    fn test_engagement_rate_scaled() {
+        let mut stats = SocialStats {
+            user: Pubkey::new_unique(),
+            followers_count: 200,
+            following_count: 0,
+            posts_count: 4,
+            likes_received: 30,
+            likes_given: 0,
+            comments_count: 8,
+            shares_count: 2,
+            tips_received: 0,
+            tips_given: 0,
+            total_earnings: 0,
+            reputation_score: 100,
+            engagement_rate: 0,
+            last_active: 0,
+            bump: 0,
+        };
+
+        // 40 interactions over 4 posts x 200 followers = 5%
+        stats.recompute_engagement().unwrap();
+        assert_eq!(stats.engagement_rate, 500);
+
+        stats.likes_received += 40;
+        stats.recompute_engagement().unwrap();
+        assert_eq!(stats.engagement_rate, 1_000);
+
+        stats.followers_count = 0;
+        stats.recompute_engagement().unwrap();
+        assert_eq!(stats.engagement_rate, 0);
+    }
+
+    #[test]
+    fn test_reply_depth_capped_at_one() {
+        let mut comment = Comment {
+            id: 0,
+            post_id: 0,
+            author: Pubkey::default(),
+            content: "top".to_string(),
+            likes_count: 0,
+            reply_to: None,
+            created_at: 0,
+            bump: 0,
+        };
+        assert!(comment.accepts_replies());
+
+        comment.reply_to = Some(Pubkey::new_unique());
+        assert!(!comment.accepts_replies());
+    }
+
+    #[test]
+    fn test_bookmark_count_follows_adds_and_removes() {
+        let mut bookmarked = post(false);
+        bookmarked.add_bookmark().unwrap();
+        bookmarked.add_bookmark().unwrap();
+        assert_eq!(bookmarked.bookmarks_count, 2);
+
+        bookmarked.remove_bookmark().unwrap();
+        bookmarked.remove_bookmark().unwrap();
+        assert_eq!(bookmarked.bookmarks_count, 0);
+        assert!(bookmarked.remove_bookmark().is_err());
+    }
+
+    #[test]
+    fn test_post_space_covers_serialized_media() {
+        let one = vec!["https://cdn.example/a.png".to_string()];
+        let ten: Vec<String> = (0..Post::MAX_MEDIA_URLS)
+            .map(|i| format!("https://{}{}", "a".repeat(Post::MAX_MEDIA_URL_LENGTH - 9), i))
+            .collect();
+        assert!(Post::validate_media_urls(&ten).is_ok());
+
+        for urls in [Vec::new(), one, ten] {
+            let mut buf = Vec::new();
+            urls.serialize(&mut buf).unwrap();
+            let serialized = buf.len();
+            assert_eq!(Post::media_space(&urls), serialized);
+            assert_eq!(Post::space_for(100, &urls), Post::space_for(100, &[]) - 4 + serialized);
+        }
+    }
+
+    #[test]
+    fn test_post_space_matches_serialized_layout() {
+        for media_count in [0, 1, Post::MAX_MEDIA_URLS] {
+            let post = Post {
+                content: "a".repeat(300),
+                // Fixed-allowance fields at their maximum
+                image_url: "i".repeat(128),
+                premium_key_ciphertext: vec![7; Post::MAX_PREMIUM_KEY_LENGTH],
+                repost_of: Some(Pubkey::new_unique()),
+                media_urls: vec!["https://cdn.example/a.png".to_string(); media_count],
+                ..Default::default()
+            };
+            let mut buf = Vec::new();
+            post.serialize(&mut buf).unwrap();
+            // Plus the 8-byte discriminator
+            assert_eq!(
+                Post::space_for(post.content.len(), &post.media_urls),
+                8 + buf.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_largest_post_fits_in_one_init() {
+        let urls = vec!["a".repeat(Post::MAX_MEDIA_URL_LENGTH); Post::MAX_MEDIA_URLS];
+        let content_len = GlobalState::DEFAULT_MAX_POST_LENGTH as usize;
+        // Accounts created through CPI are limited to 10 KiB
+        assert!(Post::space_for(content_len, &urls) <= 10 * 1024);
+    }
+
+    #[test]
+    fn test_media_url_too_long_rejected() {
+        let url = format!("https://{}", "a".repeat(Post::MAX_MEDIA_URL_LENGTH));
+        assert_eq!(
+            Post::validate_media_urls(&[url]).unwrap_err(),
+            error!(SolSocialError::MediaUrlTooLong)
+        );
+    }
+
+    #[test]
+    fn test_media_url_scheme_checked() {
+        for url in ["http://cdn.example/a.png", "javascript:alert(1)", ""] {
+            assert_eq!(
+                Post::validate_media_urls(&[url.to_string()]).unwrap_err(),
+                error!(SolSocialError::InvalidFileFormat)
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_media_post_accepted() {
+        let mut urls: Vec<String> = (0..Post::MAX_MEDIA_URLS)
+            .map(|i| format!("ipfs://bafy{}", i))
+            .collect();
+        urls[0] = format!("https://{}", "a".repeat(Post::MAX_MEDIA_URL_LENGTH - 8));
+        assert!(Post::validate_media_urls(&urls).is_ok());
+        assert!(Post::validate_media_urls(&[]).is_ok());
+
+        urls.push("ipfs://one-too-many".to_string());
+        assert!(Post::validate_media_urls(&urls).is_err());
+    }
+
+    #[test]
+    fn test_edit_accepted() {
+        assert!(post(false).validate_edit("hello, edited", 2_000).is_ok());
+    }
+
+    #[test]
+    fn test_empty_edit_rejected() {
+        assert_eq!(
+            post(false).validate_edit("", 2_000).unwrap_err(),
+            SolSocialError::InvalidPostContentLength.into()
+        );
+    }
+
+    #[test]
+    fn test_premium_edit_window_expires() {
+        let premium = post(true);
+        assert!(premium.validate_edit("fix", 1_000 + Post::PREMIUM_EDIT_WINDOW).is_ok());
+        assert!(premium.validate_edit("fix", 1_001 + Post::PREMIUM_EDIT_WINDOW).is_err());
+        // Free posts stay editable
+        assert!(post(false).validate_edit("fix", 1_001 + Post::PREMIUM_EDIT_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_mention_notification_per_user() {
+        let author = Pubkey::new_unique();
+        let mentions: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        Notification::validate_mentions(&mentions).unwrap();
+
+        let notifications: Vec<Notification> = mentions
+            .iter()
+            .enumerate()
+            .map(|(i, recipient)| Notification::mention(i as u64, *recipient, author, "gm @all", 1_000, 255))
+            .collect();
+
+        assert_eq!(notifications.len(), mentions.len());
+        for (notification, recipient) in notifications.iter().zip(&mentions) {
+            assert_eq!(notification.recipient, *recipient);
+            assert_eq!(notification.sender, author);
+            assert!(notification.notification_type == NotificationType::Mention);
+            assert!(!notification.read);
+        }
+    }
+
+    #[test]
+    fn test_mention_cap_enforced() {
+        let at_cap: Vec<Pubkey> = (0..Notification::MAX_MENTIONS).map(|_| Pubkey::new_unique()).collect();
+        assert!(Notification::validate_mentions(&at_cap).is_ok());
+
+        let mut over_cap = at_cap;
+        over_cap.push(Pubkey::new_unique());
+        assert!(Notification::validate_mentions(&over_cap).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_mention_rejected() {
+        let user = Pubkey::new_unique();
+        assert!(Notification::validate_mentions(&[user, Pubkey::new_unique(), user]).is_err());
+    }
+
+    #[test]
+    fn test_mention_content_truncated() {
+        let long = "a".repeat(Notification::MAX_CONTENT_LENGTH + 10);
+        let notification = Notification::mention(0, Pubkey::new_unique(), Pubkey::new_unique(), &long, 0, 0);
+        assert_eq!(notification.content.len(), Notification::MAX_CONTENT_LENGTH);
+    }
+
+    #[test]
+    fn test_recipient_marks_notification_read() {
+        let recipient = Pubkey::new_unique();
+        let mut notification = Notification::mention(0, recipient, Pubkey::new_unique(), "gm", 0, 0);
+
+        notification.mark_read(recipient).unwrap();
+        assert!(notification.read);
+    }
+
+    #[test]
+    fn test_non_recipient_cannot_mark_or_clear() {
+        let recipient = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let mut notification = Notification::mention(0, recipient, Pubkey::new_unique(), "gm", 0, 0);
+
+        assert!(notification.mark_read(stranger).is_err());
+        assert!(!notification.read);
+
+        notification.mark_read(recipient).unwrap();
+        assert!(notification.is_clearable_by(stranger).is_err());
+    }
+
+    fn revenue_pool() -> RevenuePool {
+        RevenuePool::default()
+    }
+
+    #[test]
+    fn test_initialized_pool_starts_empty() {
+        let creator = Pubkey::new_unique();
+        let mut pool = revenue_pool();
+        pool.initialize(creator, 254);
+
+        assert_eq!(pool.creator, creator);
+        assert_eq!(pool.platform_fee_percentage, RevenuePool::DEFAULT_PLATFORM_FEE_PERCENTAGE);
+        assert_eq!(pool.pending_revenue, 0);
+        assert_eq!(pool.bump, 254);
+    }
+
+    #[test]
+    fn test_one_distribution_cycle() {
+        let creator = profile();
+        let mut pool = revenue_pool();
+        pool.initialize(creator.authority, 254);
+        pool.pending_revenue = 1_000;
+
+        let holder_rewards = crate::utils::revenue_share::calculate_holder_rewards(
+            pool.pending_revenue,
+            creator.revenue_share_percentage,
+            pool.platform_fee_percentage,
+        )
+        .unwrap();
+        pool.settle_distribution(holder_rewards, 1_700_000_000).unwrap();
+
+        // 50% to the creator, 5% to the platform, the rest held for holders
+        assert_eq!(pool.holder_rewards_pool, 450);
+        assert_eq!(pool.pending_revenue, 0);
+        assert_eq!(pool.total_distributed, 1_000);
+        assert_eq!(pool.last_distribution_timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_clear_only_read_notifications() {
+        let recipient = Pubkey::new_unique();
+        let mut notifications: Vec<Notification> = (0..4)
+            .map(|i| Notification::mention(i, recipient, Pubkey::new_unique(), "gm", 0, 0))
+            .collect();
+        notifications[1].mark_read(recipient).unwrap();
+        notifications[3].mark_read(recipient).unwrap();
+
+        let remaining: Vec<u64> = notifications
+            .iter()
+            .filter(|n| !n.is_clearable_by(recipient).unwrap())
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(remaining, vec![0, 2]);
+    }
+
+    fn holding(subject: Pubkey, amount: u64) -> KeyHolding {
+        KeyHolding {
+            owner: Pubkey::new_unique(),
+            subject,
+            amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_partial_key_transfer() {
+        let subject = Pubkey::new_unique();
+        let mut sender = holding(subject, 3);
+        let mut recipient = holding(subject, 1);
+
+        sender.send_to(&mut recipient, 2, 50).unwrap();
+        assert_eq!((sender.amount, recipient.amount), (1, 3));
+        assert_eq!(recipient.last_trade_timestamp, 50);
+        assert_eq!(
+            sender.send_to(&mut recipient, 2, 60).unwrap_err(),
+            SolSocialError::InsufficientSharesToSell.into()
+        );
+    }
+
+    #[test]
+    fn test_key_transfer_to_new_holder() {
+        let subject = Pubkey::new_unique();
+        let mut sender = holding(subject, 1);
+        let mut recipient = KeyHolding::default();
+        recipient.ensure_initialized(Pubkey::new_unique(), subject, 255);
+
+        sender.send_to(&mut recipient, 1, 50).unwrap();
+        assert_eq!((sender.amount, recipient.amount), (0, 1));
+        assert_eq!(recipient.original_first_purchase_at, 50);
+    }
+
+    #[test]
+    fn test_rebuy_keeps_original_first_purchase() {
+        let mut holding = holding(Pubkey::new_unique(), 0);
+        holding.record_buy(2, 2_000, 10).unwrap();
+        holding.record_buy(1, 1_500, 20).unwrap();
+        assert_eq!(holding.first_purchase_at, 10);
+        assert_eq!(holding.original_first_purchase_at, 10);
+
+        // Full exit, then back in
+        holding.amount = 0;
+        holding.record_buy(1, 1_000, 30).unwrap();
+        assert_eq!(holding.first_purchase_at, 30);
+        assert_eq!(holding.original_first_purchase_at, 10);
+        assert_eq!(holding.purchase_count, 3);
+    }
+
+    #[test]
+    fn test_key_transfer_rejects_self_and_other_subjects() {
+        let subject = Pubkey::new_unique();
+        let mut sender = holding(subject, 2);
+        let mut same_wallet = holding(subject, 0);
+        same_wallet.owner = sender.owner;
+        assert_eq!(
+            sender.send_to(&mut same_wallet, 1, 50).unwrap_err(),
+            SolSocialError::CannotTransferKeysToSelf.into()
+        );
+
+        let mut other_subject = holding(Pubkey::new_unique(), 0);
+        assert_eq!(
+            sender.send_to(&mut other_subject, 1, 50).unwrap_err(),
+            SolSocialError::KeyOwnerMismatch.into()
+        );
+        assert_eq!(sender.amount, 2);
+    }
+
+    fn interaction() -> PostInteraction {
+        PostInteraction {
+            user: Pubkey::default(),
+            post: Pubkey::default(),
+            interactions: 0,
+            timestamp: 0,
+            reward_charged: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_like_then_unlike_nets_zero() {
+        let mut interaction = interaction();
+        let mut user_balance = 1_000u64;
+        let mut escrow = 0u64;
+
+        let reward = 25;
+        user_balance -= reward;
+        escrow += reward;
+        interaction.record_reward(reward);
+
+        let refund = interaction.take_unreleased_reward();
+        escrow -= refund;
+        user_balance += refund;
+
+        assert_eq!(user_balance, 1_000);
+        assert_eq!(escrow, 0);
+        assert_eq!(interaction.reward_charged, 0);
+    }
+
+    #[test]
+    fn test_like_share_bookmark_toggle_independently() {
+        let mut interaction = interaction();
+
+        interaction.insert(InteractionType::Like, 1);
+        interaction.insert(InteractionType::Bookmark, 2);
+        assert!(interaction.has(InteractionType::Like));
+        assert!(!interaction.has(InteractionType::Share));
+        assert!(interaction.has(InteractionType::Bookmark));
+
+        interaction.insert(InteractionType::Share, 3);
+        interaction.remove(InteractionType::Like, 4);
+        assert!(!interaction.has(InteractionType::Like));
+        assert!(interaction.has(InteractionType::Share));
+        assert!(interaction.has(InteractionType::Bookmark));
+
+        interaction.remove(InteractionType::Bookmark, 5);
+        assert!(interaction.has(InteractionType::Share));
+        assert!(!interaction.has(InteractionType::Bookmark));
+        assert_eq!(interaction.timestamp, 5);
+    }
+
+    #[test]
+    fn test_removing_unset_interaction_is_noop() {
+        let mut interaction = interaction();
+        interaction.insert(InteractionType::Share, 1);
+
+        interaction.remove(InteractionType::Like, 2);
+        assert_eq!(interaction.interactions, InteractionType::Share.flag());
+    }
+
+    #[test]
+    fn test_top_tipper_accumulates_repeat_tips() {
+        let mut top_tipper = TopTipper {
+            post: Pubkey::default(),
+            tipper: Pubkey::default(),
+            total_tipped: 0,
+            tip_count: 0,
+            last_tipped_at: 0,
+            bump: 0,
+        };
+
+        top_tipper.record_tip(1_500, 10).unwrap();
+        top_tipper.record_tip(2_500, 20).unwrap();
+
+        assert_eq!(top_tipper.total_tipped, 4_000);
+        assert_eq!(top_tipper.tip_count, 2);
+        assert_eq!(top_tipper.last_tipped_at, 20);
+    }
+
+    #[test]
+    fn test_unlike_after_release_cannot_drain_other_deposits() {
+        let mut alice = interaction();
+        let mut bob = interaction();
+        let mut escrow = 0u64;
+
+        alice.record_reward(25);
+        escrow += 25;
+
+        // The creator releases Alice's deposit, then Bob likes into the same escrow
+        escrow -= alice.take_unreleased_reward();
+        bob.record_reward(40);
+        escrow += 40;
+
+        // Alice's unlike finds nothing of hers left; Bob's deposit stays put
+        assert_eq!(alice.take_unreleased_reward(), 0);
+        assert_eq!(escrow, 40);
+        assert_eq!(bob.take_unreleased_reward(), 40);
+    }
+
+    #[test]
+    fn test_duplicate_post_rejected_inside_window() {
+        let author = Pubkey::new_unique();
+        let hash = anchor_lang::solana_program::hash::hash(b"gm").to_bytes();
+        let mut recent = RecentPostHash {
+            author: Pubkey::default(),
+            content_hash: [0u8; 32],
+            last_posted_at: 0,
+            bump: 0,
+        };
+
+        recent.record_post(author, hash, 1_000).unwrap();
+        assert_eq!(
+            recent.record_post(author, hash, 1_001).unwrap_err(),
+            SolSocialError::SpamDetectionTriggered.into()
+        );
+        assert_eq!(recent.last_posted_at, 1_000);
+
+        recent.record_post(author, hash, 1_000 + RecentPostHash::DUPLICATE_WINDOW).unwrap();
+        assert_eq!(recent.last_posted_at, 1_000 + RecentPostHash::DUPLICATE_WINDOW);
+    }
+
+    #[test]
+    fn test_follow_counts_move_together() {
+        let (mut follower, mut following) = (profile(), profile());
+        UserProfile::record_follow(&mut follower, &mut following, true).unwrap();
+        assert_eq!((follower.following_count, following.followers_count), (1, 1));
+
+        UserProfile::record_follow(&mut follower, &mut following, false).unwrap();
+        assert_eq!((follower.following_count, following.followers_count), (0, 0));
+    }
+
+    #[test]
+    fn test_unfollow_on_desynced_counter_underflows() {
+        let (mut follower, mut following) = (profile(), profile());
+        follower.following_count = 0;
+        following.followers_count = 0;
+        assert_eq!(
+            UserProfile::record_follow(&mut follower, &mut following, false).unwrap_err(),
+            SolSocialError::MathUnderflow.into()
+        );
+    }
 }
-```
\ No newline at end of file
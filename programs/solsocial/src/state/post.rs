@@ -1,6 +1,7 @@
-```rust
 use anchor_lang::prelude::*;
 
+use super::GlobalState;
+
 #[account]
 pub struct Post {
     pub author: Pubkey,
@@ -17,12 +18,11 @@ pub struct Post {
 }
 
 impl Post {
-    pub const MAX_CONTENT_LENGTH: usize = 280;
     pub const MAX_MEDIA_HASH_LENGTH: usize = 64;
     
     pub const SPACE: usize = 8 + // discriminator
         32 + // author
-        4 + Self::MAX_CONTENT_LENGTH + // content (string)
+        4 + GlobalState::DEFAULT_MAX_POST_LENGTH as usize + // content (string)
         8 + // timestamp
         8 + // likes
         8 + // shares
@@ -37,6 +37,7 @@ impl Post {
         &mut self,
         author: Pubkey,
         content: String,
+        max_content_length: usize,
         timestamp: i64,
         token_price: u64,
         is_premium: bool,
@@ -45,7 +46,7 @@ impl Post {
         bump: u8,
     ) -> Result<()> {
         require!(
-            content.len() <= Self::MAX_CONTENT_LENGTH,
+            content.len() <= max_content_length,
             SolSocialError::ContentTooLong
         );
 
@@ -100,47 +101,6 @@ impl Post {
     }
 }
 
-#[account]
-pub struct PostInteraction {
-    pub user: Pubkey,
-    pub post: Pubkey,
-    pub interaction_type: InteractionType,
-    pub timestamp: i64,
-    pub bump: u8,
-}
-
-impl PostInteraction {
-    pub const SPACE: usize = 8 + // discriminator
-        32 + // user
-        32 + // post
-        1 + // interaction_type
-        8 + // timestamp
-        1; // bump
-
-    pub fn initialize(
-        &mut self,
-        user: Pubkey,
-        post: Pubkey,
-        interaction_type: InteractionType,
-        timestamp: i64,
-        bump: u8,
-    ) -> Result<()> {
-        self.user = user;
-        self.post = post;
-        self.interaction_type = interaction_type;
-        self.timestamp = timestamp;
-        self.bump = bump;
-        Ok(())
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum InteractionType {
-    Like,
-    Share,
-    Tip,
-}
-
 #[account]
 pub struct PostStats {
     pub post: Pubkey,
@@ -211,4 +171,3 @@ pub enum SolSocialError {
     #[msg("Premium content access required")]
     PremiumAccessRequired,
 }
-```
\ No newline at end of file
@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use std::cmp;
 
@@ -8,8 +7,14 @@ use std::cmp;
 pub const CURVE_FACTOR: u64 = 1_000_000; // Scaling factor for curve calculations
 pub const BASE_PRICE: u64 = 1_000; // Base price in lamports (0.000001 SOL)
 pub const MAX_SUPPLY: u64 = 1_000_000_000; // Maximum token supply (1B tokens)
+/// Largest x for which x^3 fits in u128 (floor of the cube root of u128::MAX)
+pub const MAX_CUBE_INPUT: u64 = 6_981_463_658_331;
 pub const PRICE_PRECISION: u64 = 1_000_000; // 6 decimal places precision
 
+pub const CURVE_TYPE_QUADRATIC: u8 = 0;
+pub const CURVE_TYPE_EXPONENTIAL: u8 = 1; // curve_factor is the per-token growth in basis points
+const EXP_SCALE: u128 = 1_000_000_000_000; // Fixed-point scale for exponential growth
+
 #[derive(Debug, Clone, Copy)]
 pub struct BondingCurveParams {
     pub base_price: u64,
@@ -31,13 +36,15 @@ impl Default for BondingCurveParams {
 pub fn calculate_price(supply: u64, params: &BondingCurveParams) -> Result<u64> {
     require!(supply <= params.max_supply, SolSocialError::SupplyExceedsMax);
     
-    let supply_squared = supply
-        .checked_mul(supply)
+    let supply_squared = (supply as u128)
+        .checked_mul(supply as u128)
         .ok_or(SolSocialError::MathOverflow)?;
     
     let curve_component = supply_squared
-        .checked_div(params.curve_factor)
+        .checked_div(params.curve_factor as u128)
         .ok_or(SolSocialError::MathOverflow)?;
+    let curve_component = u64::try_from(curve_component)
+        .map_err(|_| error!(SolSocialError::MathOverflow))?;
     
     let price = params.base_price
         .checked_add(curve_component)
@@ -87,6 +94,79 @@ pub fn calculate_sell_proceeds(
     Ok(proceeds)
 }
 
+/// Lamports an escrow must hold to buy back every token down to zero supply
+pub fn calculate_reserve(supply: u64, params: &BondingCurveParams) -> Result<u64> {
+    calculate_reserve_with_mode(supply, CURVE_TYPE_QUADRATIC, params)
+}
+
+/// `calculate_reserve` for the curve selected by `curve_type`
+pub fn calculate_reserve_with_mode(
+    supply: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    match curve_type {
+        CURVE_TYPE_QUADRATIC => calculate_integral(0, supply, params),
+        CURVE_TYPE_EXPONENTIAL => calculate_geometric_sum(0, supply, params),
+        _ => err!(SolSocialError::InvalidCurveParams),
+    }
+}
+
+/// Buy cost priced as the change in `calculate_reserve`. Unlike summing
+/// per-trade integrals, the rounding telescopes, so an escrow funded this way
+/// always holds exactly the reserve for the current supply.
+pub fn calculate_reserve_buy_cost(
+    current_supply: u64,
+    amount: u64,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    calculate_reserve_buy_cost_with_mode(current_supply, amount, CURVE_TYPE_QUADRATIC, params)
+}
+
+/// `calculate_reserve_buy_cost` for the curve selected by `curve_type`
+pub fn calculate_reserve_buy_cost_with_mode(
+    current_supply: u64,
+    amount: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    let new_supply = current_supply
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    require!(new_supply <= params.max_supply, SolSocialError::SupplyExceedsMax);
+
+    calculate_reserve_with_mode(new_supply, curve_type, params)?
+        .checked_sub(calculate_reserve_with_mode(current_supply, curve_type, params)?)
+        .ok_or_else(|| error!(SolSocialError::MathOverflow))
+}
+
+/// Sell proceeds priced as the change in `calculate_reserve`; see `calculate_reserve_buy_cost`.
+pub fn calculate_reserve_sell_proceeds(
+    current_supply: u64,
+    amount: u64,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    calculate_reserve_sell_proceeds_with_mode(current_supply, amount, CURVE_TYPE_QUADRATIC, params)
+}
+
+/// `calculate_reserve_sell_proceeds` for the curve selected by `curve_type`
+pub fn calculate_reserve_sell_proceeds_with_mode(
+    current_supply: u64,
+    amount: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    let new_supply = current_supply
+        .checked_sub(amount)
+        .ok_or(SolSocialError::InsufficientSupply)?;
+
+    calculate_reserve_with_mode(current_supply, curve_type, params)?
+        .checked_sub(calculate_reserve_with_mode(new_supply, curve_type, params)?)
+        .ok_or_else(|| error!(SolSocialError::MathOverflow))
+}
+
 /// Calculate the integral of the bonding curve between two supply points
 /// Integral of (base_price + x^2/curve_factor) from a to b
 /// = base_price * (b - a) + (b^3 - a^3) / (3 * curve_factor)
@@ -107,6 +187,7 @@ fn calculate_integral(
         .ok_or(SolSocialError::MathOverflow)?;
     
     // Quadratic component: (to^3 - from^3) / (3 * curve_factor)
+    // Cubes are taken in u128; only the final component has to fit in u64
     let to_cubed = calculate_cube(to_supply)?;
     let from_cubed = calculate_cube(from_supply)?;
     
@@ -114,13 +195,15 @@ fn calculate_integral(
         .checked_sub(from_cubed)
         .ok_or(SolSocialError::MathOverflow)?;
     
-    let curve_divisor = params.curve_factor
+    let curve_divisor = (params.curve_factor as u128)
         .checked_mul(3)
         .ok_or(SolSocialError::MathOverflow)?;
     
     let quadratic_component = cube_diff
         .checked_div(curve_divisor)
         .ok_or(SolSocialError::MathOverflow)?;
+    let quadratic_component = u64::try_from(quadratic_component)
+        .map_err(|_| error!(SolSocialError::MathOverflow))?;
     
     let total_cost = linear_component
         .checked_add(quadratic_component)
@@ -130,7 +213,8 @@ fn calculate_integral(
 }
 
 /// Calculate x^3 with overflow protection
-fn calculate_cube(x: u64) -> Result<u64> {
+fn calculate_cube(x: u64) -> Result<u128> {
+    let x = x as u128;
     let x_squared = x
         .checked_mul(x)
         .ok_or(SolSocialError::MathOverflow)?;
@@ -142,9 +226,148 @@ fn calculate_cube(x: u64) -> Result<u64> {
     Ok(x_cubed)
 }
 
+/// Calculate the price at a given supply for the curve selected by `curve_type`
+pub fn calculate_price_with_mode(
+    supply: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    match curve_type {
+        CURVE_TYPE_QUADRATIC => calculate_price(supply, params),
+        CURVE_TYPE_EXPONENTIAL => {
+            require!(supply <= params.max_supply, SolSocialError::SupplyExceedsMax);
+            let growth = calculate_growth(supply, params)?;
+            let price = (params.base_price as u128)
+                .checked_mul(growth)
+                .ok_or(SolSocialError::MathOverflow)?
+                .checked_div(EXP_SCALE)
+                .ok_or(SolSocialError::MathOverflow)?;
+            u64::try_from(price).map_err(|_| error!(SolSocialError::MathOverflow))
+        }
+        _ => err!(SolSocialError::InvalidCurveParams),
+    }
+}
+
+/// Calculate the cost to buy a specific amount of tokens for the selected curve
+pub fn calculate_buy_cost_with_mode(
+    current_supply: u64,
+    amount: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    match curve_type {
+        CURVE_TYPE_QUADRATIC => calculate_buy_cost(current_supply, amount, params),
+        CURVE_TYPE_EXPONENTIAL => {
+            require!(amount > 0, SolSocialError::InvalidAmount);
+            let new_supply = current_supply
+                .checked_add(amount)
+                .ok_or(SolSocialError::MathOverflow)?;
+            require!(new_supply <= params.max_supply, SolSocialError::SupplyExceedsMax);
+            calculate_geometric_sum(current_supply, new_supply, params)
+        }
+        _ => err!(SolSocialError::InvalidCurveParams),
+    }
+}
+
+/// Calculate the proceeds from selling a specific amount of tokens for the selected curve
+pub fn calculate_sell_proceeds_with_mode(
+    current_supply: u64,
+    amount: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    match curve_type {
+        CURVE_TYPE_QUADRATIC => calculate_sell_proceeds(current_supply, amount, params),
+        CURVE_TYPE_EXPONENTIAL => {
+            require!(amount > 0, SolSocialError::InvalidAmount);
+            require!(amount <= current_supply, SolSocialError::InsufficientSupply);
+            let new_supply = current_supply
+                .checked_sub(amount)
+                .ok_or(SolSocialError::MathOverflow)?;
+            calculate_geometric_sum(new_supply, current_supply, params)
+        }
+        _ => err!(SolSocialError::InvalidCurveParams),
+    }
+}
+
+/// (1 + curve_factor / 10_000)^supply in EXP_SCALE fixed point, by repeated squaring
+fn calculate_growth(supply: u64, params: &BondingCurveParams) -> Result<u128> {
+    let ratio = EXP_SCALE
+        .checked_add(
+            (params.curve_factor as u128)
+                .checked_mul(EXP_SCALE)
+                .ok_or(SolSocialError::MathOverflow)?
+                / 10_000,
+        )
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    let mut result = EXP_SCALE;
+    let mut base = ratio;
+    let mut exponent = supply;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result
+                .checked_mul(base)
+                .ok_or(SolSocialError::MathOverflow)?
+                / EXP_SCALE;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base
+                .checked_mul(base)
+                .ok_or(SolSocialError::MathOverflow)?
+                / EXP_SCALE;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Sum of exponential prices for supply in [from_supply, to_supply)
+/// = base_price * (r^to - r^from) / (r - 1)
+fn calculate_geometric_sum(
+    from_supply: u64,
+    to_supply: u64,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    require!(to_supply >= from_supply, SolSocialError::InvalidRange);
+
+    if params.curve_factor == 0 {
+        return params.base_price
+            .checked_mul(to_supply - from_supply)
+            .ok_or_else(|| error!(SolSocialError::MathOverflow));
+    }
+
+    let growth_diff = calculate_growth(to_supply, params)?
+        .checked_sub(calculate_growth(from_supply, params)?)
+        .ok_or(SolSocialError::MathOverflow)?;
+    let ratio_minus_one = (params.curve_factor as u128)
+        .checked_mul(EXP_SCALE)
+        .ok_or(SolSocialError::MathOverflow)?
+        / 10_000;
+
+    let total = (params.base_price as u128)
+        .checked_mul(growth_diff)
+        .ok_or(SolSocialError::MathOverflow)?
+        .checked_div(ratio_minus_one)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    u64::try_from(total).map_err(|_| error!(SolSocialError::MathOverflow))
+}
+
 /// Calculate the market cap at a given supply
 pub fn calculate_market_cap(supply: u64, params: &BondingCurveParams) -> Result<u64> {
-    let price = calculate_price(supply, params)?;
+    calculate_market_cap_with_mode(supply, CURVE_TYPE_QUADRATIC, params)
+}
+
+/// `calculate_market_cap` for the curve selected by `curve_type`
+pub fn calculate_market_cap_with_mode(
+    supply: u64,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    let price = calculate_price_with_mode(supply, curve_type, params)?;
     let market_cap = supply
         .checked_mul(price)
         .ok_or(SolSocialError::MathOverflow)?;
@@ -201,7 +424,18 @@ pub fn calculate_price_impact(
     is_buy: bool,
     params: &BondingCurveParams,
 ) -> Result<u64> {
-    let current_price = calculate_price(current_supply, params)?;
+    calculate_price_impact_with_mode(current_supply, trade_amount, is_buy, CURVE_TYPE_QUADRATIC, params)
+}
+
+/// `calculate_price_impact` for the curve selected by `curve_type`
+pub fn calculate_price_impact_with_mode(
+    current_supply: u64,
+    trade_amount: u64,
+    is_buy: bool,
+    curve_type: u8,
+    params: &BondingCurveParams,
+) -> Result<u64> {
+    let current_price = calculate_price_with_mode(current_supply, curve_type, params)?;
     
     let new_supply = if is_buy {
         current_supply
@@ -213,7 +447,7 @@ pub fn calculate_price_impact(
             .ok_or(SolSocialError::MathOverflow)?
     };
     
-    let new_price = calculate_price(new_supply, params)?;
+    let new_price = calculate_price_with_mode(new_supply, curve_type, params)?;
     
     let price_diff = if new_price > current_price {
         new_price - current_price
@@ -231,13 +465,40 @@ pub fn calculate_price_impact(
     Ok(impact)
 }
 
-/// Calculate trading fees based on trade size and price impact
+/// Volume tiers as (minimum cumulative volume in lamports, fee in basis points),
+/// ordered by volume
+pub const FEE_TIERS: [(u64, u64); 4] = [
+    (0, 100),                      // 1%
+    (100_000_000_000, 75),         // 100 SOL
+    (1_000_000_000_000, 50),       // 1,000 SOL
+    (10_000_000_000_000, 25),      // 10,000 SOL
+];
+
+/// Base fee for a trader or creator with `volume` cumulative trading volume
+/// (`UserProfile.total_volume`); the highest tier reached wins.
+pub fn resolve_fee_bps(volume: u64) -> u64 {
+    FEE_TIERS
+        .iter()
+        .rev()
+        .find(|(min_volume, _)| volume >= *min_volume)
+        .map(|(_, fee_bps)| *fee_bps)
+        .unwrap_or(FEE_TIERS[0].1)
+}
+
+/// Calculate trading fees based on trade size, price impact and the trader's volume tier.
+/// `configured_fee_bps` is the admin-set rate, paid in full at the first tier; higher
+/// tiers pay it scaled by their share of the first tier's fee.
 pub fn calculate_trading_fee(
     trade_value: u64,
     price_impact: u64,
-    base_fee_bps: u64, // Base fee in basis points (100 = 1%)
+    cumulative_volume: u64,
+    configured_fee_bps: u64,
 ) -> Result<u64> {
     // Base fee
+    let base_fee_bps = configured_fee_bps
+        .checked_mul(resolve_fee_bps(cumulative_volume))
+        .ok_or(SolSocialError::MathOverflow)?
+        / FEE_TIERS[0].1;
     let base_fee = trade_value
         .checked_mul(base_fee_bps)
         .ok_or(SolSocialError::MathOverflow)?
@@ -268,7 +529,11 @@ pub fn validate_curve_params(params: &BondingCurveParams) -> Result<()> {
     require!(params.base_price > 0, SolSocialError::InvalidCurveParams);
     require!(params.curve_factor > 0, SolSocialError::InvalidCurveParams);
     require!(params.max_supply > 0, SolSocialError::InvalidCurveParams);
-    require!(params.max_supply <= u64::MAX / params.max_supply, SolSocialError::InvalidCurveParams);
+    // The curve is never evaluated past max_supply, so it is enough that the
+    // integral's cube and the spot price are representable there.
+    require!(params.max_supply <= MAX_CUBE_INPUT, SolSocialError::InvalidCurveParams);
+    calculate_price(params.max_supply, params)
+        .map_err(|_| error!(SolSocialError::InvalidCurveParams))?;
     
     Ok(())
 }
@@ -323,6 +588,168 @@ mod tests {
         assert!(buy_cost >= sell_proceeds);
     }
 
+    #[test]
+    fn test_buy_sell_round_trip_is_exact() {
+        let params = BondingCurveParams::default();
+
+        for &(supply, amount) in &[(0u64, 1u64), (1, 1), (7, 3), (999, 1), (10_000, 1_000), (100_000, 10_000)] {
+            let buy_cost = calculate_buy_cost(supply, amount, &params).unwrap();
+            let sell_proceeds = calculate_sell_proceeds(supply + amount, amount, &params).unwrap();
+            assert_eq!(buy_cost, sell_proceeds);
+        }
+    }
+
+    #[test]
+    fn test_steep_and_flat_creator_curves() {
+        let steep = BondingCurveParams {
+            base_price: 10_000,
+            curve_factor: 1_000,
+            max_supply: MAX_SUPPLY,
+        };
+        let flat = BondingCurveParams {
+            base_price: 10_000,
+            curve_factor: 100_000_000,
+            max_supply: MAX_SUPPLY,
+        };
+        assert!(validate_curve_params(&steep).is_ok());
+        assert!(validate_curve_params(&flat).is_ok());
+
+        assert_eq!(calculate_price(0, &steep).unwrap(), calculate_price(0, &flat).unwrap());
+        assert!(calculate_price(5_000, &steep).unwrap() > calculate_price(5_000, &flat).unwrap());
+
+        // The flat curve stays close to its base price over the same range
+        let flat_cost = calculate_buy_cost(0, 1_000, &flat).unwrap();
+        assert_eq!(flat_cost, 10_000 * 1_000 + 1_000u64.pow(3) / 300_000_000);
+        assert!(calculate_buy_cost(0, 1_000, &steep).unwrap() > flat_cost);
+    }
+
+    #[test]
+    fn test_exponential_vs_quadratic_costs() {
+        let params = BondingCurveParams {
+            base_price: BASE_PRICE,
+            curve_factor: 100, // 1% growth per token on the exponential curve
+            max_supply: MAX_SUPPLY,
+        };
+
+        let quadratic = calculate_buy_cost_with_mode(0, 100, CURVE_TYPE_QUADRATIC, &params).unwrap();
+        let exponential = calculate_buy_cost_with_mode(0, 100, CURVE_TYPE_EXPONENTIAL, &params).unwrap();
+
+        assert_eq!(quadratic, calculate_buy_cost(0, 100, &params).unwrap());
+        assert!(exponential > quadratic);
+        assert_eq!(calculate_price_with_mode(0, CURVE_TYPE_EXPONENTIAL, &params).unwrap(), BASE_PRICE);
+        assert_eq!(calculate_price_with_mode(1, CURVE_TYPE_EXPONENTIAL, &params).unwrap(), BASE_PRICE * 101 / 100);
+    }
+
+    #[test]
+    fn test_curves_are_monotonic() {
+        let params = BondingCurveParams {
+            base_price: BASE_PRICE,
+            curve_factor: 50,
+            max_supply: MAX_SUPPLY,
+        };
+
+        for curve_type in [CURVE_TYPE_QUADRATIC, CURVE_TYPE_EXPONENTIAL] {
+            let mut last_price = 0;
+            let mut last_cost = 0;
+            for supply in 0..500 {
+                let price = calculate_price_with_mode(supply, curve_type, &params).unwrap();
+                let cost = calculate_buy_cost_with_mode(supply, 1, curve_type, &params).unwrap();
+                assert!(price >= last_price);
+                assert!(cost >= last_cost);
+                last_price = price;
+                last_cost = cost;
+            }
+        }
+    }
+
+    #[test]
+    fn test_exponential_round_trip_is_exact() {
+        let params = BondingCurveParams {
+            base_price: BASE_PRICE,
+            curve_factor: 25,
+            max_supply: MAX_SUPPLY,
+        };
+
+        let buy = calculate_buy_cost_with_mode(40, 10, CURVE_TYPE_EXPONENTIAL, &params).unwrap();
+        let sell = calculate_sell_proceeds_with_mode(50, 10, CURVE_TYPE_EXPONENTIAL, &params).unwrap();
+        assert_eq!(buy, sell);
+    }
+
+    #[test]
+    fn test_unknown_curve_type_rejected() {
+        let params = BondingCurveParams::default();
+        assert!(calculate_price_with_mode(10, 7, &params).is_err());
+    }
+
+    #[test]
+    fn test_costs_at_realistic_supplies_do_not_overflow() {
+        let params = BondingCurveParams::default();
+
+        // supply^3 alone overflows u64 past ~2.6M
+        let at_million = calculate_buy_cost(1_000_000, 1, &params).unwrap();
+        let after_million = calculate_buy_cost(1_000_001, 1, &params).unwrap();
+        assert!(after_million > at_million);
+        assert_eq!(
+            calculate_sell_proceeds(1_000_001, 1, &params).unwrap(),
+            at_million
+        );
+
+        let near_max = calculate_buy_cost(MAX_SUPPLY - 2, 1, &params).unwrap();
+        let at_max = calculate_buy_cost(MAX_SUPPLY - 1, 1, &params).unwrap();
+        assert!(at_max > near_max);
+        assert!(near_max > after_million);
+    }
+
+    #[test]
+    fn test_validate_accepts_supplies_the_old_guard_rejected() {
+        let mut params = BondingCurveParams::default();
+        assert!(validate_curve_params(&params).is_ok());
+
+        params.max_supply = 10_000_000_000;
+        assert!(validate_curve_params(&params).is_ok());
+
+        params.max_supply = MAX_CUBE_INPUT;
+        params.curve_factor = 10_000_000;
+        assert!(validate_curve_params(&params).is_ok());
+        assert!(calculate_cube(MAX_CUBE_INPUT).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unrepresentable_curves() {
+        let mut params = BondingCurveParams::default();
+        params.max_supply = MAX_CUBE_INPUT + 1;
+        params.curve_factor = u64::MAX;
+        assert!(calculate_cube(MAX_CUBE_INPUT + 1).is_err());
+        assert!(validate_curve_params(&params).is_err());
+
+        // Cube fits but the spot price at max_supply does not
+        params.max_supply = MAX_CUBE_INPUT;
+        params.curve_factor = 1;
+        assert!(validate_curve_params(&params).is_err());
+    }
+
+    #[test]
+    fn test_escrow_matches_reserve_after_buys_and_sells() {
+        let params = BondingCurveParams::default();
+        let mut supply = 0u64;
+        let mut escrow = 0u64;
+
+        for &amount in &[1u64, 7, 333, 1_000, 2, 50_001] {
+            escrow += calculate_reserve_buy_cost(supply, amount, &params).unwrap();
+            supply += amount;
+            assert_eq!(escrow, calculate_reserve(supply, &params).unwrap());
+        }
+        for &amount in &[3u64, 40_000, 1, 999] {
+            escrow -= calculate_reserve_sell_proceeds(supply, amount, &params).unwrap();
+            supply -= amount;
+            assert_eq!(escrow, calculate_reserve(supply, &params).unwrap());
+        }
+
+        // Every holder can still exit
+        escrow -= calculate_reserve_sell_proceeds(supply, supply, &params).unwrap();
+        assert_eq!(escrow, 0);
+    }
+
     #[test]
     fn test_price_impact() {
         let params = BondingCurveParams::default();
@@ -332,5 +759,38 @@ mod tests {
         let impact = calculate_price_impact(supply, trade_amount, true, &params).unwrap();
         assert!(impact > 0);
     }
+
+    #[test]
+    fn test_fee_tier_boundaries() {
+        assert_eq!(resolve_fee_bps(0), 100);
+        assert_eq!(resolve_fee_bps(100_000_000_000 - 1), 100);
+        assert_eq!(resolve_fee_bps(100_000_000_000), 75);
+        assert_eq!(resolve_fee_bps(1_000_000_000_000 - 1), 75);
+        assert_eq!(resolve_fee_bps(1_000_000_000_000), 50);
+        assert_eq!(resolve_fee_bps(10_000_000_000_000), 25);
+        assert_eq!(resolve_fee_bps(u64::MAX), 25);
+    }
+
+    #[test]
+    fn test_trading_fee_decreases_with_volume() {
+        let trade_value = 1_000_000_000;
+        let fees: Vec<u64> = FEE_TIERS
+            .iter()
+            .map(|(min_volume, _)| calculate_trading_fee(trade_value, 0, *min_volume, FEE_TIERS[0].1).unwrap())
+            .collect();
+        assert_eq!(fees, vec![10_000_000, 7_500_000, 5_000_000, 2_500_000]);
+        assert!(fees.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_tiers_scale_configured_fee() {
+        let trade_value = 1_000_000_000;
+        // A 5% configured rate drops to 3.75%, 2.5% and 1.25% as volume grows
+        let fees: Vec<u64> = FEE_TIERS
+            .iter()
+            .map(|(min_volume, _)| calculate_trading_fee(trade_value, 0, *min_volume, 500).unwrap())
+            .collect();
+        assert_eq!(fees, vec![50_000_000, 37_500_000, 25_000_000, 12_500_000]);
+        assert_eq!(calculate_trading_fee(trade_value, 0, u64::MAX, 0).unwrap(), 0);
+    }
 }
-```
\ No newline at end of file
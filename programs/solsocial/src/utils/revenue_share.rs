@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
@@ -51,6 +50,26 @@ pub fn calculate_holder_rewards(
     Ok(holder_rewards)
 }
 
+/// Splits `total_revenue` into the creator, platform and holder shares. Holders get
+/// whatever the two percentage shares leave, rounding dust included.
+pub fn split_revenue(
+    total_revenue: u64,
+    creator_share_percentage: u8,
+    platform_fee_percentage: u8,
+) -> Result<(u64, u64, u64)> {
+    validate_revenue_distribution_params(creator_share_percentage, platform_fee_percentage)?;
+
+    let creator_share = calculate_creator_share(total_revenue, creator_share_percentage)?;
+    let platform_share = calculate_platform_share(total_revenue, platform_fee_percentage)?;
+    let holder_rewards = calculate_holder_rewards(
+        total_revenue,
+        creator_share_percentage,
+        platform_fee_percentage,
+    )?;
+
+    Ok((creator_share, platform_share, holder_rewards))
+}
+
 pub fn distribute_revenue_to_creator<'info>(
     creator_token_account: &Account<'info, TokenAccount>,
     vault_token_account: &Account<'info, TokenAccount>,
@@ -123,18 +142,88 @@ pub fn calculate_individual_holder_reward(
     Ok(individual_reward)
 }
 
+/// Opens a new distribution round: folds `new_rewards` into the accumulator and
+/// rewinds the cursor so holders can be paid page by page.
+///
+/// Once every holder has been settled, whatever the previous rounds left unpaid is
+/// rounding dust, so it is rolled into this round instead of sitting in the pool.
+pub fn start_holder_reward_round(
+    cursor: &mut RewardDistributionCursor,
+    new_rewards: u64,
+    total_supply: u64,
+    holder_count: u64,
+) -> Result<()> {
+    let carried_dust = if cursor.is_complete() {
+        cursor.undistributed_rewards
+    } else {
+        0
+    };
+    let round_rewards = new_rewards
+        .checked_add(carried_dust)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    cursor.rewards_per_token = update_rewards_per_token(
+        cursor.rewards_per_token,
+        round_rewards,
+        total_supply,
+    )?;
+    cursor.undistributed_rewards = cursor
+        .undistributed_rewards
+        .checked_add(new_rewards)
+        .ok_or(SolSocialError::MathOverflow)?;
+    cursor.last_index = 0;
+    cursor.holder_count = holder_count;
+
+    Ok(())
+}
+
+/// Settles a single holder against the accumulator and advances their checkpoint,
+/// so paying the same holder twice in a round yields zero the second time.
+pub fn settle_holder_reward(
+    rewards_per_token: u64,
+    holder_balance: u64,
+    total_supply: u64,
+    last_claimed_rewards_per_token: &mut u64,
+) -> Result<u64> {
+    let reward = calculate_accumulated_rewards(
+        holder_balance,
+        total_supply,
+        rewards_per_token,
+        *last_claimed_rewards_per_token,
+    )?;
+    *last_claimed_rewards_per_token = rewards_per_token;
+
+    Ok(reward)
+}
+
+/// Books a payout against the cursor so the unpaid remainder stays exact.
+pub fn record_holder_payout(cursor: &mut RewardDistributionCursor, amount: u64) -> Result<()> {
+    cursor.total_distributed = cursor
+        .total_distributed
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    cursor.undistributed_rewards = cursor
+        .undistributed_rewards
+        .checked_sub(amount)
+        .ok_or(SolSocialError::MathUnderflow)?;
+
+    Ok(())
+}
+
+/// Pays one page of holders. Call repeatedly with the next slice of holders until
+/// `cursor.is_complete()`; each call fits within a single transaction.
 pub fn distribute_holder_rewards<'info>(
-    holder_accounts: &[Account<'info, TokenAccount>],
+    cursor: &mut Account<'info, RewardDistributionCursor>,
+    holders: &mut [Account<'info, TokenHolder>],
+    holder_token_accounts: &[Account<'info, TokenAccount>],
     vault_token_account: &Account<'info, TokenAccount>,
     vault_authority: &AccountInfo<'info>,
     token_program: &Program<'info, Token>,
-    total_rewards: u64,
-    holder_balances: &[u64],
     total_supply: u64,
     vault_authority_bump: u8,
 ) -> Result<()> {
     require!(
-        holder_accounts.len() == holder_balances.len(),
+        holders.len() == holder_token_accounts.len(),
         SolSocialError::MismatchedArrayLengths
     );
 
@@ -144,13 +233,13 @@ pub fn distribute_holder_rewards<'info>(
     ];
     let signer_seeds = &[&vault_authority_seeds[..]];
 
-    for (i, holder_account) in holder_accounts.iter().enumerate() {
-        let holder_balance = holder_balances[i];
-        let individual_reward = calculate_individual_holder_reward(
-            total_rewards,
-            holder_balance,
-            total_supply,
-        )?;
+    for (holder, holder_account) in holders.iter_mut().zip(holder_token_accounts.iter()) {
+        require!(
+            holder_account.owner == holder.holder,
+            SolSocialError::InvalidTokenAccountOwner
+        );
+
+        let individual_reward = holder.take_rewards(cursor.rewards_per_token, total_supply)?;
 
         if individual_reward > 0 {
             let transfer_ctx = CpiContext::new_with_signer(
@@ -164,26 +253,52 @@ pub fn distribute_holder_rewards<'info>(
             );
 
             token::transfer(transfer_ctx, individual_reward)?;
+
+            record_holder_payout(cursor, individual_reward)?;
         }
     }
 
+    cursor.last_index = cursor
+        .last_index
+        .checked_add(holders.len() as u64)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Accounts per post in a batch tip: post, creator profile, tipper token account,
+/// creator token account, treasury token account, revenue pool, holder rewards vault
+pub const BATCH_TIP_ACCOUNTS_PER_POST: usize = 7;
+/// Keeps a full batch inside the transaction account limit
+pub const MAX_BATCH_TIPS: usize = 4;
+
+pub fn validate_batch_tip(amounts: &[u64], remaining_accounts: usize) -> Result<()> {
+    require!(!amounts.is_empty(), SolSocialError::InvalidTipAmount);
+    require!(amounts.len() <= MAX_BATCH_TIPS, SolSocialError::TooManyBatchTips);
+    require!(
+        remaining_accounts == amounts.len() * BATCH_TIP_ACCOUNTS_PER_POST,
+        SolSocialError::BatchTipLengthMismatch
+    );
+    require!(amounts.iter().all(|&amount| amount > 0), SolSocialError::InvalidTipAmount);
     Ok(())
 }
 
 pub fn calculate_tip_distribution(
     tip_amount: u64,
     creator_tip_percentage: u8,
-    platform_tip_fee: u8,
+    platform_tip_fee_bps: u16,
 ) -> Result<(u64, u64, u64)> {
     require!(creator_tip_percentage <= 100, SolSocialError::InvalidSharePercentage);
-    require!(platform_tip_fee <= 100, SolSocialError::InvalidSharePercentage);
     require!(
-        creator_tip_percentage + platform_tip_fee <= 100,
+        creator_tip_percentage as u64 * 100 + platform_tip_fee_bps as u64 <= 10_000,
         SolSocialError::InvalidSharePercentage
     );
 
     let creator_share = calculate_creator_share(tip_amount, creator_tip_percentage)?;
-    let platform_share = calculate_platform_share(tip_amount, platform_tip_fee)?;
+    let platform_share = tip_amount
+        .checked_mul(platform_tip_fee_bps as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        / 10_000;
     let holder_share = tip_amount
         .checked_sub(creator_share)
         .ok_or(SolSocialError::MathOverflow)?
@@ -193,9 +308,34 @@ pub fn calculate_tip_distribution(
     Ok((creator_share, platform_share, holder_share))
 }
 
+/// Books one post's part of a batch tip on the post and the creator's revenue pool.
+/// Returns the (creator, platform, holder) shares the caller still has to transfer.
+pub fn apply_batch_tip(
+    post: &mut Post,
+    revenue_pool: &mut RevenuePool,
+    amount: u64,
+    creator_tip_percentage: u8,
+    platform_tip_fee_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    let (creator_share, platform_share, holder_share) =
+        calculate_tip_distribution(amount, creator_tip_percentage, platform_tip_fee_bps)?;
+
+    revenue_pool.holder_rewards_pool = revenue_pool.holder_rewards_pool
+        .checked_add(holder_share)
+        .ok_or(SolSocialError::MathOverflow)?;
+    post.tips_count = post.tips_count
+        .checked_add(1)
+        .ok_or(SolSocialError::MathOverflow)?;
+    post.total_tips_amount = post.total_tips_amount
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    Ok((creator_share, platform_share, holder_share))
+}
+
 pub fn process_revenue_distribution<'info>(
     revenue_pool: &mut Account<'info, RevenuePool>,
-    creator_profile: &Account<'info, CreatorProfile>,
+    creator_profile: &Account<'info, UserProfile>,
     vault_token_account: &Account<'info, TokenAccount>,
     creator_token_account: &Account<'info, TokenAccount>,
     platform_token_account: &Account<'info, TokenAccount>,
@@ -206,17 +346,7 @@ pub fn process_revenue_distribution<'info>(
     let total_revenue = revenue_pool.pending_revenue;
     require!(total_revenue > 0, SolSocialError::NoRevenueToDistribute);
 
-    let creator_share = calculate_creator_share(
-        total_revenue,
-        creator_profile.revenue_share_percentage,
-    )?;
-
-    let platform_share = calculate_platform_share(
-        total_revenue,
-        revenue_pool.platform_fee_percentage,
-    )?;
-
-    let holder_rewards = calculate_holder_rewards(
+    let (creator_share, platform_share, holder_rewards) = split_revenue(
         total_revenue,
         creator_profile.revenue_share_percentage,
         revenue_pool.platform_fee_percentage,
@@ -247,18 +377,7 @@ pub fn process_revenue_distribution<'info>(
     }
 
     // Update revenue pool state
-    revenue_pool.total_distributed = revenue_pool
-        .total_distributed
-        .checked_add(total_revenue)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    revenue_pool.pending_revenue = 0;
-    revenue_pool.holder_rewards_pool = revenue_pool
-        .holder_rewards_pool
-        .checked_add(holder_rewards)
-        .ok_or(SolSocialError::MathOverflow)?;
-
-    revenue_pool.last_distribution_timestamp = Clock::get()?.unix_timestamp;
+    revenue_pool.settle_distribution(holder_rewards, Clock::get()?.unix_timestamp)?;
 
     Ok(())
 }
@@ -270,7 +389,7 @@ pub fn validate_revenue_distribution_params(
     require!(creator_share_percentage <= 100, SolSocialError::InvalidSharePercentage);
     require!(platform_fee_percentage <= 100, SolSocialError::InvalidSharePercentage);
     require!(
-        creator_share_percentage + platform_fee_percentage <= 100,
+        creator_share_percentage as u16 + platform_fee_percentage as u16 <= 100,
         SolSocialError::InvalidSharePercentage
     );
     
@@ -317,4 +436,210 @@ pub fn update_rewards_per_token(
     
     Ok(updated_rewards_per_token)
 }
-```
\ No newline at end of file
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_tip_needs_one_account_group_per_amount() {
+        let amounts = [1_000, 2_000, 3_000];
+        assert!(validate_batch_tip(&amounts, 3 * BATCH_TIP_ACCOUNTS_PER_POST).is_ok());
+        assert!(validate_batch_tip(&amounts, 2 * BATCH_TIP_ACCOUNTS_PER_POST).is_err());
+        assert!(validate_batch_tip(&[1_000, 0, 3_000], 3 * BATCH_TIP_ACCOUNTS_PER_POST).is_err());
+        assert!(validate_batch_tip(&[], 0).is_err());
+
+        let too_many = vec![1_000; MAX_BATCH_TIPS + 1];
+        assert!(validate_batch_tip(&too_many, too_many.len() * BATCH_TIP_ACCOUNTS_PER_POST).is_err());
+    }
+
+    #[test]
+    fn test_batch_tip_three_posts() {
+        let amounts = [1_000, 2_000, 3_000];
+        let creator_percentages = [90, 80, 97];
+        let mut posts = [Post::default(), Post::default(), Post::default()];
+        let mut pools = [RevenuePool::default(), RevenuePool::default(), RevenuePool::default()];
+        posts[1].total_tips_amount = 500;
+        posts[1].tips_count = 1;
+        assert!(validate_batch_tip(&amounts, amounts.len() * BATCH_TIP_ACCOUNTS_PER_POST).is_ok());
+
+        let shares: Vec<(u64, u64, u64)> = posts
+            .iter_mut()
+            .zip(pools.iter_mut())
+            .zip(amounts.iter().zip(creator_percentages))
+            .map(|((post, pool), (&amount, percentage))| {
+                apply_batch_tip(post, pool, amount, percentage, 200).unwrap()
+            })
+            .collect();
+
+        assert_eq!(shares, vec![(900, 20, 80), (1_600, 40, 360), (2_910, 60, 30)]);
+        for ((&(creator, platform, holders), pool), amount) in shares.iter().zip(&pools).zip(amounts) {
+            assert_eq!(creator + platform + holders, amount);
+            assert_eq!(pool.holder_rewards_pool, holders);
+        }
+        assert_eq!(
+            posts.iter().map(|post| (post.tips_count, post.total_tips_amount)).collect::<Vec<_>>(),
+            vec![(1, 1_000), (2, 2_500), (1, 3_000)]
+        );
+    }
+
+    #[test]
+    fn test_paginated_distribution_pays_each_holder_once() {
+        let balances: Vec<u64> = (1..=200).map(|i| i * 1_000).collect();
+        let total_supply: u64 = balances.iter().sum();
+        let mut checkpoints = vec![0u64; balances.len()];
+
+        let mut cursor = RewardDistributionCursor::default();
+        let new_rewards = 10_000_000_000;
+        start_holder_reward_round(&mut cursor, new_rewards, total_supply, balances.len() as u64).unwrap();
+
+        let mut paid = vec![0u64; balances.len()];
+        while !cursor.is_complete() {
+            let start = cursor.last_index as usize;
+            let end = (start + 30).min(balances.len());
+            for i in start..end {
+                paid[i] += settle_holder_reward(
+                    cursor.rewards_per_token,
+                    balances[i],
+                    total_supply,
+                    &mut checkpoints[i],
+                )
+                .unwrap();
+            }
+            cursor.last_index = end as u64;
+        }
+
+        // Replaying an already-settled page pays nothing
+        for i in 0..30 {
+            let again = settle_holder_reward(
+                cursor.rewards_per_token,
+                balances[i],
+                total_supply,
+                &mut checkpoints[i],
+            )
+            .unwrap();
+            assert_eq!(again, 0);
+        }
+
+        for (i, balance) in balances.iter().enumerate() {
+            let expected = balance * cursor.rewards_per_token / 1_000_000;
+            assert_eq!(paid[i], expected);
+        }
+        assert!(paid.iter().sum::<u64>() <= new_rewards);
+    }
+
+    #[test]
+    fn test_claim_receive_more_then_claim_again() {
+        let total_supply = 1_000u64;
+        let mut cursor = RewardDistributionCursor::default();
+        let mut checkpoint = 0u64;
+
+        start_holder_reward_round(&mut cursor, 2_000, total_supply, 1).unwrap();
+        let first = settle_holder_reward(cursor.rewards_per_token, 250, total_supply, &mut checkpoint).unwrap();
+        assert_eq!(first, 500);
+
+        // A second claim before new rewards arrive owes nothing
+        let repeat = settle_holder_reward(cursor.rewards_per_token, 250, total_supply, &mut checkpoint).unwrap();
+        assert_eq!(repeat, 0);
+
+        start_holder_reward_round(&mut cursor, 4_000, total_supply, 1).unwrap();
+        let second = settle_holder_reward(cursor.rewards_per_token, 250, total_supply, &mut checkpoint).unwrap();
+        assert_eq!(second, 1_000);
+    }
+
+    #[test]
+    fn test_late_holder_collects_missed_rounds() {
+        let total_supply = 1_000u64;
+        let mut cursor = RewardDistributionCursor::default();
+        let mut checkpoint = 0u64;
+
+        start_holder_reward_round(&mut cursor, 5_000, total_supply, 1).unwrap();
+        start_holder_reward_round(&mut cursor, 5_000, total_supply, 1).unwrap();
+
+        let reward = settle_holder_reward(cursor.rewards_per_token, 100, total_supply, &mut checkpoint).unwrap();
+        assert_eq!(reward, 1_000);
+    }
+
+    #[test]
+    fn test_rounding_dust_is_carried_across_rounds() {
+        // Many tiny holders make nearly every payout round down
+        let balances: Vec<u64> = (0..1_000).map(|i| 1 + i % 7).collect();
+        let total_supply: u64 = balances.iter().sum();
+        let mut checkpoints = vec![0u64; balances.len()];
+        let mut cursor = RewardDistributionCursor::default();
+
+        let rounds = [997u64, 13, 1, 250_003, 7, 4_999, 1_000_000, 3];
+        let mut total_in = 0u64;
+        let mut total_paid = 0u64;
+        for new_rewards in rounds {
+            start_holder_reward_round(&mut cursor, new_rewards, total_supply, balances.len() as u64).unwrap();
+            total_in += new_rewards;
+
+            for i in 0..balances.len() {
+                let reward = settle_holder_reward(
+                    cursor.rewards_per_token,
+                    balances[i],
+                    total_supply,
+                    &mut checkpoints[i],
+                )
+                .unwrap();
+                record_holder_payout(&mut cursor, reward).unwrap();
+                total_paid += reward;
+            }
+            cursor.last_index = balances.len() as u64;
+
+            assert_eq!(total_paid + cursor.undistributed_rewards, total_in);
+            assert_eq!(cursor.total_distributed, total_paid);
+            // What is left is bounded by one unit per holder, not by the number of rounds
+            assert!(cursor.undistributed_rewards <= balances.len() as u64 + 1);
+        }
+    }
+
+    #[test]
+    fn test_reward_too_small_for_accumulator_is_not_lost() {
+        let total_supply = 2_000_000u64;
+        let mut checkpoint = 0u64;
+        let mut cursor = RewardDistributionCursor::default();
+
+        start_holder_reward_round(&mut cursor, 1, total_supply, 1).unwrap();
+        assert_eq!(cursor.rewards_per_token, 0);
+        assert_eq!(cursor.undistributed_rewards, 1);
+        cursor.last_index = 1;
+
+        start_holder_reward_round(&mut cursor, 3, total_supply, 1).unwrap();
+        let reward = settle_holder_reward(cursor.rewards_per_token, total_supply, total_supply, &mut checkpoint).unwrap();
+        record_holder_payout(&mut cursor, reward).unwrap();
+        assert_eq!(reward, 4);
+        assert_eq!(cursor.undistributed_rewards, 0);
+    }
+
+    #[test]
+    fn test_tip_shares_sum_to_tip() {
+        let (creator, platform, holders) = calculate_tip_distribution(
+            10_000,
+            UserProfile::DEFAULT_CREATOR_TIP_PERCENTAGE,
+            200,
+        )
+        .unwrap();
+        assert_eq!((creator, platform, holders), (9_000, 200, 800));
+
+        // Rounding dust from the percentage shares goes to holders
+        let (creator, platform, holders) = calculate_tip_distribution(999, 90, 200).unwrap();
+        assert_eq!(creator + platform + holders, 999);
+        assert_eq!(holders, 999 - 899 - 19);
+
+        assert!(calculate_tip_distribution(1_000, 99, 200).is_err());
+    }
+
+    #[test]
+    fn test_platform_tip_fee_in_basis_points() {
+        let (creator, platform, holders) = calculate_tip_distribution(10_000, 90, 250).unwrap();
+        assert_eq!(platform, 250);
+        assert_eq!(creator, 9_000);
+        assert_eq!(holders, 10_000 - 9_000 - 250);
+
+        // With no holder share the creator gets everything but the fee
+        let (creator, platform, holders) = calculate_tip_distribution(10_000, 97, 300).unwrap();
+        assert_eq!((creator, platform, holders), (9_700, 300, 0));
+    }
+}
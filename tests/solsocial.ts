@@ -1,9 +1,8 @@
-```typescript
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Solsocial } from "../target/types/solsocial";
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
 import { expect } from "chai";
 
 describe("solsocial", () => {
@@ -11,6 +10,46 @@ describe("solsocial", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Solsocial as Program<Solsocial>;
+  const [globalState] = PublicKey.findProgramAddressSync(
+    [Buffer.from("global_state")],
+    program.programId
+  );
+
+  const setEmergencyPause = (paused: boolean) => program.methods
+    .setEmergencyPause(paused)
+    .accounts({
+      globalState,
+      authority: provider.wallet.publicKey,
+    })
+    .rpc();
+
+  const expectEmergencyPaused = async (attempt: () => Promise<string>) => {
+    await setEmergencyPause(true);
+    try {
+      await attempt();
+      expect.fail("instruction should have been rejected while paused");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EmergencyPauseActivated");
+    } finally {
+      await setEmergencyPause(false);
+    }
+  };
+  
+  // Trading fees are paid to the treasury, so make sure one is configured
+  const platformTreasury = async (): Promise<PublicKey> => {
+    const state = await program.account.globalState.fetch(globalState);
+    if (!state.treasury.equals(PublicKey.default)) {
+      return state.treasury;
+    }
+    await program.methods
+      .setPlatformTipFee(state.platformTipFeeBps, provider.wallet.publicKey)
+      .accounts({
+        globalState,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    return provider.wallet.publicKey;
+  };
   
   let userKeypair: Keypair;
   let creatorKeypair: Keypair;
@@ -92,17 +131,17 @@ describe("solsocial", () => {
 
     // Derive PDAs
     [userProfile] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), userKeypair.publicKey.toBuffer()],
+      [Buffer.from("user_profile"), userKeypair.publicKey.toBuffer()],
       program.programId
     );
 
     [creatorProfile] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), creatorKeypair.publicKey.toBuffer()],
+      [Buffer.from("user_profile"), creatorKeypair.publicKey.toBuffer()],
       program.programId
     );
 
     [followerProfile] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), followerKeypair.publicKey.toBuffer()],
+      [Buffer.from("user_profile"), followerKeypair.publicKey.toBuffer()],
       program.programId
     );
 
@@ -233,6 +272,7 @@ describe("solsocial", () => {
       .accounts({
         followerProfile: followerProfile,
         targetProfile: creatorProfile,
+        globalState,
         follower: followerKeypair.publicKey,
       })
       .signers([followerKeypair])
@@ -298,6 +338,7 @@ describe("solsocial", () => {
       .accounts({
         post: post,
         interaction: interaction,
+        globalState,
         user: followerKeypair.publicKey,
         userProfile: followerProfile,
         userTokenAccount: followerTokenAccount,
@@ -381,6 +422,1059 @@ describe("solsocial", () => {
     expect(commentAccount.content).to.equal(commentContent);
   });
 
+  it("Emits PostCreated with the new post's fields", async () => {
+    const author = Keypair.generate();
+    await provider.connection.requestAirdrop(author.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), author.publicKey.toBuffer()],
+      program.programId
+    );
+    const [postAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("post"),
+        author.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8)
+      ],
+      program.programId
+    );
+
+    const before = await program.account.globalState.fetch(globalState);
+
+    await program.methods
+      .initializeUser("eventauthor", "Emits events")
+      .accounts({
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    const content = "Indexers should see this post";
+    let event: any;
+    const listener = program.addEventListener("postCreated", (e) => { event = e; });
+
+    await program.methods
+      .createPost(content, null)
+      .accounts({
+        postAccount,
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(event).to.not.be.undefined;
+    expect(event.post.toString()).to.equal(postAccount.toString());
+    expect(event.author.toString()).to.equal(author.publicKey.toString());
+    expect(event.user.toString()).to.equal(userAccount.toString());
+    expect(event.contentPreview).to.equal(content);
+    expect(event.postsCount.toNumber()).to.equal(1);
+
+    const after = await program.account.globalState.fetch(globalState);
+    expect(after.totalUsers.toNumber()).to.equal(before.totalUsers.toNumber() + 1);
+    expect(after.totalPosts.toNumber()).to.equal(before.totalPosts.toNumber() + 1);
+  });
+
+  it("Emits UserFollowed with updated counts", async () => {
+    const follower = Keypair.generate();
+    const followed = Keypair.generate();
+    await provider.connection.requestAirdrop(follower.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(followed.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [followerAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), follower.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followingAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), followed.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("follow"), follower.publicKey.toBuffer(), followingAccount.toBuffer()],
+      program.programId
+    );
+    const [blockRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), followed.publicKey.toBuffer(), follower.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const [keypair, userAccount, username] of [
+      [follower, followerAccount, "eventfollower"],
+      [followed, followingAccount, "eventfollowed"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(username, "")
+        .accounts({
+          userAccount,
+          globalState,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    let event: any;
+    const listener = program.addEventListener("userFollowed", (e) => { event = e; });
+
+    await program.methods
+      .followUser()
+      .accounts({
+        followAccount,
+        followerAccount,
+        followingAccount,
+        blockRecord,
+        globalState,
+        follower: follower.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([follower])
+      .rpc();
+
+    await new Promise(resolve => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(event).to.not.be.undefined;
+    expect(event.follower.toString()).to.equal(followerAccount.toString());
+    expect(event.following.toString()).to.equal(followingAccount.toString());
+    expect(event.followingCount.toNumber()).to.equal(1);
+    expect(event.followersCount.toNumber()).to.equal(1);
+  });
+
+  it("Rejects a second like with AlreadyLiked", async () => {
+    const author = Keypair.generate();
+    const liker = Keypair.generate();
+    await provider.connection.requestAirdrop(author.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(liker.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), author.publicKey.toBuffer()],
+      program.programId
+    );
+    const [postAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("post"),
+        author.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8)
+      ],
+      program.programId
+    );
+    const [likeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("like"), liker.publicKey.toBuffer(), postAccount.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeUser("likedauthor", "")
+      .accounts({
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    await program.methods
+      .createPost("Like me once", null)
+      .accounts({
+        postAccount,
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    const like = () => program.methods
+      .likePost()
+      .accounts({
+        likeAccount,
+        postAccount,
+        globalState,
+        authority: liker.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([liker])
+      .rpc();
+
+    await like();
+
+    try {
+      await like();
+      expect.fail("second like should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyLiked");
+    }
+
+    const post = await program.account.postAccount.fetch(postAccount);
+    expect(post.likesCount.toNumber()).to.equal(1);
+  });
+
+  it("Rejects user token trades past max cost or after the deadline", async () => {
+    const creator = Keypair.generate();
+    const buyer = Keypair.generate();
+    await provider.connection.requestAirdrop(creator.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(buyer.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [buyerTokenAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_tokens"), buyer.publicKey.toBuffer(), userAccount.toBuffer()],
+      program.programId
+    );
+    const [tokenEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_escrow"), userAccount.toBuffer()],
+      program.programId
+    );
+    const [creatorToken] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_token"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [rewardCursor] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_cursor"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [tokenHolder] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_holder"), creator.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+      program.programId
+    );
+    const [globalState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_state")],
+      program.programId
+    );
+
+    await program.methods
+      .initializeUser("slippagecreator", "")
+      .accounts({
+        userAccount,
+        globalState,
+        authority: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const treasury = await platformTreasury();
+    const buy = (maxCost: anchor.BN, deadline: anchor.BN) => program.methods
+      .buyUserTokens(new anchor.BN(10), maxCost, deadline, null)
+      .accounts({
+        userAccount,
+        buyerTokenAccount,
+        tokenEscrow,
+        creatorToken,
+        rewardCursor,
+        tokenHolder,
+        globalState,
+        traderProfile: null,
+        treasury,
+        buyer: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await buy(new anchor.BN(1), new anchor.BN(now + 60));
+      expect.fail("buy above max cost should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("SlippageToleranceExceeded");
+    }
+
+    try {
+      await buy(new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(now - 60));
+      expect.fail("expired buy should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TransactionExpired");
+    }
+
+    const user = await program.account.userProfile.fetch(userAccount);
+    expect(user.tokenSupply.toNumber()).to.equal(0);
+  });
+
+  it("Rejects user token buys that move the price past the impact cap", async () => {
+    const creator = Keypair.generate();
+    const buyer = Keypair.generate();
+    await provider.connection.requestAirdrop(creator.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(buyer.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [buyerTokenAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_tokens"), buyer.publicKey.toBuffer(), userAccount.toBuffer()],
+      program.programId
+    );
+    const [tokenEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_escrow"), userAccount.toBuffer()],
+      program.programId
+    );
+    const [creatorToken] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_token"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [rewardCursor] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_cursor"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [tokenHolder] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_holder"), creator.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+      program.programId
+    );
+    const [globalState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_state")],
+      program.programId
+    );
+
+    await program.methods
+      .initializeUser("impactcreator", "")
+      .accounts({
+        userAccount,
+        globalState,
+        authority: creator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const treasury = await platformTreasury();
+    const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 60);
+    const buy = (amount: number, maxImpactBps: anchor.BN) => program.methods
+      .buyUserTokens(new anchor.BN(amount), new anchor.BN(LAMPORTS_PER_SOL), deadline, maxImpactBps)
+      .accounts({
+        userAccount,
+        buyerTokenAccount,
+        tokenEscrow,
+        creatorToken,
+        rewardCursor,
+        tokenHolder,
+        globalState,
+        traderProfile: null,
+        treasury,
+        buyer: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    // Ten tokens barely move the curve
+    await buy(10, new anchor.BN(100));
+
+    // 100k tokens raise the spot price roughly elevenfold
+    try {
+      await buy(100_000, new anchor.BN(500));
+      expect.fail("high-impact buy should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PriceDeviationTooHigh");
+    }
+
+    const user = await program.account.userProfile.fetch(userAccount);
+    expect(user.tokenSupply.toNumber()).to.equal(10);
+    const holder = await program.account.tokenHolder.fetch(tokenHolder);
+    expect(holder.amount.toNumber()).to.equal(10);
+  });
+
+  it("Blocks follows until the block is lifted", async () => {
+    const blocker = Keypair.generate();
+    const blocked = Keypair.generate();
+    await provider.connection.requestAirdrop(blocker.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(blocked.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [blockerAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), blocker.publicKey.toBuffer()],
+      program.programId
+    );
+    const [blockedAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), blocked.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("follow"), blocked.publicKey.toBuffer(), blockerAccount.toBuffer()],
+      program.programId
+    );
+    const [blockRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), blocker.publicKey.toBuffer(), blocked.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const [keypair, userAccount, username] of [
+      [blocker, blockerAccount, "blocker"],
+      [blocked, blockedAccount, "blocked"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(username, "")
+        .accounts({
+          userAccount,
+          globalState,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    const follow = () => program.methods
+      .followUser()
+      .accounts({
+        followAccount,
+        followerAccount: blockedAccount,
+        followingAccount: blockerAccount,
+        blockRecord,
+        globalState,
+        follower: blocked.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([blocked])
+      .rpc();
+
+    await follow();
+
+    // Blocking drops the existing follow along with both counts
+    await program.methods
+      .blockUser()
+      .accounts({
+        blockRecord,
+        blockerAccount,
+        blockedAccount,
+        existingFollow: followAccount,
+        blocked: blocked.publicKey,
+        globalState,
+        blocker: blocker.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([blocker])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(followAccount)).to.be.null;
+    let blockerUser = await program.account.userProfile.fetch(blockerAccount);
+    expect(blockerUser.followersCount.toNumber()).to.equal(0);
+
+    try {
+      await follow();
+      expect.fail("follow from a blocked user should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("UserBlocked");
+    }
+
+    const unblock = () => program.methods
+      .unblockUser()
+      .accounts({
+        blockRecord,
+        blocked: blocked.publicKey,
+        globalState,
+        blocker: blocker.publicKey,
+      })
+      .signers([blocker])
+      .rpc();
+
+    // The block outlives an emergency pause
+    await expectEmergencyPaused(unblock);
+    await unblock();
+
+    await follow();
+    blockerUser = await program.account.userProfile.fetch(blockerAccount);
+    expect(blockerUser.followersCount.toNumber()).to.equal(1);
+  });
+
+  it("Mutes and unmutes a feed without touching follows", async () => {
+    const muter = Keypair.generate();
+    const muted = Keypair.generate();
+    await provider.connection.requestAirdrop(muter.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [feedMute] = PublicKey.findProgramAddressSync(
+      [Buffer.from("feed_mute"), muter.publicKey.toBuffer(), muted.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const mute = () => program.methods
+      .muteFeed()
+      .accounts({
+        feedMute,
+        muted: muted.publicKey,
+        globalState,
+        muter: muter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([muter])
+      .rpc();
+
+    await mute();
+    // A second mute leaves the same record in place
+    await mute();
+
+    const record = await program.account.feedMute.fetch(feedMute);
+    expect(record.muter.toString()).to.equal(muter.publicKey.toString());
+    expect(record.muted.toString()).to.equal(muted.publicKey.toString());
+
+    const unmute = () => program.methods
+      .unmuteFeed()
+      .accounts({
+        feedMute,
+        muted: muted.publicKey,
+        globalState,
+        muter: muter.publicKey,
+      })
+      .signers([muter])
+      .rpc();
+
+    await expectEmergencyPaused(unmute);
+    expect(await provider.connection.getAccountInfo(feedMute)).to.not.be.null;
+    await unmute();
+
+    expect(await provider.connection.getAccountInfo(feedMute)).to.be.null;
+  });
+
+  it("Rejects double follows, self follows and unfollows without a follow", async () => {
+    const follower = Keypair.generate();
+    const followed = Keypair.generate();
+    await provider.connection.requestAirdrop(follower.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(followed.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [followerAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), follower.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followingAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), followed.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const [keypair, userAccount, username] of [
+      [follower, followerAccount, "doublefollower"],
+      [followed, followingAccount, "doublefollowed"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(username, "")
+        .accounts({
+          userAccount,
+          globalState,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    const followAccountFor = (target: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("follow"), follower.publicKey.toBuffer(), target.toBuffer()],
+      program.programId
+    )[0];
+    const blockRecordFor = (targetAuthority: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), targetAuthority.toBuffer(), follower.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const follow = (target: PublicKey, targetAuthority: PublicKey) => program.methods
+      .followUser()
+      .accounts({
+        followAccount: followAccountFor(target),
+        followerAccount,
+        followingAccount: target,
+        blockRecord: blockRecordFor(targetAuthority),
+        follower: follower.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([follower])
+      .rpc();
+
+    const unfollow = () => program.methods
+      .unfollowUser()
+      .accounts({
+        followAccount: followAccountFor(followingAccount),
+        followerAccount,
+        followingAccount,
+        globalState,
+        follower: follower.publicKey,
+      })
+      .signers([follower])
+      .rpc();
+
+    try {
+      await unfollow();
+      expect.fail("unfollow without a follow should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotFollowing");
+    }
+
+    try {
+      await follow(followerAccount, follower.publicKey);
+      expect.fail("self follow should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("CannotFollowSelf");
+    }
+
+    await follow(followingAccount, followed.publicKey);
+
+    try {
+      await follow(followingAccount, followed.publicKey);
+      expect.fail("second follow should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AlreadyFollowing");
+    }
+
+    const user = await program.account.userProfile.fetch(followingAccount);
+    expect(user.followersCount.toNumber()).to.equal(1);
+  });
+
+  it("Requests and approves a follow of a private profile", async () => {
+    const requester = Keypair.generate();
+    const target = Keypair.generate();
+    await provider.connection.requestAirdrop(requester.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(target.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [requesterAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), requester.publicKey.toBuffer()],
+      program.programId
+    );
+    const [targetAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), target.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followRequest] = PublicKey.findProgramAddressSync(
+      [Buffer.from("follow_request"), requester.publicKey.toBuffer(), targetAccount.toBuffer()],
+      program.programId
+    );
+    const [followAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("follow"), requester.publicKey.toBuffer(), targetAccount.toBuffer()],
+      program.programId
+    );
+    const [blockRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), target.publicKey.toBuffer(), requester.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const [keypair, userAccount, username] of [
+      [requester, requesterAccount, "requester"],
+      [target, targetAccount, "privatetarget"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(username, "")
+        .accounts({
+          userAccount,
+          globalState,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    await program.methods
+      .setProfilePrivacy(true)
+      .accounts({
+        userAccount: targetAccount,
+        globalState,
+        authority: target.publicKey,
+      })
+      .signers([target])
+      .rpc();
+
+    const request = () => program.methods
+      .requestFollow()
+      .accounts({
+        followRequest,
+        followerAccount: requesterAccount,
+        followingAccount: targetAccount,
+        blockRecord,
+        globalState,
+        follower: requester.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([requester])
+      .rpc();
+
+    await request();
+
+    const pending = await program.account.followRequest.fetch(followRequest);
+    expect(pending.requester.toString()).to.equal(requester.publicKey.toString());
+    expect(pending.target.toString()).to.equal(targetAccount.toString());
+
+    // The pending request PDA already exists, so a second request cannot be created
+    try {
+      await request();
+      expect.fail("duplicate follow request should have been rejected");
+    } catch (err: any) {
+      expect(err.logs.join("\n")).to.include("already in use");
+    }
+
+    await program.methods
+      .approveFollow()
+      .accounts({
+        followRequest,
+        followAccount,
+        followerAccount: requesterAccount,
+        followingAccount: targetAccount,
+        requester: requester.publicKey,
+        globalState,
+        authority: target.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([target])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(followRequest)).to.be.null;
+    const follow = await program.account.followAccount.fetch(followAccount);
+    expect(follow.follower.toString()).to.equal(requesterAccount.toString());
+    expect(follow.following.toString()).to.equal(targetAccount.toString());
+
+    const requesterUser = await program.account.userProfile.fetch(requesterAccount);
+    const targetUser = await program.account.userProfile.fetch(targetAccount);
+    expect(requesterUser.followingCount.toNumber()).to.equal(1);
+    expect(targetUser.followersCount.toNumber()).to.equal(1);
+  });
+
+  it("Keeps follow requests pending while emergency paused", async () => {
+    const requester = Keypair.generate();
+    const target = Keypair.generate();
+    await provider.connection.requestAirdrop(requester.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(target.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [requesterAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), requester.publicKey.toBuffer()],
+      program.programId
+    );
+    const [targetAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), target.publicKey.toBuffer()],
+      program.programId
+    );
+    const [followRequest] = PublicKey.findProgramAddressSync(
+      [Buffer.from("follow_request"), requester.publicKey.toBuffer(), targetAccount.toBuffer()],
+      program.programId
+    );
+    const [blockRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), target.publicKey.toBuffer(), requester.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const [keypair, userAccount, username] of [
+      [requester, requesterAccount, "pausedrequester"],
+      [target, targetAccount, "pausedtarget"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(username, "")
+        .accounts({
+          userAccount,
+          globalState,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+    }
+
+    await program.methods
+      .setProfilePrivacy(true)
+      .accounts({
+        userAccount: targetAccount,
+        globalState,
+        authority: target.publicKey,
+      })
+      .signers([target])
+      .rpc();
+
+    await program.methods
+      .requestFollow()
+      .accounts({
+        followRequest,
+        followerAccount: requesterAccount,
+        followingAccount: targetAccount,
+        blockRecord,
+        globalState,
+        follower: requester.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([requester])
+      .rpc();
+
+    const reject = () => program.methods
+      .rejectFollow()
+      .accounts({
+        followRequest,
+        followingAccount: targetAccount,
+        requester: requester.publicKey,
+        globalState,
+        authority: target.publicKey,
+      })
+      .signers([target])
+      .rpc();
+    const cancel = () => program.methods
+      .cancelFollowRequest()
+      .accounts({
+        followRequest,
+        followingAccount: targetAccount,
+        globalState,
+        follower: requester.publicKey,
+      })
+      .signers([requester])
+      .rpc();
+
+    await expectEmergencyPaused(reject);
+    await expectEmergencyPaused(cancel);
+    expect(await provider.connection.getAccountInfo(followRequest)).to.not.be.null;
+
+    await cancel();
+    expect(await provider.connection.getAccountInfo(followRequest)).to.be.null;
+  });
+
+  it("Pages an author's posts from get_post_count", async () => {
+    const author = Keypair.generate();
+    await provider.connection.requestAirdrop(author.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [userAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), author.publicKey.toBuffer()],
+      program.programId
+    );
+    const postPda = (index: number) => PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("post"),
+        author.publicKey.toBuffer(),
+        new anchor.BN(index).toArrayLike(Buffer, "le", 8)
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .initializeUser("pagedauthor", "")
+      .accounts({
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    await program.methods
+      .createPost("First page", null)
+      .accounts({
+        postAccount: postPda(0),
+        userAccount,
+        globalState,
+        authority: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    const simulation = await program.methods
+      .getPostCount()
+      .accounts({ userAccount, author: author.publicKey })
+      .simulate();
+    const returnLog = simulation.raw.find((log) => log.startsWith("Program return: "));
+    const count = new anchor.BN(
+      Buffer.from(returnLog!.split(" ").pop()!, "base64"),
+      "le"
+    ).toNumber();
+    expect(count).to.equal(1);
+
+    for (let index = 0; index < count; index++) {
+      const post = await program.account.postAccount.fetch(postPda(index));
+      expect(post.authority.toString()).to.equal(author.publicKey.toString());
+      expect(post.content).to.equal("First page");
+    }
+  });
+
+  it("Pays a key sell out of the reserve and the protocol fee to the protocol", async () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    await provider.connection.requestAirdrop(creator.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(trader.publicKey, 2 * LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 1000));
+
+    const [creatorAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [traderAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+      program.programId
+    );
+    const [protocolConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol")],
+      program.programId
+    );
+    const [reserveAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [keyHolding] = PublicKey.findProgramAddressSync(
+      [Buffer.from("keys"), creator.publicKey.toBuffer(), trader.publicKey.toBuffer()],
+      program.programId
+    );
+    const [userKeys] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [tokenPrice] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_price"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [creatorToken] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_token"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [rewardCursor] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_cursor"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [tokenHolder] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_holder"), creator.publicKey.toBuffer(), trader.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const config = await program.account.protocolConfig.fetch(protocolConfig);
+    const paymentMint = config.paymentMint;
+    const ata = async (owner: PublicKey) => (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      userKeypair,
+      paymentMint,
+      owner,
+      true
+    )).address;
+    const traderTokenAccount = await ata(trader.publicKey);
+    const creatorTokenAccount = await ata(creator.publicKey);
+    const protocolFeeAccount = await ata(protocolConfig);
+    const reserveVault = await ata(reserveAuthority);
+    await mintTo(provider.connection, userKeypair, paymentMint, traderTokenAccount, userKeypair, 100 * 10**9);
+
+    for (const [owner, account, name] of [
+      [creator, creatorAccount, "reservecreator"],
+      [trader, traderAccount, "reservetrader"],
+    ] as [Keypair, PublicKey, string][]) {
+      await program.methods
+        .initializeUser(name, "")
+        .accounts({
+          userAccount: account,
+          globalState,
+          authority: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    }
+
+    await program.methods
+      .createKeys("reservecreator")
+      .accounts({
+        creator: creator.publicKey,
+        creatorProfile: creatorAccount,
+        globalState,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .buyKeys(new anchor.BN(5), new anchor.BN(100 * 10**9))
+      .accounts({
+        buyer: trader.publicKey,
+        userAccount: creatorAccount,
+        buyerAccount: traderAccount,
+        subject: creator.publicKey,
+        keyHolding,
+        userKeys,
+        globalState,
+        protocolConfig,
+        buyerTokenAccount: traderTokenAccount,
+        subjectTokenAccount: creatorTokenAccount,
+        protocolFeeAccount,
+        reserveAuthority,
+        reserveVault,
+        referral: null,
+        referrerTokenAccount: null,
+        tokenPrice,
+        creatorToken,
+        rewardCursor,
+        tokenHolder,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([trader])
+      .rpc();
+
+    const balance = async (account: PublicKey) =>
+      BigInt((await getAccount(provider.connection, account)).amount.toString());
+    const reserveAfterBuy = await balance(reserveVault);
+    const traderBefore = await balance(traderTokenAccount);
+    const creatorBefore = await balance(creatorTokenAccount);
+    const protocolBefore = await balance(protocolFeeAccount);
+    expect(reserveAfterBuy > 0n).to.be.true;
+
+    const sell = (amount: number) => program.methods
+      .sellKeys(new anchor.BN(amount), new anchor.BN(0))
+      .accounts({
+        seller: trader.publicKey,
+        userAccount: traderAccount,
+        subjectAccount: creatorAccount,
+        subject: creator.publicKey,
+        keyHolding,
+        userKeys,
+        globalState,
+        protocolConfig,
+        sellerTokenAccount: traderTokenAccount,
+        subjectTokenAccount: creatorTokenAccount,
+        protocolFeeAccount,
+        reserveAuthority,
+        reserveVault,
+        solMint: paymentMint,
+        tokenPrice,
+        creatorToken,
+        rewardCursor,
+        tokenHolder,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([trader])
+      .rpc();
+    await sell(3);
+
+    const reserveAfterSell = await balance(reserveVault);
+    const sellerGain = (await balance(traderTokenAccount)) - traderBefore;
+    const subjectGain = (await balance(creatorTokenAccount)) - creatorBefore;
+    const protocolGain = (await balance(protocolFeeAccount)) - protocolBefore;
+
+    // Every lamport leaving the reserve lands with the seller, the subject or the protocol
+    expect(reserveAfterBuy - reserveAfterSell).to.equal(sellerGain + subjectGain + protocolGain);
+    expect(protocolGain > 0n).to.be.true;
+    // The two keys still held stay backed
+    expect(reserveAfterSell > 0n).to.be.true;
+
+    const holding = await program.account.keyHolding.fetch(keyHolding);
+    expect(holding.amount.toNumber()).to.equal(2);
+    const subject = await program.account.userProfile.fetch(creatorAccount);
+    // Genesis key plus the two still held
+    expect(subject.tokenSupply.toNumber()).to.equal(3);
+    // The creator and the trader; a partial sell keeps the trader counted
+    expect((await program.account.userKeys.fetch(userKeys)).holdersCount).to.equal(2);
+
+    await sell(2);
+    expect((await program.account.userKeys.fetch(userKeys)).holdersCount).to.equal(1);
+  });
+
   it("Sells social tokens", async () => {
     const amount = new anchor.BN(5 * 10**9); // 5 tokens
     const minPrice = new anchor.BN(500000); // 0.0005 SOL min